@@ -0,0 +1,33 @@
+create table task_dependencies
+(
+    id             uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at     timestamp with time zone default now()             not null,
+    updated_at     timestamp with time zone default now()             not null,
+    owner_id       uuid                                               not null
+        references members
+            on update cascade on delete set null,
+    source_task_id uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    target_task_id uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    kind           varchar                                            not null,
+    unique (source_task_id, target_task_id, kind),
+    check (source_task_id <> target_task_id)
+);
+
+create index task_dependencies_source_task_id_idx
+    on task_dependencies (source_task_id);
+
+create index task_dependencies_target_task_id_idx
+    on task_dependencies (target_task_id);
+
+create trigger set_public_task_dependencies_updated_at
+    before update
+    on task_dependencies
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_task_dependencies_updated_at on task_dependencies is 'trigger to set value of column "updated_at" to current timestamp on row update';
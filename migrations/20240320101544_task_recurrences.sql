@@ -0,0 +1,56 @@
+create table task_recurrences
+(
+    id                 uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at         timestamp with time zone default now()             not null,
+    updated_at         timestamp with time zone default now()             not null,
+    owner_id           uuid                                               not null
+        references members
+            on update cascade on delete set null,
+    template_task_id   uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    rrule              text                                               not null,
+    dtstart            timestamp with time zone                           not null,
+    active             boolean                  default true              not null,
+    last_occurrence_at timestamp with time zone
+);
+
+create index task_recurrences_template_task_id_idx
+    on task_recurrences (template_task_id);
+
+create trigger set_public_task_recurrences_updated_at
+    before update
+    on task_recurrences
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_task_recurrences_updated_at on task_recurrences is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create table task_occurrences
+(
+    id            uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at    timestamp with time zone default now()             not null,
+    updated_at    timestamp with time zone default now()             not null,
+    recurrence_id uuid                                               not null
+        references task_recurrences
+            on update cascade on delete cascade,
+    occurrence_at timestamp with time zone                           not null,
+    task_id       uuid
+        references tasks
+            on update cascade on delete set null,
+    status        varchar                                            not null,
+    unique (recurrence_id, occurrence_at)
+);
+
+create index task_occurrences_task_id_idx
+    on task_occurrences (task_id);
+
+create trigger set_public_task_occurrences_updated_at
+    before update
+    on task_occurrences
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_task_occurrences_updated_at on task_occurrences is 'trigger to set value of column "updated_at" to current timestamp on row update';
@@ -0,0 +1,143 @@
+alter table projects
+    add column task_counter integer default 0 not null;
+
+alter table tasks
+    add column number integer;
+
+alter table tasks
+    add column identifier varchar;
+
+create index tasks_identifier_idx
+    on tasks (identifier);
+
+create table task_identifier_redirects
+(
+    identifier varchar                                            not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    task_id    uuid                                               not null
+        references tasks
+            on update cascade on delete cascade
+);
+
+create index task_identifier_redirects_task_id_idx
+    on task_identifier_redirects (task_id);
+
+-- project prefix first, then the prefix of the first team working on the project
+create or replace function project_identifier_prefix(project uuid) returns varchar
+    language sql
+    stable
+as
+$$
+SELECT COALESCE(
+    (SELECT NULLIF(prefix, '') FROM projects WHERE id = project),
+    (SELECT NULLIF(teams.prefix, '') FROM teams
+        INNER JOIN teams_by_projects ON teams_by_projects.team_id = teams.id
+        WHERE teams_by_projects.project_id = project
+        ORDER BY teams.created_at
+        LIMIT 1)
+);
+$$;
+
+create or replace function assign_task_identifier() returns trigger
+    language plpgsql
+as
+$$
+DECLARE
+  _prefix varchar;
+BEGIN
+  IF (TG_OP = 'UPDATE') THEN
+    IF (NEW.project_id IS NOT DISTINCT FROM OLD.project_id) THEN
+      RETURN NEW;
+    END IF;
+
+    -- keep the old identifier reachable after a move
+    IF (OLD.identifier IS NOT NULL) THEN
+      INSERT INTO task_identifier_redirects (identifier, task_id)
+      VALUES (OLD.identifier, OLD.id)
+      ON CONFLICT (identifier) DO UPDATE SET task_id = EXCLUDED.task_id, created_at = now();
+    END IF;
+  END IF;
+
+  IF (NEW.project_id IS NULL) THEN
+    NEW.number := NULL;
+    NEW.identifier := NULL;
+    RETURN NEW;
+  END IF;
+
+  UPDATE projects SET task_counter = task_counter + 1
+  WHERE id = NEW.project_id
+  RETURNING task_counter INTO NEW.number;
+
+  _prefix := project_identifier_prefix(NEW.project_id);
+
+  IF (_prefix IS NULL OR NEW.number IS NULL) THEN
+    NEW.identifier := NULL;
+  ELSE
+    NEW.identifier := upper(_prefix) || '-' || NEW.number;
+    DELETE FROM task_identifier_redirects WHERE identifier = NEW.identifier;
+  END IF;
+
+  RETURN NEW;
+END;
+$$;
+
+create trigger assign_public_tasks_identifier
+    before insert or update of project_id
+    on tasks
+    for each row
+execute procedure assign_task_identifier();
+
+comment on trigger assign_public_tasks_identifier on tasks is 'trigger to assign the per-project task number and identifier';
+
+create or replace function refresh_project_task_identifiers() returns trigger
+    language plpgsql
+as
+$$
+DECLARE
+  _prefix varchar;
+BEGIN
+  _prefix := project_identifier_prefix(NEW.id);
+
+  INSERT INTO task_identifier_redirects (identifier, task_id)
+  SELECT identifier, id FROM tasks
+  WHERE project_id = NEW.id AND identifier IS NOT NULL
+  ON CONFLICT (identifier) DO UPDATE SET task_id = EXCLUDED.task_id, created_at = now();
+
+  UPDATE tasks
+  SET identifier = CASE WHEN _prefix IS NULL THEN NULL ELSE upper(_prefix) || '-' || number END
+  WHERE project_id = NEW.id AND number IS NOT NULL;
+
+  DELETE FROM task_identifier_redirects
+  WHERE identifier IN (SELECT identifier FROM tasks WHERE project_id = NEW.id AND identifier IS NOT NULL);
+
+  RETURN NULL;
+END;
+$$;
+
+create trigger refresh_public_projects_task_identifiers
+    after update of prefix
+    on projects
+    for each row
+    when (OLD.prefix IS DISTINCT FROM NEW.prefix)
+execute procedure refresh_project_task_identifiers();
+
+comment on trigger refresh_public_projects_task_identifiers on projects is 'trigger to rewrite task identifiers when the project prefix changes';
+
+-- backfill numbers for the tasks that already exist
+WITH numbered AS (
+    SELECT id, row_number() OVER (PARTITION BY project_id ORDER BY count, created_at) AS number
+    FROM tasks
+    WHERE project_id IS NOT NULL
+)
+UPDATE tasks
+SET number = numbered.number
+FROM numbered
+WHERE tasks.id = numbered.id;
+
+UPDATE tasks
+SET identifier = upper(project_identifier_prefix(project_id)) || '-' || number
+WHERE number IS NOT NULL AND project_identifier_prefix(project_id) IS NOT NULL;
+
+UPDATE projects
+SET task_counter = COALESCE((SELECT max(number) FROM tasks WHERE tasks.project_id = projects.id), 0);
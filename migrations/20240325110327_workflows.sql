@@ -0,0 +1,90 @@
+create table workflows
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    owner_id    uuid                                               not null
+        references members
+            on update cascade on delete set null,
+    project_id  uuid                                               not null
+        unique
+        references projects
+            on update cascade on delete cascade,
+    name        varchar                                            not null,
+    description text
+);
+
+create trigger set_public_workflows_updated_at
+    before update
+    on workflows
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_workflows_updated_at on workflows is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create table workflow_states
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    workflow_id uuid                                               not null
+        references workflows
+            on update cascade on delete cascade,
+    name        varchar                                            not null,
+    category    varchar                                            not null,
+    position    integer                  default 0                 not null,
+    color       varchar,
+    is_default  boolean                  default false             not null,
+    unique (workflow_id, name)
+);
+
+create index workflow_states_workflow_id_idx
+    on workflow_states (workflow_id);
+
+create trigger set_public_workflow_states_updated_at
+    before update
+    on workflow_states
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_workflow_states_updated_at on workflow_states is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create table workflow_transitions
+(
+    id            uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at    timestamp with time zone default now()             not null,
+    updated_at    timestamp with time zone default now()             not null,
+    workflow_id   uuid                                               not null
+        references workflows
+            on update cascade on delete cascade,
+    from_state_id uuid                                               not null
+        references workflow_states
+            on update cascade on delete cascade,
+    to_state_id   uuid                                               not null
+        references workflow_states
+            on update cascade on delete cascade,
+    unique (from_state_id, to_state_id),
+    check (from_state_id <> to_state_id)
+);
+
+create index workflow_transitions_workflow_id_idx
+    on workflow_transitions (workflow_id);
+
+create trigger set_public_workflow_transitions_updated_at
+    before update
+    on workflow_transitions
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_workflow_transitions_updated_at on workflow_transitions is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+alter table tasks
+    add column state_id uuid
+        references workflow_states
+            on update cascade on delete set null;
+
+create index tasks_state_id_idx
+    on tasks (state_id);
@@ -0,0 +1,67 @@
+create table custom_fields
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    owner_id    uuid                                               not null
+        references members
+            on update cascade on delete set null,
+    project_id  uuid                                               not null
+        references projects
+            on update cascade on delete cascade,
+    target      varchar                                            not null,
+    name        varchar                                            not null,
+    kind        varchar                                            not null,
+    options     varchar[]                default '{}'              not null,
+    position    integer                  default 0                 not null,
+    description text,
+    unique (project_id, target, name)
+);
+
+create index custom_fields_project_id_idx
+    on custom_fields (project_id);
+
+create trigger set_public_custom_fields_updated_at
+    before update
+    on custom_fields
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_custom_fields_updated_at on custom_fields is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create table custom_field_values
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    field_id   uuid                                               not null
+        references custom_fields
+            on update cascade on delete cascade,
+    task_id    uuid
+        references tasks
+            on update cascade on delete cascade,
+    project_id uuid
+        references projects
+            on update cascade on delete cascade,
+    value      jsonb                                              not null,
+    check (num_nonnulls(task_id, project_id) = 1)
+);
+
+create unique index custom_field_values_field_id_resource_idx
+    on custom_field_values (field_id, COALESCE(task_id, project_id));
+
+create index custom_field_values_task_id_idx
+    on custom_field_values (task_id);
+
+create index custom_field_values_project_id_idx
+    on custom_field_values (project_id);
+
+create trigger set_public_custom_field_values_updated_at
+    before update
+    on custom_field_values
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_custom_field_values_updated_at on custom_field_values is 'trigger to set value of column "updated_at" to current timestamp on row update';
@@ -0,0 +1,92 @@
+create table comments
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    author_id  uuid                                               not null
+        references members
+            on update cascade on delete set null,
+    task_id    uuid
+        references tasks
+            on update cascade on delete cascade,
+    project_id uuid
+        references projects
+            on update cascade on delete cascade,
+    parent_id  uuid
+        references comments
+            on update cascade on delete cascade,
+    body       text                                               not null,
+    edited_at  timestamp with time zone,
+    check (num_nonnulls(task_id, project_id) = 1)
+);
+
+create index comments_task_id_idx
+    on comments (task_id);
+
+create index comments_project_id_idx
+    on comments (project_id);
+
+create index comments_parent_id_idx
+    on comments (parent_id);
+
+create trigger set_public_comments_updated_at
+    before update
+    on comments
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_comments_updated_at on comments is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create or replace trigger trigger_comments_table_update
+    after insert or update or delete
+    on comments
+    for each row
+execute procedure notify_table_update();
+
+create table comment_revisions
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    comment_id uuid                                               not null
+        references comments
+            on update cascade on delete cascade,
+    body       text                                               not null
+);
+
+create index comment_revisions_comment_id_idx
+    on comment_revisions (comment_id);
+
+create table comment_mentions
+(
+    created_at timestamp with time zone default now() not null,
+    comment_id uuid                                   not null
+        references comments
+            on update cascade on delete cascade,
+    member_id  uuid                                   not null
+        references members
+            on update cascade on delete cascade,
+    primary key (comment_id, member_id)
+);
+
+create index comment_mentions_member_id_idx
+    on comment_mentions (member_id);
+
+create table comment_reactions
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    comment_id uuid                                               not null
+        references comments
+            on update cascade on delete cascade,
+    member_id  uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    emoji      varchar                                            not null,
+    unique (comment_id, member_id, emoji)
+);
+
+create index comment_reactions_comment_id_idx
+    on comment_reactions (comment_id);
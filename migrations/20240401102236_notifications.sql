@@ -0,0 +1,86 @@
+create table watch_subscriptions
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    member_id  uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    task_id    uuid
+        references tasks
+            on update cascade on delete cascade,
+    project_id uuid
+        references projects
+            on update cascade on delete cascade,
+    reason     varchar                                            not null,
+    -- unwatching keeps the row so automatic subscriptions don't come back
+    active     boolean                  default true              not null,
+    check (num_nonnulls(task_id, project_id) = 1)
+);
+
+create unique index watch_subscriptions_member_id_resource_idx
+    on watch_subscriptions (member_id, COALESCE(task_id, project_id));
+
+create index watch_subscriptions_task_id_idx
+    on watch_subscriptions (task_id);
+
+create index watch_subscriptions_project_id_idx
+    on watch_subscriptions (project_id);
+
+create trigger set_public_watch_subscriptions_updated_at
+    before update
+    on watch_subscriptions
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_watch_subscriptions_updated_at on watch_subscriptions is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create table notifications
+(
+    id           uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at   timestamp with time zone default now()             not null,
+    updated_at   timestamp with time zone default now()             not null,
+    recipient_id uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    actor_id     uuid
+        references members
+            on update cascade on delete set null,
+    kind         varchar                                            not null,
+    task_id      uuid
+        references tasks
+            on update cascade on delete cascade,
+    project_id   uuid
+        references projects
+            on update cascade on delete cascade,
+    comment_id   uuid
+        references comments
+            on update cascade on delete cascade,
+    group_key    varchar                                            not null,
+    title        text                                               not null,
+    body         text,
+    read_at      timestamp with time zone,
+    archived_at  timestamp with time zone
+);
+
+create index notifications_recipient_id_idx
+    on notifications (recipient_id, archived_at, read_at);
+
+create index notifications_group_key_idx
+    on notifications (recipient_id, group_key);
+
+create trigger set_public_notifications_updated_at
+    before update
+    on notifications
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_notifications_updated_at on notifications is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create or replace trigger trigger_notifications_table_update
+    after insert or update or delete
+    on notifications
+    for each row
+execute procedure notify_table_update();
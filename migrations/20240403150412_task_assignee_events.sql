@@ -0,0 +1,23 @@
+-- assignments live in their own table, so announce them as updates of the task
+create or replace function notify_task_assignees_update() returns trigger as
+$$
+declare
+    row record;
+begin
+    if (TG_OP = 'DELETE') then
+        row = OLD;
+    else
+        row = NEW;
+    end if;
+
+    perform pg_notify('tasks_table_update', 'tasks UPDATE ' || row.task_id);
+
+    return null;
+end;
+$$ language plpgsql;
+
+create or replace trigger trigger_tasks_by_assignees_update
+    after insert or delete
+    on tasks_by_assignees
+    for each row
+execute procedure notify_task_assignees_update();
@@ -0,0 +1,21 @@
+create table email_preferences
+(
+    id                  uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at          timestamp with time zone default now()             not null,
+    updated_at          timestamp with time zone default now()             not null,
+    member_id           uuid                                               not null
+        unique
+        references members
+            on update cascade on delete cascade,
+    digest_frequency    varchar                  default 'Daily'           not null,
+    last_digest_sent_at timestamp with time zone
+);
+
+create trigger set_public_email_preferences_updated_at
+    before update
+    on email_preferences
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_email_preferences_updated_at on email_preferences is 'trigger to set value of column "updated_at" to current timestamp on row update';
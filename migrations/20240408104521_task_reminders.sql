@@ -0,0 +1,17 @@
+create table task_reminders
+(
+    id            uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at    timestamp with time zone default now()             not null,
+    task_id       uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    kind          varchar                                            not null,
+    -- moving the due date re-arms the reminders
+    due_date      timestamp with time zone                           not null,
+    recipient_ids uuid[]                   default '{}'              not null,
+    unique (task_id, kind, due_date)
+);
+
+create index task_reminders_task_id_idx
+    on task_reminders (task_id);
@@ -0,0 +1,89 @@
+alter table tasks
+    add last_actor_id uuid
+        references members
+            on update cascade on delete set null;
+
+create table automation_rules
+(
+    id               uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at       timestamp with time zone default now()             not null,
+    updated_at       timestamp with time zone default now()             not null,
+    owner_id         uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    -- rules without a project apply to every task
+    project_id       uuid
+        references projects
+            on update cascade on delete cascade,
+    name             text                                               not null,
+    description      text,
+    enabled          boolean                  default true              not null,
+    trigger          varchar                                            not null,
+    trigger_status   varchar,
+    trigger_label_id uuid
+        references labels
+            on update cascade on delete cascade,
+    conditions       jsonb,
+    actions          jsonb                    default '[]'              not null,
+    position         integer                  default 0                 not null
+);
+
+create index automation_rules_trigger_idx
+    on automation_rules (trigger)
+    where enabled;
+
+create trigger set_public_automation_rules_updated_at
+    before update
+    on automation_rules
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_automation_rules_updated_at on automation_rules is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create table automation_runs
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    rule_id    uuid                                               not null
+        references automation_rules
+            on update cascade on delete cascade,
+    task_id    uuid
+        references tasks
+            on update cascade on delete set null,
+    trigger    varchar                                            not null,
+    status     varchar                                            not null,
+    depth      integer                  default 0                 not null,
+    message    text
+);
+
+create index automation_runs_rule_id_idx
+    on automation_runs (rule_id, created_at);
+
+create index automation_runs_task_id_idx
+    on automation_runs (task_id);
+
+-- label changes only touch labels_by_tasks, announce them as task updates
+create or replace function notify_task_labels_update() returns trigger as
+$$
+declare
+    row record;
+begin
+    if (TG_OP = 'DELETE') then
+        row = OLD;
+    else
+        row = NEW;
+    end if;
+
+    perform pg_notify('tasks_table_update', 'tasks UPDATE ' || row.task_id);
+
+    return null;
+end;
+$$ language plpgsql;
+
+create or replace trigger trigger_labels_by_tasks_update
+    after insert or delete
+    on labels_by_tasks
+    for each row
+execute procedure notify_task_labels_update();
@@ -0,0 +1,100 @@
+create table cycles
+(
+    id           uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at   timestamp with time zone default now()             not null,
+    updated_at   timestamp with time zone default now()             not null,
+    owner_id     uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    team_id      uuid
+        references teams
+            on update cascade on delete cascade,
+    project_id   uuid
+        references projects
+            on update cascade on delete cascade,
+    -- numbered per team or project
+    number       integer                                            not null,
+    name         text,
+    description  text,
+    starts_at    timestamp with time zone                           not null,
+    ends_at      timestamp with time zone                           not null,
+    completed_at timestamp with time zone,
+    check (num_nonnulls(team_id, project_id) = 1),
+    check (ends_at > starts_at)
+);
+
+create unique index cycles_resource_number_idx
+    on cycles (COALESCE(team_id, project_id), number);
+
+create index cycles_ends_at_idx
+    on cycles (ends_at)
+    where completed_at is null;
+
+create trigger set_public_cycles_updated_at
+    before update
+    on cycles
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_cycles_updated_at on cycles is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create or replace trigger trigger_cycles_table_update
+    after insert or update or delete
+    on cycles
+    for each row
+execute procedure notify_table_update();
+
+alter table tasks
+    add cycle_id uuid
+        references cycles
+            on update cascade on delete set null;
+
+create index tasks_cycle_id_idx
+    on tasks (cycle_id);
+
+-- every task that was ever part of a cycle, kept for the cycle metrics
+create table cycle_tasks
+(
+    cycle_id            uuid                                               not null
+        references cycles
+            on update cascade on delete cascade,
+    task_id             uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    added_at            timestamp with time zone default now()             not null,
+    removed_at          timestamp with time zone,
+    carried_over_to_id  uuid
+        references cycles
+            on update cascade on delete set null,
+    primary key (cycle_id, task_id)
+);
+
+create or replace function track_task_cycle() returns trigger as
+$$
+begin
+    if (TG_OP = 'UPDATE' and OLD.cycle_id is not null and OLD.cycle_id is distinct from NEW.cycle_id) then
+        update cycle_tasks
+        set removed_at = now()
+        where cycle_id = OLD.cycle_id
+          and task_id = OLD.id
+          and removed_at is null;
+    end if;
+
+    if (NEW.cycle_id is not null and (TG_OP = 'INSERT' or OLD.cycle_id is distinct from NEW.cycle_id)) then
+        insert into cycle_tasks (cycle_id, task_id)
+        values (NEW.cycle_id, NEW.id)
+        on conflict (cycle_id, task_id) do update
+            set removed_at         = null,
+                carried_over_to_id = null;
+    end if;
+
+    return null;
+end;
+$$ language plpgsql;
+
+create or replace trigger trigger_tasks_cycle_update
+    after insert or update of cycle_id
+    on tasks
+    for each row
+execute procedure track_task_cycle();
@@ -0,0 +1,42 @@
+create table milestones
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    owner_id    uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    project_id  uuid                                               not null
+        references projects
+            on update cascade on delete cascade,
+    name        text                                               not null,
+    description text,
+    target_date timestamp with time zone,
+    status      varchar                  default 'Planned'         not null
+);
+
+create index milestones_project_id_idx
+    on milestones (project_id, target_date);
+
+create trigger set_public_milestones_updated_at
+    before update
+    on milestones
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_milestones_updated_at on milestones is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create or replace trigger trigger_milestones_table_update
+    after insert or update or delete
+    on milestones
+    for each row
+execute procedure notify_table_update();
+
+alter table tasks
+    add milestone_id uuid
+        references milestones
+            on update cascade on delete set null;
+
+create index tasks_milestone_id_idx
+    on tasks (milestone_id);
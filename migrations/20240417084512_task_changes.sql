@@ -0,0 +1,42 @@
+-- every task write is kept in changes so that project history can be replayed,
+-- clock_timestamp keeps writes made in one transaction in order
+create or replace function record_task_change() returns trigger as
+$$
+begin
+    if (TG_OP = 'UPDATE' and (to_jsonb(OLD) - 'updated_at') = (to_jsonb(NEW) - 'updated_at')) then
+        return null;
+    end if;
+
+    if (TG_OP = 'DELETE') then
+        -- owner removal cascades here, its changes go with it
+        if not exists (select 1 from members where id = OLD.owner_id) then
+            return null;
+        end if;
+
+        insert into changes (created_at, owner_id, resource_id, operation, resource_type, diff_json)
+        values (clock_timestamp(), OLD.owner_id, OLD.id, 'Delete', 'Tasks',
+                jsonb_build_object('old', to_jsonb(OLD), 'new', null)::text);
+    else
+        insert into changes (created_at, owner_id, resource_id, operation, resource_type, diff_json)
+        values (clock_timestamp(), coalesce(NEW.last_actor_id, NEW.owner_id), NEW.id, initcap(lower(TG_OP)), 'Tasks',
+                jsonb_build_object('old', case when TG_OP = 'UPDATE' then to_jsonb(OLD) end, 'new', to_jsonb(NEW))::text);
+    end if;
+
+    return null;
+end;
+$$ language plpgsql;
+
+create or replace trigger trigger_tasks_record_change
+    after insert or update or delete
+    on tasks
+    for each row
+execute procedure record_task_change();
+
+-- tasks created before this migration start their history at creation
+insert into changes (created_at, owner_id, resource_id, operation, resource_type, diff_json)
+select tasks.created_at, tasks.owner_id, tasks.id, 'Insert', 'Tasks',
+       jsonb_build_object('old', null, 'new', to_jsonb(tasks))::text
+from tasks;
+
+create index if not exists changes_resource_type_resource_id_created_at_index
+    on changes (resource_type, resource_id, created_at);
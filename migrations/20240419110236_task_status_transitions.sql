@@ -0,0 +1,59 @@
+create table task_status_transitions
+(
+    id              uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at      timestamp with time zone default now()             not null,
+    task_id         uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    actor_id        uuid
+        references members
+            on delete set null,
+    from_status     varchar,
+    to_status       varchar                                            not null,
+    transitioned_at timestamp with time zone default clock_timestamp() not null
+);
+
+create index task_status_transitions_task_id_transitioned_at_index
+    on task_status_transitions (task_id, transitioned_at);
+
+create index task_status_transitions_to_status_transitioned_at_index
+    on task_status_transitions (to_status, transitioned_at);
+
+create or replace function record_task_status_transition() returns trigger as
+$$
+begin
+    if (TG_OP = 'INSERT' or OLD.status is distinct from NEW.status) then
+        insert into task_status_transitions (task_id, actor_id, from_status, to_status)
+        values (NEW.id,
+                coalesce(NEW.last_actor_id, NEW.owner_id),
+                case when TG_OP = 'UPDATE' then OLD.status end,
+                coalesce(NEW.status, 'None'));
+    end if;
+
+    return null;
+end;
+$$ language plpgsql;
+
+create or replace trigger trigger_tasks_status_transition
+    after insert or update of status
+    on tasks
+    for each row
+execute procedure record_task_status_transition();
+
+-- earlier transitions come from the task changes history
+with task_changes as materialized (select changes.created_at,
+                                          changes.resource_id,
+                                          changes.owner_id,
+                                          changes.diff_json::jsonb -> 'old' ->> 'status' as from_status,
+                                          changes.diff_json::jsonb -> 'new' ->> 'status' as to_status
+                                   from changes
+                                   where changes.resource_type = 'Tasks'
+                                     and changes.operation in ('Insert', 'Update')
+                                     and changes.diff_json like '{"new": %')
+insert
+into task_status_transitions (created_at, task_id, actor_id, from_status, to_status, transitioned_at)
+select created_at, resource_id, owner_id, from_status, coalesce(to_status, 'None'), created_at
+from task_changes
+where from_status is distinct from to_status
+  and exists (select 1 from tasks where tasks.id = task_changes.resource_id);
@@ -0,0 +1,48 @@
+create table member_capacities
+(
+    id              uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at      timestamp with time zone default now()             not null,
+    updated_at      timestamp with time zone default now()             not null,
+    member_id       uuid                                               not null
+        unique
+        references members
+            on update cascade on delete cascade,
+    unit            varchar                  default 'Points'          not null,
+    weekly_capacity double precision                                   not null
+        check (weekly_capacity >= 0)
+);
+
+create trigger set_public_member_capacities_updated_at
+    before update
+    on member_capacities
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_member_capacities_updated_at on member_capacities is 'trigger to set value of column "updated_at" to current timestamp on row update';
+
+create table member_time_off
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    member_id  uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    starts_at  timestamp with time zone                           not null,
+    ends_at    timestamp with time zone                           not null,
+    reason     text,
+    check (ends_at > starts_at)
+);
+
+create index member_time_off_member_id_idx
+    on member_time_off (member_id, starts_at);
+
+create trigger set_public_member_time_off_updated_at
+    before update
+    on member_time_off
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_member_time_off_updated_at on member_time_off is 'trigger to set value of column "updated_at" to current timestamp on row update';
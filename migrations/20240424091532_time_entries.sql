@@ -0,0 +1,38 @@
+create table time_entries
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    member_id  uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    task_id    uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    started_at timestamp with time zone                           not null,
+    -- null while the timer runs
+    ended_at   timestamp with time zone,
+    note       text,
+    billable   boolean                  default true              not null,
+    check (ended_at is null or ended_at >= started_at)
+);
+
+create index time_entries_task_id_idx
+    on time_entries (task_id);
+
+create index time_entries_member_id_started_at_idx
+    on time_entries (member_id, started_at);
+
+-- one running timer per member
+create unique index time_entries_running_member_id_idx
+    on time_entries (member_id)
+    where ended_at is null;
+
+create trigger set_public_time_entries_updated_at
+    before update
+    on time_entries
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_time_entries_updated_at on time_entries is 'trigger to set value of column "updated_at" to current timestamp on row update';
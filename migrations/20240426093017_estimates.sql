@@ -0,0 +1,5 @@
+alter table projects
+    add estimate_scale varchar default 'Points' not null;
+
+alter table tasks
+    add estimate double precision check (estimate >= 0);
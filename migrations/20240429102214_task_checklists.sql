@@ -0,0 +1,30 @@
+create table task_checklist_items
+(
+    id              uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at      timestamp with time zone default now()             not null,
+    updated_at      timestamp with time zone default now()             not null,
+    task_id         uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    owner_id        uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    title           varchar                                            not null,
+    position        integer                                            not null,
+    completed_at    timestamp with time zone,
+    completed_by_id uuid
+        references members
+            on update cascade on delete set null
+);
+
+create index task_checklist_items_task_id_position_idx
+    on task_checklist_items (task_id, position);
+
+create trigger set_public_task_checklist_items_updated_at
+    before update
+    on task_checklist_items
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_task_checklist_items_updated_at on task_checklist_items is 'trigger to set value of column "updated_at" to current timestamp on row update';
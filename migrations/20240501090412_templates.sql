@@ -0,0 +1,53 @@
+create table templates
+(
+    id                  uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at          timestamp with time zone default now()             not null,
+    updated_at          timestamp with time zone default now()             not null,
+    owner_id            uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    kind                varchar                  default 'Project'         not null,
+    name                varchar                                            not null,
+    description         text,
+    -- project fields, only set on project templates
+    project_name        varchar,
+    project_description text,
+    project_prefix      varchar,
+    project_visibility  varchar,
+    estimate_scale      varchar,
+    -- days between the start and the due date of the project
+    due_offset_days     integer
+);
+
+create table template_tasks
+(
+    id              uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at      timestamp with time zone default now()             not null,
+    template_id     uuid                                               not null
+        references templates
+            on update cascade on delete cascade,
+    parent_id       uuid
+        references template_tasks
+            on update cascade on delete cascade,
+    position        integer                                            not null,
+    title           varchar                                            not null,
+    description     text,
+    priority        varchar,
+    estimate        double precision,
+    -- days between the start and the due date of the task
+    due_offset_days integer,
+    label_ids       uuid[]                   default '{}'              not null
+);
+
+create index template_tasks_template_id_idx
+    on template_tasks (template_id);
+
+create trigger set_public_templates_updated_at
+    before update
+    on templates
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+comment on trigger set_public_templates_updated_at on templates is 'trigger to set value of column "updated_at" to current timestamp on row update';
@@ -0,0 +1,199 @@
+-- ranks are base 36 keys compared byte by byte, tasks are ordered by rank
+-- within their project and status
+alter table tasks
+    add column rank varchar collate "C";
+
+-- the first six digits are counted up when appending so ranks stay short,
+-- a rank made only of 'z' gets longer instead
+create or replace function task_rank_after(lower_rank varchar) returns varchar
+    language plpgsql
+    immutable
+as
+$$
+DECLARE
+  _digits constant varchar := '0123456789abcdefghijklmnopqrstuvwxyz';
+  _head varchar;
+  _position int;
+  _digit int;
+BEGIN
+  IF (lower_rank IS NULL OR lower_rank = '') THEN
+    RETURN 'i00000';
+  END IF;
+
+  _head := rpad(left(lower_rank, 6), 6, '0');
+  _position := 6;
+
+  WHILE _position > 0 LOOP
+    _digit := strpos(_digits, substr(_head, _position, 1)) - 1;
+
+    IF (_digit < 35) THEN
+      RETURN left(_head, _position - 1) || substr(_digits, _digit + 2, 1) || repeat('0', 6 - _position);
+    END IF;
+
+    _position := _position - 1;
+  END LOOP;
+
+  RETURN lower_rank || 'i';
+END;
+$$;
+
+-- counts the first six digits down, `null` when nothing but '0' would be left
+create or replace function task_rank_before(upper_rank varchar) returns varchar
+    language plpgsql
+    immutable
+as
+$$
+DECLARE
+  _digits constant varchar := '0123456789abcdefghijklmnopqrstuvwxyz';
+  _head varchar := rpad(left(upper_rank, 6), 6, '0');
+  _position int := 6;
+  _digit int;
+BEGIN
+  WHILE _position > 0 LOOP
+    _digit := strpos(_digits, substr(_head, _position, 1)) - 1;
+
+    IF (_digit > 0) THEN
+      RETURN nullif(left(_head, _position - 1) || substr(_digits, _digit, 1) || repeat('z', 6 - _position), '000000');
+    END IF;
+
+    _position := _position - 1;
+  END LOOP;
+
+  RETURN NULL;
+END;
+$$;
+
+-- a rank sorting strictly between both, a missing side is an open end
+create or replace function task_rank_between(lower_rank varchar, upper_rank varchar) returns varchar
+    language plpgsql
+    immutable
+as
+$$
+DECLARE
+  _digits constant varchar := '0123456789abcdefghijklmnopqrstuvwxyz';
+  _lower varchar := coalesce(lower_rank, '');
+  _upper varchar := upper_rank;
+  _prefix varchar := '';
+  _low int;
+  _high int;
+BEGIN
+  IF (_upper IS NULL) THEN
+    RETURN task_rank_after(lower_rank);
+  END IF;
+
+  IF (lower_rank IS NULL AND task_rank_before(_upper) IS NOT NULL) THEN
+    RETURN task_rank_before(_upper);
+  END IF;
+
+  IF (_lower COLLATE "C" >= _upper COLLATE "C") THEN
+    RAISE EXCEPTION 'rank % does not sort before %', lower_rank, upper_rank;
+  END IF;
+
+  LOOP
+    IF (_upper = '') THEN
+      RAISE EXCEPTION 'no rank between % and %', lower_rank, upper_rank;
+    END IF;
+
+    _low := CASE WHEN _lower = '' THEN 0 ELSE strpos(_digits, left(_lower, 1)) - 1 END;
+    _high := strpos(_digits, left(_upper, 1)) - 1;
+
+    IF (_low = _high) THEN
+      _prefix := _prefix || left(_upper, 1);
+      _lower := substr(_lower, 2);
+      _upper := substr(_upper, 2);
+    ELSIF (_high - _low > 1) THEN
+      RETURN _prefix || substr(_digits, (_low + _high) / 2 + 1, 1);
+    ELSIF (rtrim(substr(_upper, 2), '0') <> '') THEN
+      RETURN _prefix || left(_upper, 1);
+    ELSE
+      -- neighbouring digits, continue after the lower rank
+      _prefix := _prefix || substr(_digits, _low + 1, 1);
+      _lower := substr(_lower, 2);
+
+      LOOP
+        _low := CASE WHEN _lower = '' THEN 0 ELSE strpos(_digits, left(_lower, 1)) - 1 END;
+
+        IF (_low < 35) THEN
+          RETURN _prefix || substr(_digits, (_low + 36) / 2 + 1, 1);
+        END IF;
+
+        _prefix := _prefix || 'z';
+        _lower := substr(_lower, 2);
+      END LOOP;
+    END IF;
+  END LOOP;
+END;
+$$;
+
+-- tasks go to the end of their column when created or moved to another one,
+-- an explicit rank on insert is kept
+create or replace function assign_task_rank() returns trigger
+    language plpgsql
+as
+$$
+BEGIN
+  IF (TG_OP = 'UPDATE'
+    AND NEW.project_id IS NOT DISTINCT FROM OLD.project_id
+    AND NEW.status IS NOT DISTINCT FROM OLD.status) THEN
+    RETURN NEW;
+  END IF;
+
+  IF (TG_OP = 'INSERT' AND NEW.rank IS NOT NULL) THEN
+    RETURN NEW;
+  END IF;
+
+  NEW.rank := task_rank_after((
+    SELECT max(rank) FROM tasks
+    WHERE project_id IS NOT DISTINCT FROM NEW.project_id
+      AND status IS NOT DISTINCT FROM NEW.status
+      AND id <> NEW.id
+  ));
+
+  RETURN NEW;
+END;
+$$;
+
+create trigger assign_public_tasks_rank
+    before insert or update of project_id, status
+    on tasks
+    for each row
+execute procedure assign_task_rank();
+
+comment on trigger assign_public_tasks_rank on tasks is 'trigger to append tasks to the end of their project and status';
+
+-- existing tasks keep their creation order, without adding to their history
+alter table tasks
+    disable trigger trigger_tasks_record_change;
+
+do
+$$
+DECLARE
+  _task record;
+  _scope varchar;
+  _rank varchar;
+BEGIN
+  FOR _task IN
+    SELECT id, coalesce(project_id::text, '') || '/' || coalesce(status, '') AS scope
+    FROM tasks
+    ORDER BY project_id, status, created_at, count
+  LOOP
+    IF (_scope IS DISTINCT FROM _task.scope) THEN
+      _rank := task_rank_after(NULL);
+    ELSE
+      _rank := task_rank_after(_rank);
+    END IF;
+
+    UPDATE tasks SET rank = _rank WHERE id = _task.id;
+    _scope := _task.scope;
+  END LOOP;
+END;
+$$;
+
+alter table tasks
+    enable trigger trigger_tasks_record_change;
+
+alter table tasks
+    alter column rank set not null;
+
+create index tasks_project_id_status_rank_idx
+    on tasks (project_id, status, rank);
@@ -0,0 +1,77 @@
+-- deleted rows stay in the trash until they are restored or purged
+alter table tasks
+    add column deleted_at timestamp with time zone;
+
+alter table projects
+    add column deleted_at timestamp with time zone;
+
+alter table members
+    add column deleted_at timestamp with time zone;
+
+alter table teams
+    add column deleted_at timestamp with time zone;
+
+alter table labels
+    add column deleted_at timestamp with time zone;
+
+alter table assets
+    add column deleted_at timestamp with time zone;
+
+create index tasks_deleted_at_idx
+    on tasks (deleted_at) where deleted_at is not null;
+
+create index projects_deleted_at_idx
+    on projects (deleted_at) where deleted_at is not null;
+
+create index members_deleted_at_idx
+    on members (deleted_at) where deleted_at is not null;
+
+create index teams_deleted_at_idx
+    on teams (deleted_at) where deleted_at is not null;
+
+create index labels_deleted_at_idx
+    on labels (deleted_at) where deleted_at is not null;
+
+create index assets_deleted_at_idx
+    on assets (deleted_at) where deleted_at is not null;
+
+-- rows left behind by earlier hard deletes, purging relies on the cascades below
+delete from labels_by_tasks
+where not exists (select 1 from labels where labels.id = labels_by_tasks.label_id)
+   or not exists (select 1 from tasks where tasks.id = labels_by_tasks.task_id);
+
+delete from members_by_projects
+where not exists (select 1 from members where members.id = members_by_projects.member_id)
+   or not exists (select 1 from projects where projects.id = members_by_projects.project_id);
+
+delete from teams_by_projects
+where not exists (select 1 from teams where teams.id = teams_by_projects.team_id)
+   or not exists (select 1 from projects where projects.id = teams_by_projects.project_id);
+
+delete from members_by_teams
+where not exists (select 1 from members where members.id = members_by_teams.member_id)
+   or not exists (select 1 from teams where teams.id = members_by_teams.team_id);
+
+alter table labels_by_tasks
+    add foreign key (label_id) references labels (id)
+        on update cascade on delete cascade,
+    add foreign key (task_id) references tasks (id)
+        on update cascade on delete cascade;
+
+alter table members_by_projects
+    add foreign key (member_id) references members (id)
+        on update cascade on delete cascade,
+    add foreign key (project_id) references projects (id)
+        on update cascade on delete cascade;
+
+alter table teams_by_projects
+    add foreign key (team_id) references teams (id)
+        on update cascade on delete cascade,
+    add foreign key (project_id) references projects (id)
+        on update cascade on delete cascade;
+
+alter table members_by_teams
+    add foreign key (member_id) references members (id)
+        on update cascade on delete cascade,
+    add foreign key (team_id) references teams (id)
+        on update cascade on delete cascade;
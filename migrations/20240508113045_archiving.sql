@@ -0,0 +1,12 @@
+-- archived rows stay readable but are left out of listings unless asked for
+alter table projects
+    add column archived_at timestamp with time zone;
+
+alter table tasks
+    add column archived_at timestamp with time zone;
+
+create index projects_archived_at_idx
+    on projects (archived_at) where archived_at is not null;
+
+create index tasks_archived_at_idx
+    on tasks (archived_at) where archived_at is not null;
@@ -0,0 +1,36 @@
+-- shared records and the history outlive their owner, a member owning any of
+-- them can't be deleted for good and stays in the trash
+alter table cycles
+    drop constraint cycles_owner_id_fkey,
+    add constraint cycles_owner_id_fkey foreign key (owner_id) references members (id)
+        on update cascade on delete restrict;
+
+alter table milestones
+    drop constraint milestones_owner_id_fkey,
+    add constraint milestones_owner_id_fkey foreign key (owner_id) references members (id)
+        on update cascade on delete restrict;
+
+alter table time_entries
+    drop constraint time_entries_member_id_fkey,
+    add constraint time_entries_member_id_fkey foreign key (member_id) references members (id)
+        on update cascade on delete restrict;
+
+alter table task_checklist_items
+    drop constraint task_checklist_items_owner_id_fkey,
+    add constraint task_checklist_items_owner_id_fkey foreign key (owner_id) references members (id)
+        on update cascade on delete restrict;
+
+alter table templates
+    drop constraint templates_owner_id_fkey,
+    add constraint templates_owner_id_fkey foreign key (owner_id) references members (id)
+        on update cascade on delete restrict;
+
+alter table automation_rules
+    drop constraint automation_rules_owner_id_fkey,
+    add constraint automation_rules_owner_id_fkey foreign key (owner_id) references members (id)
+        on update cascade on delete restrict;
+
+alter table changes
+    drop constraint changes_owner_id_fkey,
+    add constraint changes_owner_id_fkey foreign key (owner_id) references members (id)
+        on delete restrict;
@@ -0,0 +1,166 @@
+-- the prefix a project hands out identifiers with, unique across projects so
+-- the ones sharing a team or a prefix don't issue the same identifiers
+alter table projects
+    add column identifier_prefix varchar;
+
+create unique index projects_identifier_prefix_idx
+    on projects (identifier_prefix);
+
+-- project prefix first, then the prefix of the first team working on the
+-- project. A number is appended when another project holds it already: MOB,
+-- MOB2, MOB3...
+create or replace function project_identifier_prefix(project uuid) returns varchar
+    language plpgsql
+as
+$$
+DECLARE
+  _base varchar;
+  _claimed varchar;
+  _candidate varchar;
+  _attempt int := 1;
+BEGIN
+  _base := upper(COALESCE(
+    (SELECT NULLIF(prefix, '') FROM projects WHERE id = project),
+    (SELECT NULLIF(teams.prefix, '') FROM teams
+        INNER JOIN teams_by_projects ON teams_by_projects.team_id = teams.id
+        WHERE teams_by_projects.project_id = project
+        ORDER BY teams.created_at
+        LIMIT 1)
+  ));
+
+  SELECT identifier_prefix INTO _claimed FROM projects WHERE id = project;
+
+  IF (_base IS NULL) THEN
+    IF (_claimed IS NOT NULL) THEN
+      UPDATE projects SET identifier_prefix = NULL WHERE id = project;
+    END IF;
+
+    RETURN NULL;
+  END IF;
+
+  IF (left(_claimed, length(_base)) = _base AND substr(_claimed, length(_base) + 1) ~ '^([2-9]|[1-9][0-9]+)?$') THEN
+    RETURN _claimed;
+  END IF;
+
+  LOOP
+    _candidate := _base || CASE WHEN _attempt = 1 THEN '' ELSE _attempt::text END;
+
+    IF NOT EXISTS(SELECT 1 FROM projects WHERE identifier_prefix = _candidate AND id <> project) THEN
+      BEGIN
+        UPDATE projects SET identifier_prefix = _candidate WHERE id = project;
+
+        RETURN _candidate;
+      EXCEPTION
+        WHEN unique_violation THEN
+          -- claimed by a concurrent transaction, the next one is tried
+      END;
+    END IF;
+
+    _attempt := _attempt + 1;
+  END LOOP;
+END;
+$$;
+
+-- identifiers are unique now, redirects sharing one with a current identifier
+-- are shadowed by it and left alone
+create or replace function assign_task_identifier() returns trigger
+    language plpgsql
+as
+$$
+DECLARE
+  _prefix varchar;
+BEGIN
+  IF (TG_OP = 'UPDATE') THEN
+    IF (NEW.project_id IS NOT DISTINCT FROM OLD.project_id) THEN
+      RETURN NEW;
+    END IF;
+
+    -- keep the old identifier reachable after a move
+    IF (OLD.identifier IS NOT NULL) THEN
+      INSERT INTO task_identifier_redirects (identifier, task_id)
+      VALUES (OLD.identifier, OLD.id)
+      ON CONFLICT (identifier) DO UPDATE SET task_id = EXCLUDED.task_id, created_at = now();
+    END IF;
+  END IF;
+
+  IF (NEW.project_id IS NULL) THEN
+    NEW.number := NULL;
+    NEW.identifier := NULL;
+    RETURN NEW;
+  END IF;
+
+  UPDATE projects SET task_counter = task_counter + 1
+  WHERE id = NEW.project_id
+  RETURNING task_counter INTO NEW.number;
+
+  _prefix := project_identifier_prefix(NEW.project_id);
+
+  IF (_prefix IS NULL OR NEW.number IS NULL) THEN
+    NEW.identifier := NULL;
+  ELSE
+    NEW.identifier := _prefix || '-' || NEW.number;
+  END IF;
+
+  RETURN NEW;
+END;
+$$;
+
+create or replace function refresh_project_task_identifiers() returns trigger
+    language plpgsql
+as
+$$
+DECLARE
+  _prefix varchar;
+BEGIN
+  _prefix := project_identifier_prefix(NEW.id);
+
+  INSERT INTO task_identifier_redirects (identifier, task_id)
+  SELECT identifier, id FROM tasks
+  WHERE project_id = NEW.id AND identifier IS NOT NULL
+  ON CONFLICT (identifier) DO UPDATE SET task_id = EXCLUDED.task_id, created_at = now();
+
+  UPDATE tasks
+  SET identifier = CASE WHEN _prefix IS NULL THEN NULL ELSE _prefix || '-' || number END
+  WHERE project_id = NEW.id AND number IS NOT NULL;
+
+  RETURN NULL;
+END;
+$$;
+
+-- identifiers issued twice so far move to the prefix their project claims, the
+-- oldest project keeps its identifiers and the redirects left behind
+alter table tasks
+    disable trigger trigger_tasks_record_change;
+
+do
+$$
+DECLARE
+  _project record;
+  _prefix varchar;
+BEGIN
+  FOR _project IN SELECT id FROM projects ORDER BY created_at, id LOOP
+    _prefix := project_identifier_prefix(_project.id);
+
+    INSERT INTO task_identifier_redirects (identifier, task_id)
+    SELECT identifier, id FROM tasks
+    WHERE project_id = _project.id
+      AND identifier IS NOT NULL
+      AND identifier IS DISTINCT FROM _prefix || '-' || number
+    ON CONFLICT (identifier) DO NOTHING;
+
+    UPDATE tasks
+    SET identifier = CASE WHEN _prefix IS NULL THEN NULL ELSE _prefix || '-' || number END
+    WHERE project_id = _project.id
+      AND number IS NOT NULL
+      AND identifier IS DISTINCT FROM _prefix || '-' || number;
+  END LOOP;
+END;
+$$;
+
+alter table tasks
+    enable trigger trigger_tasks_record_change;
+
+drop index tasks_identifier_idx;
+
+create unique index tasks_identifier_idx
+    on tasks (identifier);
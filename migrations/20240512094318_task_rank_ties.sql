@@ -0,0 +1,70 @@
+-- appends and reorders in the same project and status take turns, otherwise
+-- concurrent appends read the same last rank and end up sharing it
+create or replace function lock_task_rank_scope(scope_project_id uuid, scope_status varchar) returns void
+    language sql
+as
+$$
+SELECT pg_advisory_xact_lock(hashtext('task_rank/' || coalesce(scope_project_id::text, '') || '/' || coalesce(scope_status, '')));
+$$;
+
+create or replace function assign_task_rank() returns trigger
+    language plpgsql
+as
+$$
+BEGIN
+  IF (TG_OP = 'UPDATE'
+    AND NEW.project_id IS NOT DISTINCT FROM OLD.project_id
+    AND NEW.status IS NOT DISTINCT FROM OLD.status) THEN
+    RETURN NEW;
+  END IF;
+
+  IF (TG_OP = 'INSERT' AND NEW.rank IS NOT NULL) THEN
+    RETURN NEW;
+  END IF;
+
+  PERFORM lock_task_rank_scope(NEW.project_id, NEW.status);
+
+  NEW.rank := task_rank_after((
+    SELECT max(rank) FROM tasks
+    WHERE project_id IS NOT DISTINCT FROM NEW.project_id
+      AND status IS NOT DISTINCT FROM NEW.status
+      AND id <> NEW.id
+  ));
+
+  RETURN NEW;
+END;
+$$;
+
+-- tasks sharing a rank, left behind by appends made before the lock, keep
+-- their order by id and get ranks of their own up to the next rank
+create or replace function separate_task_ranks(scope_project_id uuid, scope_status varchar) returns void
+    language plpgsql
+as
+$$
+DECLARE
+  _task record;
+  _previous varchar;
+  _rank varchar;
+BEGIN
+  FOR _task IN
+    SELECT id, rank FROM tasks
+    WHERE project_id IS NOT DISTINCT FROM scope_project_id
+      AND status IS NOT DISTINCT FROM scope_status
+    ORDER BY rank, id
+  LOOP
+    IF (_previous IS NOT NULL AND _task.rank <= _previous) THEN
+      _rank := task_rank_between(_previous, (
+        SELECT min(rank) FROM tasks
+        WHERE project_id IS NOT DISTINCT FROM scope_project_id
+          AND status IS NOT DISTINCT FROM scope_status
+          AND rank > _previous
+      ));
+
+      UPDATE tasks SET rank = _rank WHERE id = _task.id;
+      _previous := _rank;
+    ELSE
+      _previous := _task.rank;
+    END IF;
+  END LOOP;
+END;
+$$;
@@ -0,0 +1,33 @@
+-- assignments and labels live in their own tables with a task_id column, one
+-- function announces both as updates of the task
+create or replace function notify_task_relation_update() returns trigger as
+$$
+declare
+    row record;
+begin
+    if (TG_OP = 'DELETE') then
+        row = OLD;
+    else
+        row = NEW;
+    end if;
+
+    perform pg_notify('tasks_table_update', 'tasks UPDATE ' || row.task_id);
+
+    return null;
+end;
+$$ language plpgsql;
+
+create or replace trigger trigger_tasks_by_assignees_update
+    after insert or delete
+    on tasks_by_assignees
+    for each row
+execute procedure notify_task_relation_update();
+
+create or replace trigger trigger_labels_by_tasks_update
+    after insert or delete
+    on labels_by_tasks
+    for each row
+execute procedure notify_task_relation_update();
+
+drop function notify_task_assignees_update();
+drop function notify_task_labels_update();
@@ -0,0 +1,6 @@
+-- rule behind the last write to the task, empty for writes made by anyone else,
+-- so the events of a rule's changes can be told apart
+alter table tasks
+    add last_automation_rule_id uuid
+        references automation_rules
+            on update cascade on delete set null;
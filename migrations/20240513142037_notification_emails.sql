@@ -0,0 +1,7 @@
+-- when the email for the notification went out, notifications from before
+-- emails were sent count as delivered so nobody gets a backlog in their inbox
+alter table notifications
+    add emailed_at timestamp with time zone;
+
+update notifications
+set emailed_at = created_at;
@@ -0,0 +1,25 @@
+-- milestones, cycles, templates and automation rules go through the trash too,
+-- time entries and checklist items are lines of their task and stay hard deletes
+alter table milestones
+    add column deleted_at timestamp with time zone;
+
+alter table cycles
+    add column deleted_at timestamp with time zone;
+
+alter table templates
+    add column deleted_at timestamp with time zone;
+
+alter table automation_rules
+    add column deleted_at timestamp with time zone;
+
+create index milestones_deleted_at_idx
+    on milestones (deleted_at) where deleted_at is not null;
+
+create index cycles_deleted_at_idx
+    on cycles (deleted_at) where deleted_at is not null;
+
+create index templates_deleted_at_idx
+    on templates (deleted_at) where deleted_at is not null;
+
+create index automation_rules_deleted_at_idx
+    on automation_rules (deleted_at) where deleted_at is not null;
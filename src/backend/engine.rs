@@ -27,6 +27,7 @@ pub struct SDKConfig {
     pub database_url: String,
     pub llm_api_key: String,
     pub llm_model_name: String,
+    pub enforce_task_blockers: bool,
 }
 
 impl SDKConfig {
@@ -34,11 +35,15 @@ impl SDKConfig {
         let database_url = var("DATABASE_URL").unwrap();
         let llm_api_key = var("OPENAI_API_KEY").unwrap();
         let llm_model_name = var("OPENAI_MODEL_NAME").unwrap_or("gpt-3.5-turbo".to_string());
+        let enforce_task_blockers = var("ENFORCE_TASK_BLOCKERS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
 
         SDKConfig {
             database_url,
             llm_api_key,
             llm_model_name,
+            enforce_task_blockers,
         }
     }
 }
@@ -3,9 +3,18 @@ use std::sync::Arc;
 use async_graphql::dataloader::DataLoader;
 
 use crate::resources::{
-    assets::loader::AssetLoader, changes::loader::ChangeLoader, labels::loader::LabelLoader,
-    members::loader::MemberLoader, projects::loader::ProjectLoader, tasks::loader::TaskLoader,
+    assets::loader::AssetLoader,
+    changes::loader::ChangeLoader,
+    comments::loader::CommentLoader,
+    cycles::loader::{CycleLoader, CycleMetricsLoader},
+    dependencies::loader::TaskDependencyLoader,
+    labels::loader::LabelLoader,
+    members::loader::MemberLoader,
+    milestones::loader::{MilestoneLoader, MilestoneProgressLoader},
+    projects::loader::ProjectLoader,
+    tasks::loader::TaskLoader,
     teams::loader::TeamLoader,
+    workflows::loader::WorkflowStateLoader,
 };
 
 use super::engine::SDKEngine;
@@ -19,6 +28,13 @@ pub struct SDKLoaders {
     pub asset_loader: DataLoader<AssetLoader>,
     pub label_loader: DataLoader<LabelLoader>,
     pub change_loader: DataLoader<ChangeLoader>,
+    pub task_dependency_loader: DataLoader<TaskDependencyLoader>,
+    pub workflow_state_loader: DataLoader<WorkflowStateLoader>,
+    pub comment_loader: DataLoader<CommentLoader>,
+    pub cycle_loader: DataLoader<CycleLoader>,
+    pub cycle_metrics_loader: DataLoader<CycleMetricsLoader>,
+    pub milestone_loader: DataLoader<MilestoneLoader>,
+    pub milestone_progress_loader: DataLoader<MilestoneProgressLoader>,
 
     pub engine: Arc<SDKEngine>,
 }
@@ -33,6 +49,13 @@ impl SDKLoaders {
             asset_loader: DataLoader::new(AssetLoader::new(engine.clone()), tokio::spawn),
             label_loader: DataLoader::new(LabelLoader::new(engine.clone()), tokio::spawn),
             change_loader: DataLoader::new(ChangeLoader::new(engine.clone()), tokio::spawn),
+            task_dependency_loader: DataLoader::new(TaskDependencyLoader::new(engine.clone()), tokio::spawn),
+            workflow_state_loader: DataLoader::new(WorkflowStateLoader::new(engine.clone()), tokio::spawn),
+            comment_loader: DataLoader::new(CommentLoader::new(engine.clone()), tokio::spawn),
+            cycle_loader: DataLoader::new(CycleLoader::new(engine.clone()), tokio::spawn),
+            cycle_metrics_loader: DataLoader::new(CycleMetricsLoader::new(engine.clone()), tokio::spawn),
+            milestone_loader: DataLoader::new(MilestoneLoader::new(engine.clone()), tokio::spawn),
+            milestone_progress_loader: DataLoader::new(MilestoneProgressLoader::new(engine.clone()), tokio::spawn),
 
             engine,
         }
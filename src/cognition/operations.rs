@@ -43,6 +43,15 @@ pub struct TaskSuggestion {
     pub status: TaskStatus,
     pub priority: TaskPriority,
     pub due_date: DateTime<Utc>,
+    // in the unit of the project estimate scale
+    #[builder(setter(strip_option), default)]
+    pub estimate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Builder, Object, SimpleObject, Deserialize)]
+#[builder(pattern = "owned")]
+pub struct ChecklistItemSuggestion {
+    pub title: String,
 }
 
 #[derive(Default, Builder, Object, InputObject)]
@@ -101,6 +101,14 @@ impl CognitionCapabilities for SDKEngine {
                 owner_id: r.owner_id,
                 count: r.count,
                 parent_id: r.parent_id,
+                state_id: r.state_id,
+                cycle_id: r.cycle_id,
+                milestone_id: r.milestone_id,
+                estimate: r.estimate,
+                number: r.number,
+                identifier: r.identifier.clone(),
+                rank: r.rank.clone(),
+                archived_at: r.archived_at,
             })
             .map(Self::calculate_task_fingerprint)
             .collect::<Vec<String>>()
@@ -1,15 +1,20 @@
 use askama::Template;
 use async_trait::async_trait;
+use uuid::Uuid;
 
 use crate::{
     backend::engine::SDKEngine,
     cognition::{
-        operations::{SubdivideTaskInput, TaskSuggestion, TaskSuggestionInput},
+        operations::{ChecklistItemSuggestion, SubdivideTaskInput, TaskSuggestion, TaskSuggestionInput},
         suggestions::CognitionCapabilities,
     },
     common::commons::SortOrder,
     errors::sdk::SDKError,
     resources::{
+        milestones::{
+            milestone::Milestone,
+            operations::{GetMilestonesInputBuilder, MilestoneCrudOperations},
+        },
         projects::{
             operations::{GetProjectsInputBuilder, ProjectCrudOperations},
             project::Project,
@@ -27,6 +32,10 @@ use super::projects::{ProjectSuggestion, ProjectSuggestionInput, ProjectTaskSugg
 pub trait CognitionOperationsV2 {
     async fn get_suggestions_v2(&self, input: TaskSuggestionInput) -> Result<TaskSuggestion, SDKError>;
     async fn subdivide_task_v2(&self, input: SubdivideTaskInput) -> Result<Vec<TaskSuggestion>, SDKError>;
+    async fn subdivide_task_checklist_v2(
+        &self,
+        input: SubdivideTaskInput,
+    ) -> Result<Vec<ChecklistItemSuggestion>, SDKError>;
     async fn get_project_suggestion(&self, input: ProjectSuggestionInput) -> Result<ProjectSuggestion, SDKError>;
 }
 
@@ -38,6 +47,10 @@ fn calculate_project_fingerprint(project: &Project) -> String {
     serde_json::to_string_pretty(&project).unwrap()
 }
 
+fn calculate_milestone_fingerprint(milestone: &Milestone) -> String {
+    serde_json::to_string_pretty(&milestone).unwrap()
+}
+
 fn calculate_task_suggestion_input_fingerprint(input: &TaskSuggestionInput) -> String {
     serde_json::to_string_pretty(&input).unwrap()
 }
@@ -60,6 +73,7 @@ pub struct TaskSuggestionTemplate {
     tasks: Vec<Task>,
     initial_state: Option<TaskSuggestionInput>,
     project: Option<Project>,
+    milestones: Vec<Milestone>,
     user_query: Option<String>,
 }
 
@@ -69,8 +83,10 @@ pub struct TaskSubdivideTemplate {
     parent_task: Task,
     number_of_subtasks: u8,
     project: Option<Project>,
+    milestones: Vec<Milestone>,
     tasks: Option<Vec<Task>>,
     user_query: Option<String>,
+    as_checklist: bool,
 }
 
 #[derive(Template)]
@@ -88,6 +104,79 @@ pub struct ProjectSuggestionTemplate {
     user_query: Option<String>,
 }
 
+impl SDKEngine {
+    async fn get_project_milestones_context(&self, project_id: Option<Uuid>) -> Result<Vec<Milestone>, SDKError> {
+        let Some(project_id) = project_id else {
+            return Ok(Vec::new());
+        };
+
+        self.get_milestones(
+            GetMilestonesInputBuilder::default()
+                .project_id(project_id)
+                .limit(20)
+                .build()
+                .unwrap(),
+        )
+        .await
+    }
+
+    async fn render_task_subdivide(&self, input: &SubdivideTaskInput, as_checklist: bool) -> Result<String, SDKError> {
+        let parent_task = self.get_task(input.task_id).await?;
+
+        let (project, project_id) = match parent_task.project_id {
+            Some(project_id) => (Some(self.get_project(project_id).await?), Some(project_id)),
+            None => (None, None),
+        };
+
+        let tasks = match (input.with_tasks_context, project_id) {
+            (Some(true), Some(project_id)) => {
+                let tasks = self
+                    .get_tasks(
+                        GetTasksInputBuilder::default()
+                            .filter(GetTasksWhereBuilder::default().project_id(project_id).build().unwrap())
+                            .sort_by("created_at".to_string())
+                            .sort_order(SortOrder::Desc)
+                            .limit(10)
+                            .build()
+                            .ok(),
+                    )
+                    .await?;
+
+                Some(tasks)
+            }
+            (Some(true), None) => {
+                let tasks = self
+                    .get_tasks(
+                        GetTasksInputBuilder::default()
+                            .sort_by("created_at".to_string())
+                            .sort_order(SortOrder::Desc)
+                            .limit(10)
+                            .build()
+                            .ok(),
+                    )
+                    .await?;
+
+                Some(tasks)
+            }
+            (None, _) | (Some(false), _) => None,
+        };
+
+        let milestones = self.get_project_milestones_context(project_id).await?;
+
+        Ok(TaskSubdivideTemplate {
+            parent_task,
+            number_of_subtasks: input.subtasks,
+            project,
+            milestones,
+            tasks,
+            user_query: None,
+            as_checklist,
+        }
+        .render()
+        .unwrap())
+    }
+}
+
 #[async_trait]
 impl CognitionOperationsV2 for SDKEngine {
     async fn get_suggestions_v2(&self, input: TaskSuggestionInput) -> Result<TaskSuggestion, SDKError> {
@@ -126,9 +215,12 @@ impl CognitionOperationsV2 for SDKEngine {
             ),
         };
 
+        let milestones = self.get_project_milestones_context(input.project_id).await?;
+
         let input_message = TaskSuggestionTemplate {
             tasks,
             project,
+            milestones,
             initial_state: Some(input),
             user_query: None,
         }
@@ -148,66 +240,35 @@ impl CognitionOperationsV2 for SDKEngine {
 
     async fn subdivide_task_v2(&self, input: SubdivideTaskInput) -> Result<Vec<TaskSuggestion>, SDKError> {
         let system_message = PlexoSystemTemplate {}.render().unwrap();
+        let input_message = self.render_task_subdivide(&input, false).await?;
 
-        let parent_task = self.get_task(input.task_id).await?;
-
-        let (project, project_id) = match parent_task.project_id {
-            Some(project_id) => (Some(self.get_project(project_id).await?), Some(project_id)),
-            None => (None, None),
-        };
-
-        let tasks = match (input.with_tasks_context, project_id) {
-            (Some(true), Some(project_id)) => {
-                let tasks = self
-                    .get_tasks(
-                        GetTasksInputBuilder::default()
-                            .filter(GetTasksWhereBuilder::default().project_id(project_id).build().unwrap())
-                            .sort_by("created_at".to_string())
-                            .sort_order(SortOrder::Desc)
-                            .limit(10)
-                            .build()
-                            .ok(),
-                    )
-                    .await?;
+        let result = self.chat_completion(system_message, input_message).await;
+        let result = result.trim().trim_matches('`');
 
-                Some(tasks)
-            }
-            (Some(true), None) => {
-                let tasks = self
-                    .get_tasks(
-                        GetTasksInputBuilder::default()
-                            .sort_by("created_at".to_string())
-                            .sort_order(SortOrder::Desc)
-                            .limit(10)
-                            .build()
-                            .ok(),
-                    )
-                    .await?;
+        let subtasks: Vec<TaskSuggestion> = serde_json::from_str(result).inspect_err(|err| {
+            println!("Error parsing subtasks result: {:?}", err);
+            println!("raw result: {:?}", result);
+        })?;
 
-                Some(tasks)
-            }
-            (None, _) | (Some(false), _) => None,
-        };
+        Ok(subtasks)
+    }
 
-        let input_message = TaskSubdivideTemplate {
-            parent_task,
-            number_of_subtasks: input.subtasks,
-            project,
-            tasks,
-            user_query: None,
-        }
-        .render()
-        .unwrap();
+    async fn subdivide_task_checklist_v2(
+        &self,
+        input: SubdivideTaskInput,
+    ) -> Result<Vec<ChecklistItemSuggestion>, SDKError> {
+        let system_message = PlexoSystemTemplate {}.render().unwrap();
+        let input_message = self.render_task_subdivide(&input, true).await?;
 
         let result = self.chat_completion(system_message, input_message).await;
         let result = result.trim().trim_matches('`');
 
-        let subtasks: Vec<TaskSuggestion> = serde_json::from_str(result).inspect_err(|err| {
-            println!("Error parsing subtasks result: {:?}", err);
+        let items: Vec<ChecklistItemSuggestion> = serde_json::from_str(result).inspect_err(|err| {
+            println!("Error parsing checklist result: {:?}", err);
             println!("raw result: {:?}", result);
         })?;
 
-        Ok(subtasks)
+        Ok(items)
     }
 
     async fn get_project_suggestion(&self, input: ProjectSuggestionInput) -> Result<ProjectSuggestion, SDKError> {
@@ -1 +1,3 @@
+pub mod clock;
 pub mod commons;
+pub mod runner;
@@ -0,0 +1,44 @@
+use std::{future::Future, time::Duration};
+
+use tokio::{sync::watch, task::JoinHandle};
+
+pub struct RunnerHandle {
+    shutdown: watch::Sender<bool>,
+    join: JoinHandle<()>,
+}
+
+impl RunnerHandle {
+    pub fn new(shutdown: watch::Sender<bool>, join: JoinHandle<()>) -> Self {
+        Self { shutdown, join }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.join.is_finished()
+    }
+
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        let _ = self.join.await;
+    }
+}
+
+pub fn spawn_periodic<F, Fut>(interval: Duration, mut tick: F) -> RunnerHandle
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let (shutdown, mut shutdown_recv) = watch::channel(false);
+
+    let join = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            tokio::select! {
+                _ = shutdown_recv.changed() => break,
+                _ = ticker.tick() => tick().await,
+            }
+        }
+    });
+
+    RunnerHandle::new(shutdown, join)
+}
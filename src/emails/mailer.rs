@@ -0,0 +1,76 @@
+use std::{path::PathBuf, sync::Mutex};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::errors::sdk::SDKError;
+
+use super::message::EmailMessage;
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<(), SDKError>;
+}
+
+// Writes every message as an .eml file, handy to eyeball rendered emails locally.
+pub struct FileMailer {
+    directory: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    pub fn new(directory: impl Into<PathBuf>, from: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            from: from.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), SDKError> {
+        tokio::fs::create_dir_all(&self.directory)
+            .await
+            .map_err(|err| SDKError::MailerError(err.to_string()))?;
+
+        let path = self.directory.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%d%H%M%S"),
+            Uuid::new_v4().simple()
+        ));
+
+        tokio::fs::write(path, message.to_mime(&self.from))
+            .await
+            .map_err(|err| SDKError::MailerError(err.to_string()))
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryMailer {
+    messages: Mutex<Vec<EmailMessage>>,
+}
+
+impl InMemoryMailer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn messages(&self) -> Vec<EmailMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    pub fn clear(&self) {
+        self.messages.lock().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl Mailer for InMemoryMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), SDKError> {
+        self.messages.lock().unwrap().push(message.clone());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailMessage {
+    pub to: String,
+    pub to_name: Option<String>,
+
+    pub subject: String,
+
+    pub text: String,
+    pub html: String,
+}
+
+impl EmailMessage {
+    // multipart/alternative with the plain text first so clients prefer the html part
+    pub fn to_mime(&self, from: &str) -> String {
+        let boundary = format!("plexo-{}", Uuid::new_v4().simple());
+
+        let to = match &self.to_name {
+            Some(name) => format!("{} <{}>", encode_display_name(name), self.to),
+            None => self.to.clone(),
+        };
+
+        let mut mime = String::new();
+
+        mime.push_str(&format!("From: {}\r\n", from));
+        mime.push_str(&format!("To: {}\r\n", to));
+        mime.push_str(&format!("Subject: {}\r\n", encode_header(&self.subject)));
+        mime.push_str(&format!("Date: {}\r\n", Utc::now().to_rfc2822()));
+        mime.push_str(&format!("Message-ID: <{}@plexo>\r\n", Uuid::new_v4()));
+        mime.push_str("MIME-Version: 1.0\r\n");
+        mime.push_str(&format!(
+            "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
+            boundary
+        ));
+
+        for (content_type, body) in [("text/plain", &self.text), ("text/html", &self.html)] {
+            mime.push_str(&format!("--{}\r\n", boundary));
+            mime.push_str(&format!("Content-Type: {}; charset=utf-8\r\n", content_type));
+            mime.push_str("Content-Transfer-Encoding: base64\r\n\r\n");
+
+            let encoded = STANDARD.encode(body.as_bytes());
+
+            for line in encoded.as_bytes().chunks(76) {
+                mime.push_str(std::str::from_utf8(line).unwrap());
+                mime.push_str("\r\n");
+            }
+        }
+
+        mime.push_str(&format!("--{}--\r\n", boundary));
+
+        mime
+    }
+}
+
+// A bare address, anything that could end a header or an SMTP command is
+// refused.
+pub fn is_valid_address(address: &str) -> bool {
+    let Some((local, domain)) = address.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !domain.is_empty()
+        && !domain.contains('@')
+        && address.chars().all(|c| {
+            c.is_ascii_graphic() && !matches!(c, '<' | '>' | '(' | ')' | ',' | ';' | ':' | '\\' | '"' | '[' | ']')
+        })
+}
+
+// control characters are replaced so a line break can't start another header
+fn strip_controls(value: &str) -> String {
+    value.chars().map(|c| if c.is_control() { ' ' } else { c }).collect()
+}
+
+fn encode_word(value: &str) -> String {
+    format!("=?UTF-8?B?{}?=", STANDARD.encode(value.as_bytes()))
+}
+
+fn encode_header(value: &str) -> String {
+    let value = strip_controls(value);
+
+    if value.is_ascii() {
+        return value;
+    }
+
+    encode_word(&value)
+}
+
+// names with commas or quotes would otherwise read as several mailboxes
+fn encode_display_name(value: &str) -> String {
+    let value = strip_controls(value);
+
+    if !value.is_ascii() {
+        return encode_word(&value);
+    }
+
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
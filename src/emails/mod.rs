@@ -0,0 +1,7 @@
+pub mod mailer;
+pub mod message;
+pub mod operations;
+pub mod preferences;
+pub mod runner;
+pub mod smtp;
+pub mod templates;
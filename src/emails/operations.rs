@@ -0,0 +1,474 @@
+use std::{collections::HashMap, str::FromStr};
+
+use askama::Template;
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{
+        members::{member::Member, operations::MemberCrudOperations},
+        notifications::{dispatch::task_label, notification::NotificationKind},
+        projects::operations::ProjectCrudOperations,
+        tasks::{
+            operations::TaskCrudOperations,
+            task::{Task, TaskPriority, TaskStatus},
+        },
+    },
+};
+
+use super::{
+    mailer::Mailer,
+    message::EmailMessage,
+    preferences::{DigestFrequency, EmailPreferences},
+    templates::{
+        DigestHtmlTemplate, DigestProject, DigestTextTemplate, EmailTask, TaskAssignedHtmlTemplate,
+        TaskAssignedTextTemplate, TaskDueSoonHtmlTemplate, TaskDueSoonTextTemplate,
+    },
+};
+
+#[async_trait]
+pub trait EmailOperations {
+    async fn get_email_preferences(&self, member_id: Uuid) -> Result<EmailPreferences, SDKError>;
+    async fn update_email_preferences(
+        &self,
+        member_id: Uuid,
+        input: UpdateEmailPreferencesInput,
+    ) -> Result<EmailPreferences, SDKError>;
+
+    async fn render_task_assigned_email(&self, task_id: Uuid, member_id: Uuid) -> Result<EmailMessage, SDKError>;
+    async fn render_task_due_soon_email(
+        &self,
+        task_id: Uuid,
+        member_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<EmailMessage, SDKError>;
+    async fn render_digest_email(
+        &self,
+        member_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Option<EmailMessage>, SDKError>;
+
+    async fn send_due_digests(&self, mailer: &dyn Mailer, now: DateTime<Utc>) -> Result<Vec<Uuid>, SDKError>;
+    async fn send_notification_emails(&self, mailer: &dyn Mailer, now: DateTime<Utc>) -> Result<Vec<Uuid>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateEmailPreferencesInput {
+    #[builder(setter(strip_option), default)]
+    pub digest_frequency: Option<DigestFrequency>,
+}
+
+impl SDKEngine {
+    async fn email_task(&self, task: &Task) -> Result<EmailTask, SDKError> {
+        let project = match task.project_id {
+            Some(project_id) => Some(self.get_project(project_id).await?.name),
+            None => None,
+        };
+
+        Ok(EmailTask {
+            label: task_label(task),
+            status: task.status.to_string(),
+            priority: task.priority.to_string(),
+            due_date: task.due_date.map(|due_date| due_date.format("%b %-d, %Y").to_string()),
+            project,
+        })
+    }
+
+    fn email_message(member: &Member, subject: String, text: String, html: String) -> EmailMessage {
+        EmailMessage {
+            to: member.email.clone(),
+            to_name: Some(member.name.clone()),
+            subject,
+            text,
+            html,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailOperations for SDKEngine {
+    async fn get_email_preferences(&self, member_id: Uuid) -> Result<EmailPreferences, SDKError> {
+        // members without stored preferences get the defaults
+        let preferences_info = sqlx::query!(
+            r#"
+            INSERT INTO email_preferences (member_id)
+            VALUES ($1)
+            ON CONFLICT (member_id) DO UPDATE SET member_id = email_preferences.member_id
+            RETURNING *
+            "#,
+            member_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(EmailPreferences {
+            id: preferences_info.id,
+            created_at: preferences_info.created_at,
+            updated_at: preferences_info.updated_at,
+            member_id: preferences_info.member_id,
+            digest_frequency: DigestFrequency::from_str(&preferences_info.digest_frequency).unwrap_or_default(),
+            last_digest_sent_at: preferences_info.last_digest_sent_at,
+        })
+    }
+
+    async fn update_email_preferences(
+        &self,
+        member_id: Uuid,
+        input: UpdateEmailPreferencesInput,
+    ) -> Result<EmailPreferences, SDKError> {
+        let preferences_info = sqlx::query!(
+            r#"
+            INSERT INTO email_preferences (member_id, digest_frequency)
+            VALUES ($1, COALESCE($2, 'Daily'))
+            ON CONFLICT (member_id) DO UPDATE
+            SET digest_frequency = COALESCE($2, email_preferences.digest_frequency)
+            RETURNING *
+            "#,
+            member_id,
+            input.digest_frequency.map(|frequency| frequency.to_string()),
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(EmailPreferences {
+            id: preferences_info.id,
+            created_at: preferences_info.created_at,
+            updated_at: preferences_info.updated_at,
+            member_id: preferences_info.member_id,
+            digest_frequency: DigestFrequency::from_str(&preferences_info.digest_frequency).unwrap_or_default(),
+            last_digest_sent_at: preferences_info.last_digest_sent_at,
+        })
+    }
+
+    async fn render_task_assigned_email(&self, task_id: Uuid, member_id: Uuid) -> Result<EmailMessage, SDKError> {
+        let member = self.get_member(member_id).await?;
+        let task = self.get_task(task_id).await?;
+        let email_task = self.email_task(&task).await?;
+
+        let text = TaskAssignedTextTemplate {
+            recipient: &member.name,
+            task: &email_task,
+            description: task.description.as_deref(),
+        }
+        .render()?;
+
+        let html = TaskAssignedHtmlTemplate {
+            recipient: &member.name,
+            task: &email_task,
+            description: task.description.as_deref(),
+        }
+        .render()?;
+
+        Ok(Self::email_message(
+            &member,
+            format!("You were assigned to {}", email_task.label),
+            text,
+            html,
+        ))
+    }
+
+    async fn render_task_due_soon_email(
+        &self,
+        task_id: Uuid,
+        member_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<EmailMessage, SDKError> {
+        let member = self.get_member(member_id).await?;
+        let task = self.get_task(task_id).await?;
+        let email_task = self.email_task(&task).await?;
+
+        let overdue = task.due_date.is_some_and(|due_date| due_date < now);
+
+        let text = TaskDueSoonTextTemplate {
+            recipient: &member.name,
+            task: &email_task,
+            overdue,
+        }
+        .render()?;
+
+        let html = TaskDueSoonHtmlTemplate {
+            recipient: &member.name,
+            task: &email_task,
+            overdue,
+        }
+        .render()?;
+
+        let subject = match overdue {
+            true => format!("{} is overdue", email_task.label),
+            false => format!("{} is due soon", email_task.label),
+        };
+
+        Ok(Self::email_message(&member, subject, text, html))
+    }
+
+    async fn render_digest_email(
+        &self,
+        member_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Option<EmailMessage>, SDKError> {
+        let member = self.get_member(member_id).await?;
+
+        let projects = sqlx::query!(
+            r#"
+            SELECT id, name FROM projects
+            WHERE (owner_id = $1 OR lead_id = $1
+                OR id IN (SELECT project_id FROM members_by_projects WHERE member_id = $1))
+                AND deleted_at IS NULL AND archived_at IS NULL
+            ORDER BY name
+            "#,
+            member_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        if projects.is_empty() {
+            return Ok(None);
+        }
+
+        let tasks = sqlx::query!(
+            r#"
+            SELECT id, created_at, title, identifier, status, priority, due_date, project_id FROM tasks
+            WHERE project_id = ANY($1) AND updated_at >= $2 AND updated_at < $3
+                AND deleted_at IS NULL AND archived_at IS NULL
+            ORDER BY updated_at
+            "#,
+            &projects.iter().map(|project| project.id).collect::<Vec<Uuid>>(),
+            since,
+            until,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        if tasks.is_empty() {
+            return Ok(None);
+        }
+
+        let mut digest: HashMap<Uuid, DigestProject> = HashMap::new();
+
+        for task in tasks.iter() {
+            let Some(project) = projects.iter().find(|project| Some(project.id) == task.project_id) else {
+                continue;
+            };
+
+            let entry = digest.entry(project.id).or_insert_with(|| DigestProject {
+                name: project.name.clone(),
+                created: Vec::new(),
+                completed: Vec::new(),
+                updated: Vec::new(),
+            });
+
+            let status = task
+                .status
+                .as_deref()
+                .and_then(|status| TaskStatus::from_str(status).ok())
+                .unwrap_or_default();
+
+            let email_task = EmailTask {
+                label: match &task.identifier {
+                    Some(identifier) => format!("{} {}", identifier, task.title),
+                    None => task.title.clone(),
+                },
+                status: status.to_string(),
+                priority: task
+                    .priority
+                    .as_deref()
+                    .and_then(|priority| TaskPriority::from_str(priority).ok())
+                    .unwrap_or_default()
+                    .to_string(),
+                due_date: task.due_date.map(|due_date| due_date.format("%b %-d, %Y").to_string()),
+                project: Some(project.name.clone()),
+            };
+
+            if task.created_at >= since {
+                entry.created.push(email_task);
+            } else if status == TaskStatus::Done {
+                entry.completed.push(email_task);
+            } else {
+                entry.updated.push(email_task);
+            }
+        }
+
+        let digest: Vec<DigestProject> = projects
+            .iter()
+            .filter_map(|project| digest.remove(&project.id))
+            .collect();
+
+        let period = match until - since {
+            period if period <= Duration::try_days(1).unwrap() => "today".to_string(),
+            period if period <= Duration::try_weeks(1).unwrap() => "this week".to_string(),
+            _ => format!("since {}", since.format("%b %-d, %Y")),
+        };
+
+        let text = DigestTextTemplate {
+            recipient: &member.name,
+            period: &period,
+            projects: &digest,
+        }
+        .render()?;
+
+        let html = DigestHtmlTemplate {
+            recipient: &member.name,
+            period: &period,
+            projects: &digest,
+        }
+        .render()?;
+
+        Ok(Some(Self::email_message(
+            &member,
+            "Your Plexo digest".to_string(),
+            text,
+            html,
+        )))
+    }
+
+    async fn send_due_digests(&self, mailer: &dyn Mailer, now: DateTime<Utc>) -> Result<Vec<Uuid>, SDKError> {
+        let members = sqlx::query!(
+            r#"
+            SELECT members.id AS "id!", email_preferences.digest_frequency AS "digest_frequency?", email_preferences.last_digest_sent_at
+            FROM members
+            LEFT JOIN email_preferences ON email_preferences.member_id = members.id
+            WHERE members.deleted_at IS NULL
+            "#,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let mut sent = Vec::new();
+
+        for member in members {
+            let frequency = member
+                .digest_frequency
+                .and_then(|frequency| DigestFrequency::from_str(&frequency).ok())
+                .unwrap_or_default();
+
+            let Some(period) = frequency.period() else {
+                continue;
+            };
+
+            if member
+                .last_digest_sent_at
+                .is_some_and(|last_digest_sent_at| now - last_digest_sent_at < period)
+            {
+                continue;
+            }
+
+            let since = member.last_digest_sent_at.unwrap_or(now - period);
+
+            if let Some(message) = self.render_digest_email(member.id, since, now).await? {
+                // the window stays put so the next run tries again, the others still get theirs
+                if let Err(err) = mailer.send(&message).await {
+                    println!("Error sending digest to {}: {:?}", member.id, err);
+                    continue;
+                }
+
+                sent.push(member.id);
+            }
+
+            // the window moves on even when there was nothing to report
+            sqlx::query!(
+                r#"
+                INSERT INTO email_preferences (member_id, last_digest_sent_at)
+                VALUES ($1, $2)
+                ON CONFLICT (member_id) DO UPDATE SET last_digest_sent_at = $2
+                "#,
+                member.id,
+                now,
+            )
+            .execute(self.db_pool.as_ref())
+            .await?;
+        }
+
+        Ok(sent)
+    }
+
+    async fn send_notification_emails(&self, mailer: &dyn Mailer, now: DateTime<Utc>) -> Result<Vec<Uuid>, SDKError> {
+        let notifications = sqlx::query!(
+            r#"
+            SELECT notifications.id, notifications.recipient_id, notifications.kind, notifications.task_id AS "task_id!"
+            FROM notifications
+            JOIN members ON members.id = notifications.recipient_id AND members.deleted_at IS NULL
+            JOIN tasks ON tasks.id = notifications.task_id AND tasks.deleted_at IS NULL
+            WHERE notifications.emailed_at IS NULL AND notifications.kind = ANY($1)
+            ORDER BY notifications.created_at
+            "#,
+            &[
+                NotificationKind::Assigned.to_string(),
+                NotificationKind::DueSoon.to_string(),
+                NotificationKind::Overdue.to_string(),
+            ],
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let mut sent = Vec::new();
+
+        for notification in notifications {
+            // claimed before sending so overlapping runs don't email it twice
+            let claimed = sqlx::query!(
+                r#"
+                UPDATE notifications SET emailed_at = $2
+                WHERE id = $1 AND emailed_at IS NULL
+                RETURNING id
+                "#,
+                notification.id,
+                now,
+            )
+            .fetch_optional(self.db_pool.as_ref())
+            .await?;
+
+            if claimed.is_none() {
+                continue;
+            }
+
+            let message = match NotificationKind::from_str(&notification.kind).unwrap_or_default() {
+                NotificationKind::Assigned => {
+                    self.render_task_assigned_email(notification.task_id, notification.recipient_id)
+                        .await
+                }
+                _ => {
+                    self.render_task_due_soon_email(notification.task_id, notification.recipient_id, now)
+                        .await
+                }
+            };
+
+            // a notification that can't be rendered stays claimed, it would fail the same way next run
+            let message = match message {
+                Ok(message) => message,
+                Err(err) => {
+                    println!("Error rendering email for notification {}: {:?}", notification.id, err);
+                    continue;
+                }
+            };
+
+            if let Err(err) = mailer.send(&message).await {
+                println!("Error sending email for notification {}: {:?}", notification.id, err);
+
+                sqlx::query!(
+                    r#"
+                    UPDATE notifications SET emailed_at = NULL
+                    WHERE id = $1
+                    "#,
+                    notification.id,
+                )
+                .execute(self.db_pool.as_ref())
+                .await?;
+
+                continue;
+            }
+
+            sent.push(notification.id);
+        }
+
+        Ok(sent)
+    }
+}
@@ -0,0 +1,42 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Duration, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKEmailPreferences")]
+pub struct EmailPreferences {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub member_id: Uuid,
+
+    pub digest_frequency: DigestFrequency,
+    pub last_digest_sent_at: Option<DateTime<Utc>>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum DigestFrequency {
+    Never,
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub fn period(&self) -> Option<Duration> {
+        match self {
+            DigestFrequency::Never => None,
+            DigestFrequency::Daily => Duration::try_days(1),
+            DigestFrequency::Weekly => Duration::try_weeks(1),
+        }
+    }
+}
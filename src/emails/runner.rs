@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        clock::Clock,
+        runner::{spawn_periodic, RunnerHandle},
+    },
+    errors::sdk::SDKError,
+};
+
+use super::{mailer::Mailer, operations::EmailOperations};
+
+#[derive(Clone)]
+pub struct DigestRunner {
+    engine: Arc<SDKEngine>,
+    mailer: Arc<dyn Mailer>,
+    clock: Arc<dyn Clock>,
+}
+
+impl DigestRunner {
+    pub fn new(engine: Arc<SDKEngine>, mailer: Arc<dyn Mailer>, clock: Arc<dyn Clock>) -> Self {
+        Self { engine, mailer, clock }
+    }
+
+    pub async fn run_once(&self) -> Result<Vec<Uuid>, SDKError> {
+        self.engine
+            .send_due_digests(self.mailer.as_ref(), self.clock.now())
+            .await
+    }
+
+    pub fn spawn(self, interval: std::time::Duration) -> RunnerHandle {
+        spawn_periodic(interval, move || {
+            let runner = self.clone();
+
+            async move {
+                if let Err(err) = runner.run_once().await {
+                    println!("Error sending email digests: {:?}", err);
+                }
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct NotificationEmailRunner {
+    engine: Arc<SDKEngine>,
+    mailer: Arc<dyn Mailer>,
+    clock: Arc<dyn Clock>,
+}
+
+impl NotificationEmailRunner {
+    pub fn new(engine: Arc<SDKEngine>, mailer: Arc<dyn Mailer>, clock: Arc<dyn Clock>) -> Self {
+        Self { engine, mailer, clock }
+    }
+
+    pub async fn run_once(&self) -> Result<Vec<Uuid>, SDKError> {
+        self.engine
+            .send_notification_emails(self.mailer.as_ref(), self.clock.now())
+            .await
+    }
+
+    pub fn spawn(self, interval: std::time::Duration) -> RunnerHandle {
+        spawn_periodic(interval, move || {
+            let runner = self.clone();
+
+            async move {
+                if let Err(err) = runner.run_once().await {
+                    println!("Error sending notification emails: {:?}", err);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use native_tls::{TlsConnector, TlsStream};
+
+use crate::errors::sdk::SDKError;
+
+use super::{
+    mailer::Mailer,
+    message::{is_valid_address, EmailMessage},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    None,
+    #[default]
+    StartTls,
+    // implicit TLS, usually on port 465
+    Tls,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+
+    pub username: Option<String>,
+    pub password: Option<String>,
+
+    pub from: String,
+    pub hello_name: String,
+    pub timeout: Duration,
+}
+
+impl SmtpConfig {
+    pub fn new(host: impl Into<String>, port: u16, from: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port,
+            security: SmtpSecurity::default(),
+            username: None,
+            password: None,
+            from: from.into(),
+            hello_name: "localhost".to_string(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_security(mut self, security: SmtpSecurity) -> Self {
+        self.security = security;
+        self
+    }
+
+    pub fn with_credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+}
+
+// A small SMTP client, one connection per message. The conversation is blocking
+// so it runs on tokio's blocking pool.
+pub struct SmtpMailer {
+    config: SmtpConfig,
+}
+
+impl SmtpMailer {
+    pub fn new(config: SmtpConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), SDKError> {
+        let config = self.config.clone();
+        let message = message.clone();
+
+        tokio::task::spawn_blocking(move || deliver(&config, &message))
+            .await
+            .map_err(|err| SDKError::MailerError(err.to_string()))?
+    }
+}
+
+enum SmtpStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl Read for SmtpStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            SmtpStream::Plain(stream) => stream.read(buf),
+            SmtpStream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for SmtpStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            SmtpStream::Plain(stream) => stream.write(buf),
+            SmtpStream::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            SmtpStream::Plain(stream) => stream.flush(),
+            SmtpStream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+fn mailer_error(err: impl ToString) -> SDKError {
+    SDKError::MailerError(err.to_string())
+}
+
+fn tls_wrap(host: &str, stream: TcpStream) -> Result<SmtpStream, SDKError> {
+    let connector = TlsConnector::new().map_err(mailer_error)?;
+    let stream = connector.connect(host, stream).map_err(mailer_error)?;
+
+    Ok(SmtpStream::Tls(Box::new(stream)))
+}
+
+// reads a possibly multi-line reply and checks its code
+fn read_reply(stream: &mut SmtpStream, expected: u16) -> Result<String, SDKError> {
+    let mut reply = String::new();
+
+    loop {
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+
+        while !line.ends_with(b"\r\n") {
+            if stream.read(&mut byte).map_err(mailer_error)? == 0 {
+                return Err(mailer_error("connection closed by the SMTP server"));
+            }
+
+            line.push(byte[0]);
+        }
+
+        let line = String::from_utf8_lossy(&line).to_string();
+        reply.push_str(&line);
+
+        // "250-..." continues the reply, "250 ..." ends it
+        if line.len() < 4 || line.as_bytes()[3] != b'-' {
+            break;
+        }
+    }
+
+    let code = reply.get(0..3).and_then(|code| code.parse::<u16>().ok());
+
+    if code != Some(expected) {
+        return Err(mailer_error(format!("unexpected SMTP reply: {}", reply.trim_end())));
+    }
+
+    Ok(reply)
+}
+
+fn command(stream: &mut SmtpStream, line: &str, expected: u16) -> Result<String, SDKError> {
+    stream
+        .write_all(format!("{}\r\n", line).as_bytes())
+        .map_err(mailer_error)?;
+
+    read_reply(stream, expected)
+}
+
+fn deliver(config: &SmtpConfig, message: &EmailMessage) -> Result<(), SDKError> {
+    let from_address = match (config.from.find('<'), config.from.find('>')) {
+        (Some(start), Some(end)) if start < end => &config.from[start + 1..end],
+        _ => config.from.as_str(),
+    };
+
+    // the addresses go into the SMTP session and the headers as they are
+    for address in [from_address, message.to.as_str()] {
+        if !is_valid_address(address) {
+            return Err(mailer_error(format!(
+                "invalid email address: {}",
+                address.escape_debug()
+            )));
+        }
+    }
+
+    let tcp = TcpStream::connect((config.host.as_str(), config.port)).map_err(mailer_error)?;
+    tcp.set_read_timeout(Some(config.timeout)).map_err(mailer_error)?;
+    tcp.set_write_timeout(Some(config.timeout)).map_err(mailer_error)?;
+
+    let mut stream = match config.security {
+        SmtpSecurity::Tls => tls_wrap(&config.host, tcp)?,
+        _ => SmtpStream::Plain(tcp),
+    };
+
+    read_reply(&mut stream, 220)?;
+    command(&mut stream, &format!("EHLO {}", config.hello_name), 250)?;
+
+    if config.security == SmtpSecurity::StartTls {
+        command(&mut stream, "STARTTLS", 220)?;
+
+        let tcp = match stream {
+            SmtpStream::Plain(tcp) => tcp,
+            SmtpStream::Tls(_) => unreachable!(),
+        };
+
+        stream = tls_wrap(&config.host, tcp)?;
+
+        command(&mut stream, &format!("EHLO {}", config.hello_name), 250)?;
+    }
+
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        // AUTH PLAIN is only base64, the credentials never go out in the clear
+        if let SmtpStream::Plain(_) = stream {
+            return Err(mailer_error("refusing to authenticate without TLS or STARTTLS"));
+        }
+
+        let credentials = STANDARD.encode(format!("\0{}\0{}", username, password));
+
+        command(&mut stream, &format!("AUTH PLAIN {}", credentials), 235)?;
+    }
+
+    command(&mut stream, &format!("MAIL FROM:<{}>", from_address), 250)?;
+    command(&mut stream, &format!("RCPT TO:<{}>", message.to), 250)?;
+    command(&mut stream, "DATA", 354)?;
+
+    // lines starting with a dot are escaped so they don't end the data early
+    let data = message
+        .to_mime(&config.from)
+        .split("\r\n")
+        .map(|line| match line.starts_with('.') {
+            true => format!(".{}", line),
+            false => line.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("\r\n");
+
+    // the mime body already ends with a line break
+    stream.write_all(data.as_bytes()).map_err(mailer_error)?;
+    command(&mut stream, ".", 250)?;
+
+    let _ = command(&mut stream, "QUIT", 221);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use askama::Template;
+
+// Every email has a plain text and an html template fed with the same data.
+
+#[derive(Debug, Clone)]
+pub struct EmailTask {
+    pub label: String,
+    pub status: String,
+    pub priority: String,
+    pub due_date: Option<String>,
+    pub project: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DigestProject {
+    pub name: String,
+    pub created: Vec<EmailTask>,
+    pub completed: Vec<EmailTask>,
+    pub updated: Vec<EmailTask>,
+}
+
+#[derive(Template)]
+#[template(path = "emails/task_assigned.txt.jinja", ext = "txt", escape = "none")]
+pub struct TaskAssignedTextTemplate<'a> {
+    pub recipient: &'a str,
+    pub task: &'a EmailTask,
+    pub description: Option<&'a str>,
+}
+
+#[derive(Template)]
+#[template(path = "emails/task_assigned.html.jinja", ext = "html")]
+pub struct TaskAssignedHtmlTemplate<'a> {
+    pub recipient: &'a str,
+    pub task: &'a EmailTask,
+    pub description: Option<&'a str>,
+}
+
+#[derive(Template)]
+#[template(path = "emails/task_due_soon.txt.jinja", ext = "txt", escape = "none")]
+pub struct TaskDueSoonTextTemplate<'a> {
+    pub recipient: &'a str,
+    pub task: &'a EmailTask,
+    pub overdue: bool,
+}
+
+#[derive(Template)]
+#[template(path = "emails/task_due_soon.html.jinja", ext = "html")]
+pub struct TaskDueSoonHtmlTemplate<'a> {
+    pub recipient: &'a str,
+    pub task: &'a EmailTask,
+    pub overdue: bool,
+}
+
+#[derive(Template)]
+#[template(path = "emails/digest.txt.jinja", ext = "txt", escape = "none")]
+pub struct DigestTextTemplate<'a> {
+    pub recipient: &'a str,
+    pub period: &'a str,
+    pub projects: &'a [DigestProject],
+}
+
+#[derive(Template)]
+#[template(path = "emails/digest.html.jinja", ext = "html")]
+pub struct DigestHtmlTemplate<'a> {
+    pub recipient: &'a str,
+    pub period: &'a str,
+    pub projects: &'a [DigestProject],
+}
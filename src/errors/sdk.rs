@@ -6,12 +6,62 @@ pub enum SDKError {
     VersionNotFound,
     #[error("Resource not found")]
     ResourceNotFound,
+    #[error("Task dependency would create a cycle")]
+    TaskDependencyCycle,
+    #[error("Task has open blockers")]
+    TaskBlocked,
+    #[error("Workflow state does not belong to the project workflow")]
+    InvalidWorkflowState,
+    #[error("Workflow transition not allowed")]
+    WorkflowTransitionNotAllowed,
+    #[error("Invalid value for custom field {0}")]
+    InvalidCustomFieldValue(String),
+    #[error("Comment must belong to exactly one task or project")]
+    InvalidCommentTarget,
+    #[error("Not an occurrence of the task recurrence")]
+    InvalidTaskOccurrence,
+    #[error("Watch subscription must target exactly one task or project")]
+    InvalidWatchTarget,
+    #[error("Cycle must belong to exactly one team or project")]
+    InvalidCycleTarget,
+    #[error("Cycle must end after it starts")]
+    InvalidCycleDates,
+    #[error("Cycle is already completed")]
+    CycleAlreadyCompleted,
+    #[error("Cycle does not belong to the task project or its teams")]
+    TaskCycleMismatch,
+    #[error("Milestone does not belong to the task project")]
+    TaskMilestoneMismatch,
+    #[error("Invalid automation rule, {0}")]
+    InvalidAutomationRule(String),
+    #[error("Analytics range must end after it starts")]
+    InvalidAnalyticsRange,
+    #[error("Time off must end after it starts")]
+    InvalidTimeOffDates,
+    #[error("Invalid time entry, {0}")]
+    InvalidTimeEntry(String),
+    #[error("Member already has a running timer")]
+    TimerAlreadyRunning,
+    #[error("Invalid estimate, {0}")]
+    InvalidEstimate(String),
+    #[error("The new order must list every checklist item of the task once")]
+    InvalidChecklistOrder,
+    #[error("Missing values for template variables {0}")]
+    MissingTemplateVariables(String),
+    #[error("Tasks to order around must be distinct and share the project and status of the task")]
+    InvalidTaskOrder,
     #[error("SQLX Error")]
     SQLXError(#[from] sqlx::Error),
     #[error("Database Migration Error")]
     MigrateError(#[from] sqlx::migrate::MigrateError),
     #[error("Serde JSON Error")]
     SerdeJSONError(#[from] serde_json::Error),
+    #[error("RRule Error")]
+    RRuleError(#[from] rrule::RRuleError),
+    #[error("Mailer Error: {0}")]
+    MailerError(String),
+    #[error("Template Error")]
+    TemplateError(#[from] askama::Error),
     #[error("OpenAI Error")]
     OpenAIError(#[from] async_openai::error::OpenAIError),
 }
@@ -1,6 +1,7 @@
 pub mod backend;
 pub mod cognition;
 pub mod common;
+pub mod emails;
 pub mod errors;
 pub mod organization;
 pub mod resources;
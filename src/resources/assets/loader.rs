@@ -24,7 +24,7 @@ impl Loader<Uuid> for AssetLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let assets = sqlx::query!(
             r#"
-            SELECT * FROM assets WHERE id  = ANY($1)
+            SELECT * FROM assets WHERE id  = ANY($1) AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -171,7 +171,7 @@ impl AssetCrudOperations for SDKEngine {
     async fn get_asset(&self, id: Uuid) -> Result<Asset, SDKError> {
         let asset_info = sqlx::query!(
             r#"
-            SELECT * FROM assets WHERE id = $1
+            SELECT * FROM assets WHERE id = $1 AND deleted_at IS NULL
             "#,
             id,
         )
@@ -190,10 +190,10 @@ impl AssetCrudOperations for SDKEngine {
     }
 
     async fn get_assets(&self, input: GetAssetsInput) -> Result<Vec<Asset>, SDKError> {
-        let mut query = "SELECT * FROM assets ".to_string();
+        let mut query = "SELECT * FROM assets WHERE deleted_at IS NULL ".to_string();
 
         if let Some(filter) = input.filter {
-            query.push_str(&format!("WHERE {} ", filter.compile_sql()));
+            query.push_str(&format!("AND ({}) ", filter.compile_sql()));
         }
 
         if let Some(sort_by) = input.sort_by {
@@ -241,7 +241,7 @@ impl AssetCrudOperations for SDKEngine {
                 name = COALESCE($1, name),
                 kind = COALESCE($2, kind),
                 project_id = COALESCE($3, project_id)
-            WHERE id = $4
+            WHERE id = $4 AND deleted_at IS NULL
             RETURNING *
             "#,
             input.name,
@@ -266,7 +266,8 @@ impl AssetCrudOperations for SDKEngine {
     async fn delete_asset(&self, id: Uuid) -> Result<Asset, SDKError> {
         let asset_info = sqlx::query!(
             r#"
-            DELETE FROM assets WHERE id = $1
+            UPDATE assets SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
             id,
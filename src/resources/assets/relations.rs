@@ -14,7 +14,12 @@ pub trait AssetRelations {
 #[async_trait]
 impl AssetRelations for Asset {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
-        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
 
         Ok(data)
     }
@@ -24,8 +29,8 @@ impl AssetRelations for Asset {
             return Ok(None);
         };
 
-        let data = loaders.project_loader.load_one(project_id).await.unwrap().unwrap();
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
     }
 }
@@ -0,0 +1,115 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::sdk::SDKError,
+    resources::tasks::{
+        operations::GetTasksWhere,
+        task::{TaskPriority, TaskStatus},
+    },
+};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKAutomationRule")]
+pub struct AutomationRule {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub project_id: Option<Uuid>,
+
+    pub name: String,
+    pub description: Option<String>,
+    pub enabled: bool,
+
+    pub trigger: AutomationTrigger,
+    pub trigger_status: Option<TaskStatus>,
+    pub trigger_label_id: Option<Uuid>,
+
+    // JSON encoded `GetTasksWhere` the task must match
+    pub conditions: Option<String>,
+    // JSON encoded list of `AutomationAction`
+    pub actions: String,
+
+    pub position: i32,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKAutomationRun")]
+pub struct AutomationRun {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub rule_id: Uuid,
+    pub task_id: Option<Uuid>,
+
+    pub trigger: AutomationTrigger,
+    pub status: AutomationRunStatus,
+    // how many automations led to this one
+    pub depth: i32,
+    pub message: Option<String>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum AutomationTrigger {
+    #[default]
+    TaskCreated,
+    TaskUpdated,
+    TaskStatusChanged,
+    TaskAssigned,
+    TaskLabelAdded,
+    // fires on the parent once every subtask is done or canceled
+    SubtasksCompleted,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum AutomationRunStatus {
+    #[default]
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum AutomationAction {
+    SetStatus { status: TaskStatus },
+    SetPriority { priority: TaskPriority },
+    SetLead { member_id: Uuid },
+    Assign { member_id: Uuid },
+    // assigns whoever made the change that triggered the rule
+    AssignActor,
+    Unassign { member_id: Uuid },
+    AddLabel { label_id: Uuid },
+    RemoveLabel { label_id: Uuid },
+    // posted on the task in the name of the rule owner
+    Comment { body: String },
+}
+
+impl AutomationRule {
+    pub fn parse_conditions(conditions: Option<&str>) -> Result<Option<GetTasksWhere>, SDKError> {
+        conditions
+            .map(|conditions| {
+                serde_json::from_str::<GetTasksWhere>(conditions)
+                    .map_err(|err| SDKError::InvalidAutomationRule(format!("conditions: {}", err)))
+            })
+            .transpose()
+    }
+
+    pub fn parse_actions(actions: &str) -> Result<Vec<AutomationAction>, SDKError> {
+        serde_json::from_str::<Vec<AutomationAction>>(actions)
+            .map_err(|err| SDKError::InvalidAutomationRule(format!("actions: {}", err)))
+    }
+}
@@ -0,0 +1,3 @@
+pub mod automation;
+pub mod operations;
+pub mod runner;
@@ -0,0 +1,309 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError, resources::tasks::task::TaskStatus};
+
+use super::automation::{AutomationRule, AutomationRun, AutomationRunStatus, AutomationTrigger};
+
+#[async_trait]
+pub trait AutomationOperations {
+    async fn create_automation_rule(&self, input: CreateAutomationRuleInput) -> Result<AutomationRule, SDKError>;
+    async fn get_automation_rule(&self, id: Uuid) -> Result<AutomationRule, SDKError>;
+    async fn get_automation_rules(&self, input: GetAutomationRulesInput) -> Result<Vec<AutomationRule>, SDKError>;
+    async fn update_automation_rule(
+        &self,
+        id: Uuid,
+        input: UpdateAutomationRuleInput,
+    ) -> Result<AutomationRule, SDKError>;
+    async fn delete_automation_rule(&self, id: Uuid) -> Result<AutomationRule, SDKError>;
+
+    async fn get_automation_runs(&self, input: GetAutomationRunsInput) -> Result<Vec<AutomationRun>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateAutomationRuleInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    // JSON list of actions, e.g. [{"type": "SetPriority", "priority": "High"}]
+    pub actions: String,
+
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub enabled: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub trigger_status: Option<TaskStatus>,
+    #[builder(setter(strip_option), default)]
+    pub trigger_label_id: Option<Uuid>,
+    // JSON encoded `GetTasksWhere`
+    #[builder(setter(strip_option), default)]
+    pub conditions: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateAutomationRuleInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub enabled: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub trigger: Option<AutomationTrigger>,
+    #[builder(setter(strip_option), default)]
+    pub trigger_status: Option<TaskStatus>,
+    #[builder(setter(strip_option), default)]
+    pub trigger_label_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub conditions: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub actions: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetAutomationRulesInput {
+    // rules without a project are included for every project
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub trigger: Option<AutomationTrigger>,
+    #[builder(setter(strip_option), default)]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetAutomationRunsInput {
+    #[builder(setter(strip_option), default)]
+    pub rule_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub status: Option<AutomationRunStatus>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+const AUTOMATION_RULE_COLUMNS: &str = "id, created_at, updated_at, owner_id, project_id, name, description, enabled, \
+    trigger, trigger_status, trigger_label_id, conditions::text AS conditions, actions::text AS actions, position";
+
+fn automation_rule_from_row(row: &PgRow) -> AutomationRule {
+    AutomationRule {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        owner_id: row.get("owner_id"),
+        project_id: row.get("project_id"),
+        name: row.get("name"),
+        description: row.get("description"),
+        enabled: row.get("enabled"),
+        trigger: AutomationTrigger::from_str(row.get("trigger")).unwrap_or_default(),
+        trigger_status: row
+            .get::<Option<String>, _>("trigger_status")
+            .and_then(|status| TaskStatus::from_str(&status).ok()),
+        trigger_label_id: row.get("trigger_label_id"),
+        conditions: row.get("conditions"),
+        actions: row.get("actions"),
+        position: row.get("position"),
+    }
+}
+
+fn automation_run_from_row(row: &PgRow) -> AutomationRun {
+    AutomationRun {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        rule_id: row.get("rule_id"),
+        task_id: row.get("task_id"),
+        trigger: AutomationTrigger::from_str(row.get("trigger")).unwrap_or_default(),
+        status: AutomationRunStatus::from_str(row.get("status")).unwrap_or_default(),
+        depth: row.get("depth"),
+        message: row.get("message"),
+    }
+}
+
+#[async_trait]
+impl AutomationOperations for SDKEngine {
+    async fn create_automation_rule(&self, input: CreateAutomationRuleInput) -> Result<AutomationRule, SDKError> {
+        AutomationRule::parse_conditions(input.conditions.as_deref())?;
+        AutomationRule::parse_actions(&input.actions)?;
+
+        let rule_info = sqlx::query(&format!(
+            r#"
+            INSERT INTO automation_rules (owner_id, project_id, name, description, enabled, trigger, trigger_status, trigger_label_id, conditions, actions, position)
+            VALUES ($1, $2, $3, $4, COALESCE($5, true), $6, $7, $8, $9::jsonb, $10::jsonb, COALESCE($11, 0))
+            RETURNING {}
+            "#,
+            AUTOMATION_RULE_COLUMNS,
+        ))
+        .bind(input.owner_id)
+        .bind(input.project_id)
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.enabled)
+        .bind(input.trigger.to_string())
+        .bind(input.trigger_status.map(|status| status.to_string()))
+        .bind(input.trigger_label_id)
+        .bind(input.conditions)
+        .bind(input.actions)
+        .bind(input.position)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(automation_rule_from_row(&rule_info))
+    }
+
+    async fn get_automation_rule(&self, id: Uuid) -> Result<AutomationRule, SDKError> {
+        let rule_info = sqlx::query(&format!(
+            "SELECT {} FROM automation_rules WHERE id = $1 AND deleted_at IS NULL",
+            AUTOMATION_RULE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(automation_rule_from_row(&rule_info))
+    }
+
+    async fn get_automation_rules(&self, input: GetAutomationRulesInput) -> Result<Vec<AutomationRule>, SDKError> {
+        let mut and_clauses = vec!["deleted_at IS NULL".to_string()];
+
+        if let Some(project_id) = &input.project_id {
+            and_clauses.push(format!("(project_id = '{}' OR project_id IS NULL)", project_id));
+        }
+
+        if let Some(trigger) = &input.trigger {
+            and_clauses.push(format!("trigger = '{}'", trigger));
+        }
+
+        if let Some(enabled) = &input.enabled {
+            and_clauses.push(format!("enabled = {}", enabled));
+        }
+
+        let mut query = format!("SELECT {} FROM automation_rules ", AUTOMATION_RULE_COLUMNS);
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY position, created_at");
+
+        let rules_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(rules_info.iter().map(automation_rule_from_row).collect())
+    }
+
+    async fn update_automation_rule(
+        &self,
+        id: Uuid,
+        input: UpdateAutomationRuleInput,
+    ) -> Result<AutomationRule, SDKError> {
+        AutomationRule::parse_conditions(input.conditions.as_deref())?;
+
+        if let Some(actions) = &input.actions {
+            AutomationRule::parse_actions(actions)?;
+        }
+
+        let rule_info = sqlx::query(&format!(
+            r#"
+            UPDATE automation_rules
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                enabled = COALESCE($3, enabled),
+                trigger = COALESCE($4, trigger),
+                trigger_status = COALESCE($5, trigger_status),
+                trigger_label_id = COALESCE($6, trigger_label_id),
+                conditions = COALESCE($7::jsonb, conditions),
+                actions = COALESCE($8::jsonb, actions),
+                position = COALESCE($9, position)
+            WHERE id = $10 AND deleted_at IS NULL
+            RETURNING {}
+            "#,
+            AUTOMATION_RULE_COLUMNS,
+        ))
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.enabled)
+        .bind(input.trigger.map(|trigger| trigger.to_string()))
+        .bind(input.trigger_status.map(|status| status.to_string()))
+        .bind(input.trigger_label_id)
+        .bind(input.conditions)
+        .bind(input.actions)
+        .bind(input.position)
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(automation_rule_from_row(&rule_info))
+    }
+
+    async fn delete_automation_rule(&self, id: Uuid) -> Result<AutomationRule, SDKError> {
+        let rule_info = sqlx::query(&format!(
+            "UPDATE automation_rules SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL RETURNING {}",
+            AUTOMATION_RULE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(automation_rule_from_row(&rule_info))
+    }
+
+    async fn get_automation_runs(&self, input: GetAutomationRunsInput) -> Result<Vec<AutomationRun>, SDKError> {
+        let mut and_clauses = Vec::new();
+
+        if let Some(rule_id) = &input.rule_id {
+            and_clauses.push(format!("rule_id = '{}'", rule_id));
+        }
+
+        if let Some(task_id) = &input.task_id {
+            and_clauses.push(format!("task_id = '{}'", task_id));
+        }
+
+        if let Some(status) = &input.status {
+            and_clauses.push(format!("status = '{}'", status));
+        }
+
+        let mut query = "SELECT * FROM automation_runs ".to_string();
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY created_at DESC ");
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let runs_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(runs_info.iter().map(automation_run_from_row).collect())
+    }
+}
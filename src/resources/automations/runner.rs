@@ -0,0 +1,429 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{commons::UpdateListInput, runner::RunnerHandle},
+    errors::sdk::SDKError,
+    resources::{
+        comments::operations::{CommentCrudOperations, CreateCommentInputBuilder},
+        tasks::{
+            operations::{TaskCrudOperations, UpdateTaskInput},
+            task::{Task, TaskStatus},
+            watchers::{TaskEvent, TaskEventKind, TaskWatcherScope, TaskWatchers},
+        },
+    },
+};
+
+use super::{
+    automation::{AutomationAction, AutomationRule, AutomationRun, AutomationRunStatus, AutomationTrigger},
+    operations::{AutomationOperations, GetAutomationRulesInputBuilder},
+};
+
+pub const MAX_AUTOMATION_DEPTH: i32 = 5;
+
+// how long a change made by a rule is remembered, so the events it causes can be
+// traced back to it. Rules tag their writes, any other change starts afresh.
+const CHAIN_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Default)]
+struct AutomationChain {
+    depth: i32,
+    fired_rule_ids: HashSet<Uuid>,
+}
+
+// Rules are matched against the events of `TaskWatchers`. Changes made by a rule
+// are tracked per task, a rule never fires twice within the same chain of changes
+// and chains stop after `max_depth` automations.
+#[derive(Clone)]
+pub struct AutomationRunner {
+    engine: Arc<SDKEngine>,
+    max_depth: i32,
+    chains: Arc<Mutex<HashMap<Uuid, (AutomationChain, Instant)>>>,
+}
+
+impl AutomationRunner {
+    pub fn new(engine: Arc<SDKEngine>) -> Self {
+        Self {
+            engine,
+            max_depth: MAX_AUTOMATION_DEPTH,
+            chains: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: i32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    // registers the runner on watchers that are spawned by the caller
+    pub fn attach(&self, watchers: &TaskWatchers) -> Vec<Uuid> {
+        [
+            TaskEventKind::Created,
+            TaskEventKind::Updated,
+            TaskEventKind::StatusChanged,
+            TaskEventKind::Assigned,
+            TaskEventKind::Labeled,
+        ]
+        .into_iter()
+        .map(|kind| {
+            let runner = self.clone();
+
+            watchers.on(kind, TaskWatcherScope::default(), move |event| {
+                let runner = runner.clone();
+
+                async move {
+                    runner.handle(event).await?;
+                    Ok(())
+                }
+            })
+        })
+        .collect()
+    }
+
+    pub async fn spawn(self) -> Result<RunnerHandle, SDKError> {
+        let watchers = TaskWatchers::new(self.engine.clone());
+        self.attach(&watchers);
+
+        watchers.spawn().await
+    }
+
+    pub async fn handle(&self, event: TaskEvent) -> Result<Vec<AutomationRun>, SDKError> {
+        let chain = match event.automation_rule_id {
+            Some(_) => self.chain(event.task.id),
+            None => AutomationChain::default(),
+        };
+        let mut runs = Vec::new();
+
+        let trigger = match event.kind {
+            TaskEventKind::Created => AutomationTrigger::TaskCreated,
+            TaskEventKind::Updated => AutomationTrigger::TaskUpdated,
+            TaskEventKind::StatusChanged => AutomationTrigger::TaskStatusChanged,
+            TaskEventKind::Assigned => AutomationTrigger::TaskAssigned,
+            TaskEventKind::Labeled => AutomationTrigger::TaskLabelAdded,
+            TaskEventKind::Deleted => return Ok(runs),
+        };
+
+        runs.extend(self.run_rules(trigger, &event, &event.task, &chain).await?);
+
+        if event.kind == TaskEventKind::StatusChanged {
+            if let Some(parent) = self.completed_parent(&event.task).await? {
+                runs.extend(
+                    self.run_rules(AutomationTrigger::SubtasksCompleted, &event, &parent, &chain)
+                        .await?,
+                );
+            }
+        }
+
+        Ok(runs)
+    }
+
+    fn chain(&self, task_id: Uuid) -> AutomationChain {
+        let mut chains = self.chains.lock().unwrap();
+        chains.retain(|_, (_, at)| at.elapsed() < CHAIN_TTL);
+
+        chains.get(&task_id).map(|(chain, _)| chain.clone()).unwrap_or_default()
+    }
+
+    // several rules can fire on the same event, their chains are merged
+    fn extend_chain(&self, task_id: Uuid, chain: &AutomationChain, rule_id: Uuid) {
+        let mut chains = self.chains.lock().unwrap();
+        chains.retain(|_, (_, at)| at.elapsed() < CHAIN_TTL);
+
+        let (extended, at) = chains
+            .entry(task_id)
+            .or_insert_with(|| (AutomationChain::default(), Instant::now()));
+
+        extended.depth = extended.depth.max(chain.depth + 1);
+        extended.fired_rule_ids.extend(chain.fired_rule_ids.iter().copied());
+        extended.fired_rule_ids.insert(rule_id);
+        *at = Instant::now();
+    }
+
+    async fn completed_parent(&self, task: &Task) -> Result<Option<Task>, SDKError> {
+        let Some(parent_id) = task.parent_id else {
+            return Ok(None);
+        };
+
+        if !matches!(task.status, TaskStatus::Done | TaskStatus::Canceled) {
+            return Ok(None);
+        }
+
+        let subtasks = sqlx::query!(
+            r#"
+            SELECT bool_and(COALESCE(status, '') IN ('Done', 'Canceled')) AS "completed!"
+            FROM tasks
+            WHERE parent_id = $1 AND deleted_at IS NULL
+            "#,
+            parent_id,
+        )
+        .fetch_one(self.engine.db_pool.as_ref())
+        .await?;
+
+        if !subtasks.completed {
+            return Ok(None);
+        }
+
+        Ok(Some(self.engine.get_task(parent_id).await?))
+    }
+
+    async fn run_rules(
+        &self,
+        trigger: AutomationTrigger,
+        event: &TaskEvent,
+        task: &Task,
+        chain: &AutomationChain,
+    ) -> Result<Vec<AutomationRun>, SDKError> {
+        let mut input = GetAutomationRulesInputBuilder::default().trigger(trigger).enabled(true);
+
+        if let Some(project_id) = task.project_id {
+            input = input.project_id(project_id);
+        }
+
+        let rules = self.engine.get_automation_rules(input.build().unwrap()).await?;
+
+        let mut runs = Vec::new();
+
+        for rule in rules {
+            // a rule whose conditions can't be read never runs its actions
+            let matched = match self.rule_matches(&rule, event, task).await {
+                Ok(matched) => matched,
+                Err(err @ SDKError::InvalidAutomationRule(_)) => {
+                    runs.push(
+                        self.log_run(&rule, task, chain, AutomationRunStatus::Failed, Some(err.to_string()))
+                            .await?,
+                    );
+
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
+            if !matched {
+                continue;
+            }
+
+            let run = if chain.fired_rule_ids.contains(&rule.id) {
+                self.log_run(
+                    &rule,
+                    task,
+                    chain,
+                    AutomationRunStatus::Skipped,
+                    Some("Rule already ran in this chain".to_string()),
+                )
+                .await?
+            } else if chain.depth >= self.max_depth {
+                self.log_run(
+                    &rule,
+                    task,
+                    chain,
+                    AutomationRunStatus::Skipped,
+                    Some("Maximum automation depth reached".to_string()),
+                )
+                .await?
+            } else {
+                self.extend_chain(task.id, chain, rule.id);
+
+                match self.execute(&rule, event, task).await {
+                    Ok(message) => {
+                        self.log_run(&rule, task, chain, AutomationRunStatus::Succeeded, message)
+                            .await?
+                    }
+                    Err(err) => {
+                        self.log_run(&rule, task, chain, AutomationRunStatus::Failed, Some(err.to_string()))
+                            .await?
+                    }
+                }
+            };
+
+            runs.push(run);
+        }
+
+        Ok(runs)
+    }
+
+    async fn rule_matches(&self, rule: &AutomationRule, event: &TaskEvent, task: &Task) -> Result<bool, SDKError> {
+        if rule.project_id.is_some() && rule.project_id != task.project_id {
+            return Ok(false);
+        }
+
+        if rule.trigger_status.is_some_and(|status| status != task.status) {
+            return Ok(false);
+        }
+
+        if rule
+            .trigger_label_id
+            .is_some_and(|label_id| !event.added_label_ids.contains(&label_id))
+        {
+            return Ok(false);
+        }
+
+        let Some(conditions) = AutomationRule::parse_conditions(rule.conditions.as_deref())? else {
+            return Ok(true);
+        };
+
+        let conditions = conditions.compile_sql();
+
+        if conditions.is_empty() {
+            return Ok(true);
+        }
+
+        let matched: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = $1 AND ({}))",
+            conditions
+        ))
+        .bind(task.id)
+        .fetch_one(self.engine.db_pool.as_ref())
+        .await?;
+
+        Ok(matched)
+    }
+
+    // Every action of a rule is folded into a single task update, so a rule causes
+    // at most one round of events.
+    async fn execute(&self, rule: &AutomationRule, event: &TaskEvent, task: &Task) -> Result<Option<String>, SDKError> {
+        let actions = AutomationRule::parse_actions(&rule.actions)?;
+
+        // earlier rules may have changed the task since the event was emitted
+        let task = self.engine.get_task(task.id).await?;
+
+        let current = sqlx::query!(
+            r#"
+            SELECT
+                array(SELECT assignee_id FROM tasks_by_assignees WHERE task_id = $1) AS "assignee_ids!",
+                array(SELECT label_id FROM labels_by_tasks WHERE task_id = $1) AS "label_ids!"
+            "#,
+            task.id,
+        )
+        .fetch_one(self.engine.db_pool.as_ref())
+        .await?;
+
+        let mut update = UpdateTaskInput {
+            automation_rule_id: Some(rule.id),
+            ..Default::default()
+        };
+        let mut assignees = UpdateListInput::default();
+        let mut labels = UpdateListInput::default();
+        let mut comments = Vec::new();
+        let mut notes = Vec::new();
+
+        for action in actions {
+            match action {
+                AutomationAction::SetStatus { status } => update.status = Some(status),
+                AutomationAction::SetPriority { priority } => update.priority = Some(priority),
+                AutomationAction::SetLead { member_id } => update.lead_id = Some(member_id),
+                AutomationAction::Assign { member_id } => assignees.add.push(member_id),
+                AutomationAction::AssignActor => match event.actor_id {
+                    Some(actor_id) => assignees.add.push(actor_id),
+                    None => notes.push("No known actor to assign".to_string()),
+                },
+                AutomationAction::Unassign { member_id } => assignees.remove.push(member_id),
+                AutomationAction::AddLabel { label_id } => labels.add.push(label_id),
+                AutomationAction::RemoveLabel { label_id } => labels.remove.push(label_id),
+                AutomationAction::Comment { body } => comments.push(body),
+            }
+        }
+
+        assignees.add.retain(|id| !current.assignee_ids.contains(id));
+        assignees.remove.retain(|id| current.assignee_ids.contains(id));
+        labels.add.retain(|id| !current.label_ids.contains(id));
+        labels.remove.retain(|id| current.label_ids.contains(id));
+
+        assignees.add.dedup();
+        labels.add.dedup();
+
+        if update.status == Some(task.status) {
+            update.status = None;
+        }
+
+        if update.priority == Some(task.priority) {
+            update.priority = None;
+        }
+
+        if update.lead_id.is_some() && update.lead_id == task.lead_id {
+            update.lead_id = None;
+        }
+
+        if !assignees.add.is_empty() || !assignees.remove.is_empty() {
+            update.assignees = Some(assignees);
+        }
+
+        if !labels.add.is_empty() || !labels.remove.is_empty() {
+            update.labels = Some(labels);
+        }
+
+        let changed = update.status.is_some()
+            || update.priority.is_some()
+            || update.lead_id.is_some()
+            || update.assignees.is_some()
+            || update.labels.is_some();
+
+        if changed {
+            self.engine.update_task(task.id, update).await?;
+        } else {
+            notes.push("Task already up to date".to_string());
+        }
+
+        for body in comments {
+            self.engine
+                .create_comment(
+                    CreateCommentInputBuilder::default()
+                        .author_id(rule.owner_id)
+                        .body(body)
+                        .task_id(task.id)
+                        .build()
+                        .unwrap(),
+                )
+                .await?;
+        }
+
+        Ok((!notes.is_empty()).then(|| notes.join(", ")))
+    }
+
+    async fn log_run(
+        &self,
+        rule: &AutomationRule,
+        task: &Task,
+        chain: &AutomationChain,
+        status: AutomationRunStatus,
+        message: Option<String>,
+    ) -> Result<AutomationRun, SDKError> {
+        let run_info = sqlx::query!(
+            r#"
+            INSERT INTO automation_runs (rule_id, task_id, trigger, status, depth, message)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+            rule.id,
+            task.id,
+            rule.trigger.to_string(),
+            status.to_string(),
+            chain.depth,
+            message,
+        )
+        .fetch_one(self.engine.db_pool.as_ref())
+        .await?;
+
+        Ok(AutomationRun {
+            id: run_info.id,
+            created_at: run_info.created_at,
+            rule_id: run_info.rule_id,
+            task_id: run_info.task_id,
+            trigger: rule.trigger,
+            status,
+            depth: run_info.depth,
+            message: run_info.message,
+        })
+    }
+}
+
+impl SDKEngine {
+    pub async fn spawn_automation_runner(self: &Arc<Self>) -> Result<RunnerHandle, SDKError> {
+        AutomationRunner::new(self.clone()).spawn().await
+    }
+}
@@ -0,0 +1,57 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::projects::project::EstimateScale;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKMemberCapacity")]
+pub struct MemberCapacity {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub member_id: Uuid,
+
+    pub unit: CapacityUnit,
+    pub weekly_capacity: f64,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTimeOff")]
+pub struct TimeOff {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub member_id: Uuid,
+
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum CapacityUnit {
+    #[default]
+    Points,
+    Hours,
+}
+
+impl CapacityUnit {
+    // t-shirt sizes are stored as their point values
+    pub fn measures(&self, scale: EstimateScale) -> bool {
+        match self {
+            CapacityUnit::Points => matches!(scale, EstimateScale::Points | EstimateScale::TShirt),
+            CapacityUnit::Hours => scale == EstimateScale::Hours,
+        }
+    }
+}
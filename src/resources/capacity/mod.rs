@@ -0,0 +1,3 @@
+pub mod member_capacity;
+pub mod operations;
+pub mod workload;
@@ -0,0 +1,472 @@
+use std::{collections::HashMap, str::FromStr};
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError, resources::projects::project::EstimateScale};
+
+use super::{
+    member_capacity::{CapacityUnit, MemberCapacity, TimeOff},
+    workload::{MemberWorkload, TeamWorkload},
+};
+
+#[async_trait]
+pub trait CapacityOperations {
+    async fn set_member_capacity(&self, input: SetMemberCapacityInput) -> Result<MemberCapacity, SDKError>;
+    async fn get_member_capacity(&self, member_id: Uuid) -> Result<MemberCapacity, SDKError>;
+    async fn delete_member_capacity(&self, member_id: Uuid) -> Result<MemberCapacity, SDKError>;
+
+    async fn create_time_off(&self, input: CreateTimeOffInput) -> Result<TimeOff, SDKError>;
+    async fn get_time_off(&self, input: GetTimeOffInput) -> Result<Vec<TimeOff>, SDKError>;
+    async fn delete_time_off(&self, id: Uuid) -> Result<TimeOff, SDKError>;
+
+    async fn get_member_workload(&self, member_id: Uuid, input: GetWorkloadInput) -> Result<MemberWorkload, SDKError>;
+    async fn get_team_workload(&self, team_id: Uuid, input: GetWorkloadInput) -> Result<TeamWorkload, SDKError>;
+    async fn find_available_members(&self, input: FindAvailableMembersInput) -> Result<Vec<MemberWorkload>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct SetMemberCapacityInput {
+    pub member_id: Uuid,
+    pub weekly_capacity: f64,
+
+    #[builder(setter(strip_option), default)]
+    pub unit: Option<CapacityUnit>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateTimeOffInput {
+    pub member_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+
+    #[builder(setter(strip_option), default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetTimeOffInput {
+    #[builder(setter(strip_option), default)]
+    pub member_id: Option<Uuid>,
+    // time off overlapping the range
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetWorkloadInput {
+    // now when left out
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    // a week after `from` when left out
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct FindAvailableMembersInput {
+    // every member when left out
+    #[builder(setter(strip_option), default)]
+    pub team_id: Option<Uuid>,
+
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+
+    // room the new work needs, any room at all when left out
+    #[builder(setter(strip_option), default)]
+    pub needed: Option<f64>,
+}
+
+impl GetWorkloadInput {
+    fn range(&self) -> Result<(DateTime<Utc>, DateTime<Utc>), SDKError> {
+        let from = self.from.unwrap_or_else(Utc::now);
+        let to = self.to.unwrap_or(from + Duration::try_weeks(1).unwrap_or_default());
+
+        if from >= to {
+            return Err(SDKError::InvalidAnalyticsRange);
+        }
+
+        Ok((from, to))
+    }
+}
+
+pub(crate) fn capacity_from_row(row: &PgRow) -> MemberCapacity {
+    MemberCapacity {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        member_id: row.get("member_id"),
+        unit: CapacityUnit::from_str(row.get("unit")).unwrap_or_default(),
+        weekly_capacity: row.get("weekly_capacity"),
+    }
+}
+
+pub(crate) fn time_off_from_row(row: &PgRow) -> TimeOff {
+    TimeOff {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        member_id: row.get("member_id"),
+        starts_at: row.get("starts_at"),
+        ends_at: row.get("ends_at"),
+        reason: row.get("reason"),
+    }
+}
+
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds() as f64 / 86400.0
+}
+
+// overlapping entries are merged first so shared days only count once
+fn merged_days(mut periods: Vec<(DateTime<Utc>, DateTime<Utc>)>) -> f64 {
+    periods.sort();
+
+    let mut merged: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::with_capacity(periods.len());
+
+    for (starts_at, ends_at) in periods {
+        match merged.last_mut() {
+            Some((_, last_ends_at)) if starts_at <= *last_ends_at => *last_ends_at = ends_at.max(*last_ends_at),
+            _ => merged.push((starts_at, ends_at)),
+        }
+    }
+
+    merged.into_iter().fold(0.0, |total, (starts_at, ends_at)| {
+        total + days_between(starts_at, ends_at)
+    })
+}
+
+impl SDKEngine {
+    // Open tasks count their estimate as load, one unit when they have none.
+    // Tasks due before the end of the range are counted, overdue ones
+    // included, along with undated tasks already in progress.
+    async fn load_members_workload(
+        &self,
+        member_ids: &[Uuid],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<MemberWorkload>, SDKError> {
+        let capacities = sqlx::query!(
+            r#"
+            SELECT member_id, unit, weekly_capacity FROM member_capacities
+            WHERE member_id = ANY($1)
+            "#,
+            member_ids,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let time_off = sqlx::query!(
+            r#"
+            SELECT member_id, starts_at, ends_at FROM member_time_off
+            WHERE member_id = ANY($1)
+            AND starts_at < $3
+            AND ends_at > $2
+            "#,
+            member_ids,
+            from,
+            to,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let tasks = sqlx::query!(
+            r#"
+            SELECT tasks_by_assignees.assignee_id, tasks.id, tasks.due_date, tasks.estimate, projects.estimate_scale AS "estimate_scale?"
+            FROM tasks_by_assignees
+            JOIN tasks ON tasks.id = tasks_by_assignees.task_id
+            LEFT JOIN projects ON projects.id = tasks.project_id
+            WHERE tasks_by_assignees.assignee_id = ANY($1)
+            AND COALESCE(tasks.status, 'None') NOT IN ('Done', 'Canceled')
+            AND (tasks.due_date < $2 OR (tasks.due_date IS NULL AND tasks.status = 'InProgress'))
+            AND tasks.deleted_at IS NULL
+            "#,
+            member_ids,
+            to,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let capacities: HashMap<Uuid, (CapacityUnit, f64)> = capacities
+            .into_iter()
+            .map(|capacity| {
+                (
+                    capacity.member_id,
+                    (
+                        CapacityUnit::from_str(&capacity.unit).unwrap_or_default(),
+                        capacity.weekly_capacity,
+                    ),
+                )
+            })
+            .collect();
+
+        let range_days = days_between(from, to);
+
+        Ok(member_ids
+            .iter()
+            .map(|member_id| {
+                let time_off_days = merged_days(
+                    time_off
+                        .iter()
+                        .filter(|time_off| time_off.member_id == *member_id)
+                        .map(|time_off| (time_off.starts_at.max(from), time_off.ends_at.min(to)))
+                        .collect(),
+                )
+                .min(range_days);
+
+                let member_tasks: Vec<_> = tasks.iter().filter(|task| task.assignee_id == *member_id).collect();
+
+                let capacity = capacities.get(member_id);
+                let unit = capacity.map(|(unit, _)| *unit).unwrap_or_default();
+
+                // estimates in another scale than the capacity can't be added up, those count as one unit
+                let load = member_tasks.iter().fold(0.0, |total, task| {
+                    let scale = task
+                        .estimate_scale
+                        .as_deref()
+                        .and_then(|scale| EstimateScale::from_str(scale).ok())
+                        .unwrap_or_default();
+
+                    match task.estimate {
+                        Some(estimate) if unit.measures(scale) => total + estimate,
+                        _ => total + 1.0,
+                    }
+                });
+                let available = capacity.map(|(_, weekly)| weekly / 7.0 * (range_days - time_off_days));
+
+                MemberWorkload {
+                    member_id: *member_id,
+                    from,
+                    to,
+                    unit: capacity.map(|(unit, _)| *unit),
+                    capacity: capacity.map(|(_, weekly)| weekly / 7.0 * range_days),
+                    time_off_days,
+                    available,
+                    load,
+                    open_tasks: member_tasks.len() as i64,
+                    overdue_tasks: member_tasks
+                        .iter()
+                        .filter(|task| task.due_date.is_some_and(|due_date| due_date < from))
+                        .count() as i64,
+                    task_ids: member_tasks.iter().map(|task| task.id).collect(),
+                    remaining: available.map(|available| available - load),
+                    utilization: available
+                        .filter(|available| *available > 0.0)
+                        .map(|available| load / available * 100.0),
+                    over_allocated: available.is_some_and(|available| load > available),
+                }
+            })
+            .collect())
+    }
+
+    async fn get_team_member_ids(&self, team_id: Uuid) -> Result<Vec<Uuid>, SDKError> {
+        let members = sqlx::query!(
+            r#"
+            SELECT member_id FROM members_by_teams
+            JOIN members ON members.id = members_by_teams.member_id
+            WHERE team_id = $1 AND members.deleted_at IS NULL
+            "#,
+            team_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(members.into_iter().map(|member| member.member_id).collect())
+    }
+}
+
+#[async_trait]
+impl CapacityOperations for SDKEngine {
+    async fn set_member_capacity(&self, input: SetMemberCapacityInput) -> Result<MemberCapacity, SDKError> {
+        let capacity_info = sqlx::query(
+            r#"
+            INSERT INTO member_capacities (member_id, unit, weekly_capacity)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (member_id) DO UPDATE
+            SET unit = EXCLUDED.unit, weekly_capacity = EXCLUDED.weekly_capacity
+            RETURNING *
+            "#,
+        )
+        .bind(input.member_id)
+        .bind(input.unit.unwrap_or_default().to_string())
+        .bind(input.weekly_capacity.max(0.0))
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(capacity_from_row(&capacity_info))
+    }
+
+    async fn get_member_capacity(&self, member_id: Uuid) -> Result<MemberCapacity, SDKError> {
+        let capacity_info = sqlx::query("SELECT * FROM member_capacities WHERE member_id = $1")
+            .bind(member_id)
+            .fetch_optional(self.db_pool.as_ref())
+            .await?
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(capacity_from_row(&capacity_info))
+    }
+
+    async fn delete_member_capacity(&self, member_id: Uuid) -> Result<MemberCapacity, SDKError> {
+        let capacity_info = sqlx::query("DELETE FROM member_capacities WHERE member_id = $1 RETURNING *")
+            .bind(member_id)
+            .fetch_optional(self.db_pool.as_ref())
+            .await?
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(capacity_from_row(&capacity_info))
+    }
+
+    async fn create_time_off(&self, input: CreateTimeOffInput) -> Result<TimeOff, SDKError> {
+        if input.ends_at <= input.starts_at {
+            return Err(SDKError::InvalidTimeOffDates);
+        }
+
+        let time_off_info = sqlx::query(
+            r#"
+            INSERT INTO member_time_off (member_id, starts_at, ends_at, reason)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.member_id)
+        .bind(input.starts_at)
+        .bind(input.ends_at)
+        .bind(input.reason)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(time_off_from_row(&time_off_info))
+    }
+
+    async fn get_time_off(&self, input: GetTimeOffInput) -> Result<Vec<TimeOff>, SDKError> {
+        let mut and_clauses = Vec::new();
+
+        if let Some(member_id) = &input.member_id {
+            and_clauses.push(format!("member_id = '{}'", member_id));
+        }
+
+        if let Some(from) = &input.from {
+            and_clauses.push(format!("ends_at > '{}'", from));
+        }
+
+        if let Some(to) = &input.to {
+            and_clauses.push(format!("starts_at < '{}'", to));
+        }
+
+        let mut query = "SELECT * FROM member_time_off ".to_string();
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY starts_at");
+
+        let time_off_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(time_off_info.iter().map(time_off_from_row).collect())
+    }
+
+    async fn delete_time_off(&self, id: Uuid) -> Result<TimeOff, SDKError> {
+        let time_off_info = sqlx::query("DELETE FROM member_time_off WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(time_off_from_row(&time_off_info))
+    }
+
+    async fn get_member_workload(&self, member_id: Uuid, input: GetWorkloadInput) -> Result<MemberWorkload, SDKError> {
+        let (from, to) = input.range()?;
+
+        self.load_members_workload(&[member_id], from, to)
+            .await?
+            .pop()
+            .ok_or(SDKError::ResourceNotFound)
+    }
+
+    async fn get_team_workload(&self, team_id: Uuid, input: GetWorkloadInput) -> Result<TeamWorkload, SDKError> {
+        let (from, to) = input.range()?;
+
+        let member_ids = self.get_team_member_ids(team_id).await?;
+        let members = self.load_members_workload(&member_ids, from, to).await?;
+
+        let mut units = members.iter().filter_map(|member| member.unit);
+        let unit = units.next().filter(|unit| units.all(|other| other == *unit));
+
+        let available = members
+            .iter()
+            .filter_map(|member| member.available)
+            .fold(0.0, |total, available| total + available);
+        let load = members.iter().fold(0.0, |total, member| total + member.load);
+
+        Ok(TeamWorkload {
+            team_id,
+            from,
+            to,
+            unit,
+            available,
+            load,
+            remaining: available - load,
+            utilization: (available > 0.0).then(|| load / available * 100.0),
+            over_allocated_member_ids: members
+                .iter()
+                .filter(|member| member.over_allocated)
+                .map(|member| member.member_id)
+                .collect(),
+            members,
+        })
+    }
+
+    // Members without a capacity are left out, there is no telling whether
+    // they have room. The roomiest members come first.
+    async fn find_available_members(&self, input: FindAvailableMembersInput) -> Result<Vec<MemberWorkload>, SDKError> {
+        let (from, to) = GetWorkloadInput {
+            from: input.from,
+            to: input.to,
+        }
+        .range()?;
+
+        let member_ids = match input.team_id {
+            Some(team_id) => self.get_team_member_ids(team_id).await?,
+            None => sqlx::query!("SELECT id FROM members WHERE deleted_at IS NULL")
+                .fetch_all(self.db_pool.as_ref())
+                .await?
+                .into_iter()
+                .map(|member| member.id)
+                .collect(),
+        };
+
+        let mut members: Vec<MemberWorkload> = self
+            .load_members_workload(&member_ids, from, to)
+            .await?
+            .into_iter()
+            .filter(|member| match (member.remaining, input.needed) {
+                (Some(remaining), Some(needed)) => remaining >= needed,
+                (Some(remaining), None) => remaining > 0.0,
+                (None, _) => false,
+            })
+            .collect();
+
+        members.sort_by(|a, b| {
+            b.remaining
+                .unwrap_or_default()
+                .total_cmp(&a.remaining.unwrap_or_default())
+        });
+
+        Ok(members)
+    }
+}
@@ -0,0 +1,53 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+use super::member_capacity::CapacityUnit;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKMemberWorkload")]
+pub struct MemberWorkload {
+    pub member_id: Uuid,
+
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+
+    // none while the member has no capacity set
+    pub unit: Option<CapacityUnit>,
+    pub capacity: Option<f64>,
+    pub time_off_days: f64,
+    // capacity left once time off is taken out
+    pub available: Option<f64>,
+
+    pub load: f64,
+    pub open_tasks: i64,
+    pub overdue_tasks: i64,
+    pub task_ids: Vec<Uuid>,
+
+    pub remaining: Option<f64>,
+    pub utilization: Option<f64>,
+    pub over_allocated: bool,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTeamWorkload")]
+pub struct TeamWorkload {
+    pub team_id: Uuid,
+
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+
+    // none when members plan in different units
+    pub unit: Option<CapacityUnit>,
+    pub available: f64,
+    pub load: f64,
+    pub remaining: f64,
+    pub utilization: Option<f64>,
+
+    pub over_allocated_member_ids: Vec<Uuid>,
+    pub members: Vec<MemberWorkload>,
+}
@@ -50,4 +50,10 @@ pub enum ChangeResourceType {
     Assets,
     Labels,
     Changes,
+    Comments,
+    Notifications,
+    Cycles,
+    Milestones,
+    Templates,
+    AutomationRules,
 }
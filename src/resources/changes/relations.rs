@@ -15,7 +15,12 @@ pub trait ChangeRelations {
 #[async_trait]
 impl ChangeRelations for Change {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
-        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
 
         Ok(data)
     }
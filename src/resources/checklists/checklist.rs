@@ -0,0 +1,36 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKChecklistItem")]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub task_id: Uuid,
+    pub owner_id: Uuid,
+
+    pub title: String,
+    pub position: i32,
+
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by_id: Option<Uuid>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKChecklistProgress")]
+pub struct ChecklistProgress {
+    pub task_id: Uuid,
+
+    pub total: i64,
+    pub completed: i64,
+
+    // none for tasks without a checklist
+    pub percentage: Option<f64>,
+}
@@ -0,0 +1,3 @@
+pub mod checklist;
+pub mod operations;
+pub mod relations;
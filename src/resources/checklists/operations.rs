@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::tasks::{
+        extensions::CreateTasksInput,
+        operations::{CreateTaskInput, TaskCrudOperations},
+        task::{Task, TaskStatus},
+    },
+};
+
+use super::checklist::{ChecklistItem, ChecklistProgress};
+
+#[async_trait]
+pub trait ChecklistOperations {
+    async fn create_checklist_item(&self, input: CreateChecklistItemInput) -> Result<ChecklistItem, SDKError>;
+    async fn get_checklist_item(&self, id: Uuid) -> Result<ChecklistItem, SDKError>;
+    async fn get_checklist_items(&self, task_id: Uuid) -> Result<Vec<ChecklistItem>, SDKError>;
+    async fn update_checklist_item(&self, id: Uuid, input: UpdateChecklistItemInput)
+        -> Result<ChecklistItem, SDKError>;
+    async fn delete_checklist_item(&self, id: Uuid) -> Result<ChecklistItem, SDKError>;
+
+    async fn reorder_checklist_items(&self, task_id: Uuid, item_ids: Vec<Uuid>)
+        -> Result<Vec<ChecklistItem>, SDKError>;
+    async fn convert_checklist_item_to_subtask(&self, id: Uuid, owner_id: Uuid) -> Result<Task, SDKError>;
+    async fn get_checklist_progress(&self, task_id: Uuid) -> Result<ChecklistProgress, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateChecklistItemInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+    pub task_id: Uuid,
+    pub title: String,
+
+    // appended to the end when left out
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateChecklistItemInput {
+    #[builder(setter(strip_option), default)]
+    pub title: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub completed: Option<bool>,
+
+    // member completing the item
+    #[graphql(skip)]
+    #[builder(setter(strip_option), default)]
+    pub actor_id: Option<Uuid>,
+}
+
+pub(crate) fn checklist_item_from_row(row: &PgRow) -> ChecklistItem {
+    ChecklistItem {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        task_id: row.get("task_id"),
+        owner_id: row.get("owner_id"),
+        title: row.get("title"),
+        position: row.get("position"),
+        completed_at: row.get("completed_at"),
+        completed_by_id: row.get("completed_by_id"),
+    }
+}
+
+#[async_trait]
+impl ChecklistOperations for SDKEngine {
+    async fn create_checklist_item(&self, input: CreateChecklistItemInput) -> Result<ChecklistItem, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let position = match input.position {
+            Some(position) => {
+                let position = position.max(0);
+
+                sqlx::query(
+                    "UPDATE task_checklist_items SET position = position + 1 WHERE task_id = $1 AND position >= $2",
+                )
+                .bind(input.task_id)
+                .bind(position)
+                .execute(&mut *tx)
+                .await?;
+
+                position
+            }
+            None => {
+                sqlx::query_scalar("SELECT COALESCE(max(position) + 1, 0) FROM task_checklist_items WHERE task_id = $1")
+                    .bind(input.task_id)
+                    .fetch_one(&mut *tx)
+                    .await?
+            }
+        };
+
+        let checklist_item_info = sqlx::query(
+            r#"
+            INSERT INTO task_checklist_items (task_id, owner_id, title, position)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.task_id)
+        .bind(input.owner_id)
+        .bind(input.title)
+        .bind(position)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(checklist_item_from_row(&checklist_item_info))
+    }
+
+    async fn get_checklist_item(&self, id: Uuid) -> Result<ChecklistItem, SDKError> {
+        let checklist_item_info = sqlx::query("SELECT * FROM task_checklist_items WHERE id = $1")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(checklist_item_from_row(&checklist_item_info))
+    }
+
+    async fn get_checklist_items(&self, task_id: Uuid) -> Result<Vec<ChecklistItem>, SDKError> {
+        let checklist_items_info =
+            sqlx::query("SELECT * FROM task_checklist_items WHERE task_id = $1 ORDER BY position, created_at")
+                .bind(task_id)
+                .fetch_all(self.db_pool.as_ref())
+                .await?;
+
+        Ok(checklist_items_info.iter().map(checklist_item_from_row).collect())
+    }
+
+    // Completing an item that is already complete keeps who completed it and
+    // when.
+    async fn update_checklist_item(
+        &self,
+        id: Uuid,
+        input: UpdateChecklistItemInput,
+    ) -> Result<ChecklistItem, SDKError> {
+        let checklist_item_info = sqlx::query(
+            r#"
+            UPDATE task_checklist_items
+            SET
+                title = COALESCE($1, title),
+                completed_at = CASE
+                    WHEN $2::bool IS NULL THEN completed_at
+                    WHEN $2 THEN COALESCE(completed_at, now())
+                END,
+                completed_by_id = CASE
+                    WHEN $2::bool IS NULL THEN completed_by_id
+                    WHEN $2 AND completed_at IS NOT NULL THEN completed_by_id
+                    WHEN $2 THEN $3
+                END
+            WHERE id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(input.title)
+        .bind(input.completed)
+        .bind(input.actor_id)
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(checklist_item_from_row(&checklist_item_info))
+    }
+
+    async fn delete_checklist_item(&self, id: Uuid) -> Result<ChecklistItem, SDKError> {
+        let checklist_item_info = sqlx::query("DELETE FROM task_checklist_items WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(checklist_item_from_row(&checklist_item_info))
+    }
+
+    async fn reorder_checklist_items(
+        &self,
+        task_id: Uuid,
+        item_ids: Vec<Uuid>,
+    ) -> Result<Vec<ChecklistItem>, SDKError> {
+        let current: HashSet<Uuid> = self
+            .get_checklist_items(task_id)
+            .await?
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+
+        let ordered: HashSet<Uuid> = item_ids.iter().copied().collect();
+
+        if ordered.len() != item_ids.len() || ordered != current {
+            return Err(SDKError::InvalidChecklistOrder);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE task_checklist_items
+            SET position = ordered.position - 1
+            FROM unnest($2::uuid[]) WITH ORDINALITY AS ordered(id, position)
+            WHERE task_checklist_items.id = ordered.id AND task_checklist_items.task_id = $1
+            "#,
+        )
+        .bind(task_id)
+        .bind(&item_ids)
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        self.get_checklist_items(task_id).await
+    }
+
+    // The subtask lands in the project of the task, done when the item was
+    // completed.
+    async fn convert_checklist_item_to_subtask(&self, id: Uuid, owner_id: Uuid) -> Result<Task, SDKError> {
+        let item = self.get_checklist_item(id).await?;
+        let parent = self.get_task(item.task_id).await?;
+
+        let input = CreateTasksInput {
+            tasks: vec![CreateTaskInput {
+                title: item.title,
+                owner_id,
+                status: item.completed_at.map(|_| TaskStatus::Done),
+                project_id: parent.project_id,
+                parent_id: Some(parent.id),
+                ..Default::default()
+            }],
+        };
+
+        let state = self
+            .resolve_task_state(parent.project_id, None, None, input.tasks[0].status, None)
+            .await?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        // the item goes first, a conversion running at the same time finds it gone
+        sqlx::query("DELETE FROM task_checklist_items WHERE id = $1 RETURNING id")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        let subtask = self.create_tasks_in_tx(&mut tx, &input, &[state]).await?.remove(0);
+
+        tx.commit().await?;
+
+        self.notify_task_created(&subtask, &[]).await;
+
+        Ok(subtask)
+    }
+
+    async fn get_checklist_progress(&self, task_id: Uuid) -> Result<ChecklistProgress, SDKError> {
+        let progress = sqlx::query(
+            r#"
+            SELECT
+                count(*) AS total,
+                count(*) FILTER (WHERE completed_at IS NOT NULL) AS completed
+            FROM task_checklist_items
+            WHERE task_id = $1
+            "#,
+        )
+        .bind(task_id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        let total: i64 = progress.get("total");
+        let completed: i64 = progress.get("completed");
+
+        Ok(ChecklistProgress {
+            task_id,
+            total,
+            completed,
+            percentage: (total > 0).then(|| completed as f64 / total as f64 * 100.0),
+        })
+    }
+}
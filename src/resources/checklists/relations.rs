@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, tasks::task::Task},
+};
+
+use super::checklist::ChecklistItem;
+
+#[async_trait]
+pub trait ChecklistItemRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Task, SDKError>;
+    async fn completed_by(&self, loaders: &SDKLoaders) -> Result<Option<Member>, SDKError>;
+}
+
+#[async_trait]
+impl ChecklistItemRelations for ChecklistItem {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Task, SDKError> {
+        let data = loaders
+            .task_loader
+            .load_one(self.task_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn completed_by(&self, loaders: &SDKLoaders) -> Result<Option<Member>, SDKError> {
+        let Some(completed_by_id) = self.completed_by_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.member_loader.load_one(completed_by_id).await.unwrap();
+
+        Ok(data)
+    }
+}
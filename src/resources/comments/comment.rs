@@ -0,0 +1,48 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKComment")]
+pub struct Comment {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub author_id: Uuid,
+
+    // exactly one of them is set
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+
+    pub parent_id: Option<Uuid>,
+
+    // markdown
+    pub body: String,
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCommentRevision")]
+pub struct CommentRevision {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub comment_id: Uuid,
+    pub body: String,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCommentReaction")]
+pub struct CommentReaction {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub comment_id: Uuid,
+    pub member_id: Uuid,
+    pub emoji: String,
+}
@@ -0,0 +1,126 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::comment::{CommentReaction, CommentRevision};
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CommentReactionInput {
+    #[graphql(skip)]
+    pub member_id: Uuid,
+
+    pub comment_id: Uuid,
+    pub emoji: String,
+}
+
+#[async_trait]
+pub trait CommentExtensionOperations {
+    async fn get_comment_revisions(&self, comment_id: Uuid) -> Result<Vec<CommentRevision>, SDKError>;
+    async fn get_comment_reactions(&self, comment_id: Uuid) -> Result<Vec<CommentReaction>, SDKError>;
+    async fn add_comment_reaction(&self, input: CommentReactionInput) -> Result<CommentReaction, SDKError>;
+    async fn remove_comment_reaction(&self, input: CommentReactionInput) -> Result<CommentReaction, SDKError>;
+}
+
+#[async_trait]
+impl CommentExtensionOperations for SDKEngine {
+    async fn get_comment_revisions(&self, comment_id: Uuid) -> Result<Vec<CommentRevision>, SDKError> {
+        let revisions_info = sqlx::query!(
+            r#"
+            SELECT * FROM comment_revisions
+            WHERE comment_id = $1
+            ORDER BY created_at
+            "#,
+            comment_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(revisions_info
+            .into_iter()
+            .map(|revision_info| CommentRevision {
+                id: revision_info.id,
+                created_at: revision_info.created_at,
+                comment_id: revision_info.comment_id,
+                body: revision_info.body,
+            })
+            .collect())
+    }
+
+    async fn get_comment_reactions(&self, comment_id: Uuid) -> Result<Vec<CommentReaction>, SDKError> {
+        let reactions_info = sqlx::query!(
+            r#"
+            SELECT * FROM comment_reactions
+            WHERE comment_id = $1
+            ORDER BY created_at
+            "#,
+            comment_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(reactions_info
+            .into_iter()
+            .map(|reaction_info| CommentReaction {
+                id: reaction_info.id,
+                created_at: reaction_info.created_at,
+                comment_id: reaction_info.comment_id,
+                member_id: reaction_info.member_id,
+                emoji: reaction_info.emoji,
+            })
+            .collect())
+    }
+
+    async fn add_comment_reaction(&self, input: CommentReactionInput) -> Result<CommentReaction, SDKError> {
+        // reacting twice with the same emoji is a no-op
+        let reaction_info = sqlx::query!(
+            r#"
+            INSERT INTO comment_reactions (comment_id, member_id, emoji)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (comment_id, member_id, emoji) DO UPDATE SET emoji = EXCLUDED.emoji
+            RETURNING *
+            "#,
+            input.comment_id,
+            input.member_id,
+            input.emoji,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CommentReaction {
+            id: reaction_info.id,
+            created_at: reaction_info.created_at,
+            comment_id: reaction_info.comment_id,
+            member_id: reaction_info.member_id,
+            emoji: reaction_info.emoji,
+        })
+    }
+
+    async fn remove_comment_reaction(&self, input: CommentReactionInput) -> Result<CommentReaction, SDKError> {
+        let reaction_info = sqlx::query!(
+            r#"
+            DELETE FROM comment_reactions
+            WHERE comment_id = $1 AND member_id = $2 AND emoji = $3
+            RETURNING *
+            "#,
+            input.comment_id,
+            input.member_id,
+            input.emoji,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CommentReaction {
+            id: reaction_info.id,
+            created_at: reaction_info.created_at,
+            comment_id: reaction_info.comment_id,
+            member_id: reaction_info.member_id,
+            emoji: reaction_info.emoji,
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+
+use super::comment::Comment;
+
+pub struct CommentLoader(Arc<SDKEngine>);
+
+impl CommentLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for CommentLoader {
+    type Value = Comment;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let comments = sqlx::query!(
+            r#"
+            SELECT * FROM comments WHERE id  = ANY($1)
+            "#,
+            &keys
+        )
+        .fetch_all(&*self.0.db_pool)
+        .await
+        .unwrap();
+
+        let comments_map: HashMap<Uuid, Comment> = comments
+            .iter()
+            .map(|comment| {
+                (
+                    comment.id,
+                    Comment {
+                        id: comment.id,
+                        created_at: comment.created_at,
+                        updated_at: comment.updated_at,
+                        author_id: comment.author_id,
+                        task_id: comment.task_id,
+                        project_id: comment.project_id,
+                        parent_id: comment.parent_id,
+                        body: comment.body.clone(),
+                        edited_at: comment.edited_at,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(comments_map)
+    }
+}
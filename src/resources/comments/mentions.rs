@@ -0,0 +1,64 @@
+// Extracts `@handle` mentions from a markdown body. A handle is a member email, the local part
+// of it, or the member name without spaces; code spans and fenced blocks are ignored.
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    let mut handles: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+
+        if in_fence {
+            continue;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut in_code = false;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            if c == '`' {
+                in_code = !in_code;
+                i += 1;
+                continue;
+            }
+
+            let starts_mention = c == '@' && !in_code && (i == 0 || !is_handle_char(chars[i - 1]));
+
+            if !starts_mention {
+                i += 1;
+                continue;
+            }
+
+            let start = i + 1;
+            let mut end = start;
+
+            while end < chars.len() && (is_handle_char(chars[end]) || chars[end] == '@') {
+                end += 1;
+            }
+
+            // trailing punctuation belongs to the sentence, not the handle
+            let handle: String = chars[start..end]
+                .iter()
+                .collect::<String>()
+                .trim_end_matches(['.', '-', '_', '@'])
+                .to_lowercase();
+
+            if !handle.is_empty() && !handles.contains(&handle) {
+                handles.push(handle);
+            }
+
+            i = end;
+        }
+    }
+
+    handles
+}
+
+fn is_handle_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '_' | '-' | '+')
+}
@@ -0,0 +1,6 @@
+pub mod comment;
+pub mod extensions;
+pub mod loader;
+pub mod mentions;
+pub mod operations;
+pub mod relations;
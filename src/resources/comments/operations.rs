@@ -0,0 +1,478 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::commons::SortOrder,
+    errors::sdk::SDKError,
+    resources::{
+        members::operations::MemberCrudOperations,
+        notifications::{
+            dispatch::{task_label, NotificationDraft},
+            notification::{NotificationKind, WatchReason},
+        },
+        projects::operations::ProjectCrudOperations,
+        tasks::operations::TaskCrudOperations,
+    },
+};
+
+use super::{comment::Comment, mentions::parse_mentions};
+
+#[async_trait]
+pub trait CommentCrudOperations {
+    async fn create_comment(&self, input: CreateCommentInput) -> Result<Comment, SDKError>;
+    async fn get_comment(&self, id: Uuid) -> Result<Comment, SDKError>;
+    async fn get_comments(&self, input: GetCommentsInput) -> Result<Vec<Comment>, SDKError>;
+    async fn update_comment(&self, id: Uuid, input: UpdateCommentInput) -> Result<Comment, SDKError>;
+    async fn delete_comment(&self, id: Uuid) -> Result<Comment, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateCommentInput {
+    #[graphql(skip)]
+    pub author_id: Uuid,
+
+    pub body: String,
+
+    // replies inherit the task or project of their parent
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateCommentInput {
+    #[builder(setter(strip_option), default)]
+    pub body: Option<String>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCommentsInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetCommentsWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCommentsWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub author_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub parent_id: Option<Uuid>,
+    // only top level comments when true
+    #[builder(setter(strip_option), default)]
+    pub root: Option<bool>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetCommentsWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetCommentsWhere>>,
+}
+
+impl GetCommentsWhere {
+    pub fn compile_sql(&self) -> String {
+        let mut and_clauses = Vec::new();
+        let mut or_clauses = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            and_clauses.push(format!(
+                "id = ANY(array[{}]::uuid[])",
+                ids.iter()
+                    .map(|x| format!("'{}'", x))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ));
+        }
+
+        if let Some(author_id) = &self.author_id {
+            and_clauses.push(format!("author_id = '{}'", author_id));
+        }
+        if let Some(task_id) = &self.task_id {
+            and_clauses.push(format!("task_id = '{}'", task_id));
+        }
+        if let Some(project_id) = &self.project_id {
+            and_clauses.push(format!("project_id = '{}'", project_id));
+        }
+        if let Some(parent_id) = &self.parent_id {
+            and_clauses.push(format!("parent_id = '{}'", parent_id));
+        }
+        if let Some(root) = &self.root {
+            and_clauses.push(format!("parent_id IS {}NULL", if *root { "" } else { "NOT " }));
+        }
+
+        if let Some(ands) = &self._and {
+            for and in ands {
+                and_clauses.push(and.compile_sql());
+            }
+        }
+        if let Some(ors) = &self._or {
+            for or in ors {
+                or_clauses.push(or.compile_sql());
+            }
+        }
+
+        let mut where_clause = String::new();
+        if !and_clauses.is_empty() {
+            where_clause.push_str(&format!("({})", and_clauses.join(" AND ")));
+        }
+        if !or_clauses.is_empty() {
+            if !where_clause.is_empty() {
+                where_clause.push_str(" OR ");
+            }
+            where_clause.push_str(&format!("({})", or_clauses.join(" OR ")));
+        }
+
+        where_clause
+    }
+}
+
+// returns the members mentioned for the first time so edits don't notify twice
+async fn sync_comment_mentions(
+    tx: &mut Transaction<'_, Postgres>,
+    comment_id: Uuid,
+    body: &str,
+) -> Result<Vec<Uuid>, SDKError> {
+    let handles = parse_mentions(body);
+
+    sqlx::query!(
+        r#"
+        DELETE FROM comment_mentions
+        WHERE comment_id = $1 AND member_id <> ALL(
+            SELECT id FROM members
+            WHERE lower(email) = ANY($2)
+                OR lower(split_part(email, '@', 1)) = ANY($2)
+                OR lower(replace(name, ' ', '')) = ANY($2)
+                OR lower(replace(name, ' ', '.')) = ANY($2)
+        )
+        "#,
+        comment_id,
+        &handles,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    if handles.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mentions = sqlx::query!(
+        r#"
+        INSERT INTO comment_mentions (comment_id, member_id)
+        SELECT $1, id FROM members
+        WHERE (lower(email) = ANY($2)
+            OR lower(split_part(email, '@', 1)) = ANY($2)
+            OR lower(replace(name, ' ', '')) = ANY($2)
+            OR lower(replace(name, ' ', '.')) = ANY($2))
+            AND deleted_at IS NULL
+        ON CONFLICT DO NOTHING
+        RETURNING member_id
+        "#,
+        comment_id,
+        &handles,
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(mentions.into_iter().map(|mention| mention.member_id).collect())
+}
+
+impl SDKEngine {
+    async fn notify_comment(&self, comment: &Comment, mentioned: Vec<Uuid>, edited: bool) -> Result<(), SDKError> {
+        self.auto_watch(&mentioned, comment.task_id, comment.project_id, WatchReason::Mentioned)
+            .await?;
+
+        let target = match comment.task_id {
+            Some(task_id) => task_label(&self.get_task(task_id).await?),
+            None => match comment.project_id {
+                Some(project_id) => self.get_project(project_id).await?.name,
+                None => return Ok(()),
+            },
+        };
+
+        let author = self.get_member(comment.author_id).await?.name;
+
+        self.notify(NotificationDraft {
+            actor_id: Some(comment.author_id),
+            task_id: comment.task_id,
+            project_id: comment.project_id,
+            comment_id: Some(comment.id),
+            body: Some(comment.body.clone()),
+            recipients: mentioned.clone(),
+            ..NotificationDraft::new(
+                NotificationKind::Mentioned,
+                format!("{} mentioned you on {}", author, target),
+            )
+        })
+        .await?;
+
+        if edited {
+            return Ok(());
+        }
+
+        self.auto_watch(
+            &[comment.author_id],
+            comment.task_id,
+            comment.project_id,
+            WatchReason::Commented,
+        )
+        .await?;
+
+        self.notify(NotificationDraft {
+            actor_id: Some(comment.author_id),
+            task_id: comment.task_id,
+            project_id: comment.project_id,
+            comment_id: Some(comment.id),
+            body: Some(comment.body.clone()),
+            watched: comment.task_id.or(comment.project_id).into_iter().collect(),
+            excluded: mentioned,
+            ..NotificationDraft::new(
+                NotificationKind::Commented,
+                format!("{} commented on {}", author, target),
+            )
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommentCrudOperations for SDKEngine {
+    async fn create_comment(&self, input: CreateCommentInput) -> Result<Comment, SDKError> {
+        let (task_id, project_id) = match input.parent_id {
+            Some(parent_id) => {
+                let parent = self.get_comment(parent_id).await?;
+
+                if input.task_id.is_some_and(|id| parent.task_id != Some(id))
+                    || input.project_id.is_some_and(|id| parent.project_id != Some(id))
+                {
+                    return Err(SDKError::InvalidCommentTarget);
+                }
+
+                (parent.task_id, parent.project_id)
+            }
+            None => (input.task_id, input.project_id),
+        };
+
+        if task_id.is_some() == project_id.is_some() {
+            return Err(SDKError::InvalidCommentTarget);
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let comment_info = sqlx::query!(
+            r#"
+            INSERT INTO comments (author_id, task_id, project_id, parent_id, body)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            input.author_id,
+            task_id,
+            project_id,
+            input.parent_id,
+            input.body,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mentioned = sync_comment_mentions(&mut tx, comment_info.id, &comment_info.body).await?;
+
+        tx.commit().await?;
+
+        let comment = Comment {
+            id: comment_info.id,
+            created_at: comment_info.created_at,
+            updated_at: comment_info.updated_at,
+            author_id: comment_info.author_id,
+            task_id: comment_info.task_id,
+            project_id: comment_info.project_id,
+            parent_id: comment_info.parent_id,
+            body: comment_info.body,
+            edited_at: comment_info.edited_at,
+        };
+
+        self.notify_comment(&comment, mentioned, false).await?;
+
+        Ok(comment)
+    }
+
+    async fn get_comment(&self, id: Uuid) -> Result<Comment, SDKError> {
+        let comment_info = sqlx::query!(
+            r#"
+            SELECT * FROM comments
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Comment {
+            id: comment_info.id,
+            created_at: comment_info.created_at,
+            updated_at: comment_info.updated_at,
+            author_id: comment_info.author_id,
+            task_id: comment_info.task_id,
+            project_id: comment_info.project_id,
+            parent_id: comment_info.parent_id,
+            body: comment_info.body,
+            edited_at: comment_info.edited_at,
+        })
+    }
+
+    async fn get_comments(&self, input: GetCommentsInput) -> Result<Vec<Comment>, SDKError> {
+        let mut query = "SELECT * FROM comments ".to_string();
+
+        if let Some(filter) = input.filter {
+            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+        }
+
+        if let Some(sort_by) = input.sort_by {
+            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
+        } else {
+            query.push_str("ORDER BY created_at ");
+        }
+
+        if let Some(sort_order) = input.sort_order {
+            query.push_str(format!("{} ", sort_order).as_str());
+        }
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let comments_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        let comments = comments_info
+            .into_iter()
+            .map(|comment_info| Comment {
+                id: comment_info.get("id"),
+                created_at: comment_info.get("created_at"),
+                updated_at: comment_info.get("updated_at"),
+                author_id: comment_info.get("author_id"),
+                task_id: comment_info.get("task_id"),
+                project_id: comment_info.get("project_id"),
+                parent_id: comment_info.get("parent_id"),
+                body: comment_info.get("body"),
+                edited_at: comment_info.get("edited_at"),
+            })
+            .collect();
+
+        Ok(comments)
+    }
+
+    async fn update_comment(&self, id: Uuid, input: UpdateCommentInput) -> Result<Comment, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        if let Some(body) = &input.body {
+            // keep the previous body around as a revision
+            sqlx::query!(
+                r#"
+                INSERT INTO comment_revisions (comment_id, body)
+                SELECT id, body FROM comments WHERE id = $1 AND body <> $2
+                "#,
+                id,
+                body,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let comment_info = sqlx::query!(
+            r#"
+            UPDATE comments
+            SET
+                body = COALESCE($1, body),
+                edited_at = CASE WHEN $1 IS NOT NULL AND $1 <> body THEN now() ELSE edited_at END
+            WHERE id = $2
+            RETURNING *
+            "#,
+            input.body,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mentioned = match input.body {
+            Some(_) => sync_comment_mentions(&mut tx, comment_info.id, &comment_info.body).await?,
+            None => Vec::new(),
+        };
+
+        tx.commit().await?;
+
+        let comment = Comment {
+            id: comment_info.id,
+            created_at: comment_info.created_at,
+            updated_at: comment_info.updated_at,
+            author_id: comment_info.author_id,
+            task_id: comment_info.task_id,
+            project_id: comment_info.project_id,
+            parent_id: comment_info.parent_id,
+            body: comment_info.body,
+            edited_at: comment_info.edited_at,
+        };
+
+        if !mentioned.is_empty() {
+            self.notify_comment(&comment, mentioned, true).await?;
+        }
+
+        Ok(comment)
+    }
+
+    async fn delete_comment(&self, id: Uuid) -> Result<Comment, SDKError> {
+        let comment_info = sqlx::query!(
+            r#"
+            DELETE FROM comments WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Comment {
+            id: comment_info.id,
+            created_at: comment_info.created_at,
+            updated_at: comment_info.updated_at,
+            author_id: comment_info.author_id,
+            task_id: comment_info.task_id,
+            project_id: comment_info.project_id,
+            parent_id: comment_info.parent_id,
+            body: comment_info.body,
+            edited_at: comment_info.edited_at,
+        })
+    }
+}
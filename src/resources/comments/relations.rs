@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, projects::project::Project, tasks::task::Task},
+};
+
+use super::{
+    comment::{Comment, CommentReaction, CommentRevision},
+    extensions::CommentExtensionOperations,
+};
+
+#[async_trait]
+pub trait CommentRelations {
+    async fn author(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Option<Task>, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
+    async fn parent(&self, loaders: &SDKLoaders) -> Result<Option<Comment>, SDKError>;
+
+    async fn replies(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError>;
+    async fn mentions(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError>;
+    async fn reactions(&self, loaders: &SDKLoaders) -> Result<Vec<CommentReaction>, SDKError>;
+    async fn revisions(&self, loaders: &SDKLoaders) -> Result<Vec<CommentRevision>, SDKError>;
+}
+
+#[async_trait]
+impl CommentRelations for Comment {
+    async fn author(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders
+            .member_loader
+            .load_one(self.author_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Option<Task>, SDKError> {
+        let Some(task_id) = self.task_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.task_loader.load_one(task_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError> {
+        let Some(project_id) = self.project_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn parent(&self, loaders: &SDKLoaders) -> Result<Option<Comment>, SDKError> {
+        let Some(parent_id) = self.parent_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.comment_loader.load_one(parent_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn replies(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM comments
+            WHERE parent_id = $1
+            ORDER BY created_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+
+        let comments_map = loaders.comment_loader.load_many(ids.clone()).await.unwrap();
+
+        let comments: &Vec<Comment> = &ids
+            .into_iter()
+            .map(|id| comments_map.get(&id).unwrap().clone())
+            .collect();
+
+        Ok(comments.clone())
+    }
+
+    async fn mentions(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT member_id FROM comment_mentions
+            WHERE comment_id = $1
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.member_id)
+        .collect();
+
+        let members_map = loaders.member_loader.load_many(ids.clone()).await.unwrap();
+
+        let members: &Vec<Member> = &ids.into_iter().filter_map(|id| members_map.get(&id).cloned()).collect();
+
+        Ok(members.clone())
+    }
+
+    async fn reactions(&self, loaders: &SDKLoaders) -> Result<Vec<CommentReaction>, SDKError> {
+        loaders.engine.get_comment_reactions(self.id).await
+    }
+
+    async fn revisions(&self, loaders: &SDKLoaders) -> Result<Vec<CommentRevision>, SDKError> {
+        loaders.engine.get_comment_revisions(self.id).await
+    }
+}
@@ -0,0 +1,127 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use serde_json::Value;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::sdk::SDKError;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCustomField")]
+pub struct CustomField {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub project_id: Uuid,
+
+    pub target: CustomFieldTarget,
+    pub name: String,
+    pub kind: CustomFieldKind,
+    pub options: Vec<String>,
+    pub position: i32,
+
+    pub description: Option<String>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCustomFieldValue")]
+pub struct CustomFieldValue {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub field_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+
+    // JSON encoded, e.g. `"high"`, `42`, `["a", "b"]`
+    pub value: String,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum CustomFieldTarget {
+    #[default]
+    Task,
+    Project,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum CustomFieldKind {
+    #[default]
+    Text,
+    Number,
+    Date,
+    SingleSelect,
+    MultiSelect,
+    Member,
+    Url,
+}
+
+impl CustomField {
+    // checks a value against the definition and returns it in its stored form
+    pub fn validate_value(&self, value: &Value) -> Result<Value, SDKError> {
+        let invalid = || SDKError::InvalidCustomFieldValue(self.name.clone());
+
+        match self.kind {
+            CustomFieldKind::Text => value.as_str().map(|_| value.clone()).ok_or_else(invalid),
+            CustomFieldKind::Number => value
+                .as_f64()
+                .filter(|n| n.is_finite())
+                .map(|_| value.clone())
+                .ok_or_else(invalid),
+            // dates are kept as UTC RFC 3339 strings so they sort and compare as jsonb
+            CustomFieldKind::Date => value
+                .as_str()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|d| Value::String(d.with_timezone(&Utc).to_rfc3339()))
+                .ok_or_else(invalid),
+            CustomFieldKind::SingleSelect => value
+                .as_str()
+                .filter(|s| self.options.iter().any(|o| o == s))
+                .map(|_| value.clone())
+                .ok_or_else(invalid),
+            CustomFieldKind::MultiSelect => {
+                let selected = value.as_array().ok_or_else(invalid)?;
+                let mut normalized: Vec<String> = Vec::with_capacity(selected.len());
+
+                for item in selected {
+                    let item = item
+                        .as_str()
+                        .filter(|s| self.options.iter().any(|o| o == s))
+                        .ok_or_else(invalid)?;
+
+                    if !normalized.iter().any(|n| n == item) {
+                        normalized.push(item.to_string());
+                    }
+                }
+
+                Ok(Value::from(normalized))
+            }
+            CustomFieldKind::Member => value
+                .as_str()
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .map(|id| Value::String(id.to_string()))
+                .ok_or_else(invalid),
+            CustomFieldKind::Url => value
+                .as_str()
+                .filter(|s| {
+                    s.split_once("://")
+                        .is_some_and(|(scheme, rest)| matches!(scheme, "http" | "https") && !rest.is_empty())
+                        && !s.chars().any(char::is_whitespace)
+                })
+                .map(|_| value.clone())
+                .ok_or_else(invalid),
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod custom_field;
+pub mod operations;
@@ -0,0 +1,446 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::custom_field::{CustomField, CustomFieldKind, CustomFieldTarget, CustomFieldValue};
+
+#[async_trait]
+pub trait CustomFieldOperations {
+    async fn create_custom_field(&self, input: CreateCustomFieldInput) -> Result<CustomField, SDKError>;
+    async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError>;
+    async fn get_project_custom_fields(
+        &self,
+        project_id: Uuid,
+        target: Option<CustomFieldTarget>,
+    ) -> Result<Vec<CustomField>, SDKError>;
+    async fn update_custom_field(&self, id: Uuid, input: UpdateCustomFieldInput) -> Result<CustomField, SDKError>;
+    async fn delete_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError>;
+
+    async fn set_custom_field_value(&self, input: SetCustomFieldValueInput) -> Result<CustomFieldValue, SDKError>;
+    async fn get_custom_field_values(&self, resource_id: Uuid) -> Result<Vec<CustomFieldValue>, SDKError>;
+    async fn delete_custom_field_value(&self, field_id: Uuid, resource_id: Uuid) -> Result<CustomFieldValue, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateCustomFieldInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub project_id: Uuid,
+    pub name: String,
+    pub kind: CustomFieldKind,
+
+    #[builder(setter(strip_option), default)]
+    pub target: Option<CustomFieldTarget>,
+    #[builder(setter(strip_option), default)]
+    pub options: Option<Vec<String>>,
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateCustomFieldInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub options: Option<Vec<String>>,
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct SetCustomFieldValueInput {
+    pub field_id: Uuid,
+    // task or project id, depending on the field target
+    pub resource_id: Uuid,
+    // JSON encoded
+    pub value: String,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize, Deserialize)]
+#[builder(pattern = "owned")]
+pub struct CustomFieldFilter {
+    pub field_id: Uuid,
+
+    // JSON encoded; a bare string is taken as a JSON string
+    #[builder(setter(strip_option), default)]
+    pub equals: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub contains: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub greater_than: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub less_than: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub is_set: Option<bool>,
+}
+
+fn json_value(raw: &str) -> Value {
+    serde_json::from_str::<Value>(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+fn json_literal(raw: &str) -> String {
+    format!("'{}'::jsonb", json_value(raw).to_string().replace('\'', "''"))
+}
+
+impl CustomFieldFilter {
+    // date values are stored in one offset but filters may come in any, so
+    // date fields are compared as timestamps rather than as jsonb strings
+    fn compare_sql(&self, operator: &str, raw: &str) -> String {
+        let date = json_value(raw)
+            .as_str()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok());
+
+        match date {
+            Some(date) => format!(
+                "CASE WHEN (SELECT kind FROM custom_fields WHERE id = '{}') = '{}' THEN (value #>> '{{}}')::timestamptz {} '{}'::timestamptz ELSE value {} {} END",
+                self.field_id,
+                CustomFieldKind::Date,
+                operator,
+                date.with_timezone(&Utc).to_rfc3339(),
+                operator,
+                json_literal(raw)
+            ),
+            None => format!("value {} {}", operator, json_literal(raw)),
+        }
+    }
+
+    pub fn compile_sql(&self) -> String {
+        if self.is_set == Some(false) {
+            return format!(
+                "NOT EXISTS (SELECT 1 FROM custom_field_values WHERE task_id = tasks.id AND field_id = '{}')",
+                self.field_id
+            );
+        }
+
+        let mut conditions = vec![format!("field_id = '{}'", self.field_id)];
+
+        if let Some(equals) = &self.equals {
+            conditions.push(self.compare_sql("=", equals));
+        }
+
+        if let Some(contains) = &self.contains {
+            conditions.push(format!("value @> {}", json_literal(contains)));
+        }
+
+        if let Some(greater_than) = &self.greater_than {
+            conditions.push(self.compare_sql(">", greater_than));
+        }
+
+        if let Some(less_than) = &self.less_than {
+            conditions.push(self.compare_sql("<", less_than));
+        }
+
+        format!(
+            "EXISTS (SELECT 1 FROM custom_field_values WHERE task_id = tasks.id AND {})",
+            conditions.join(" AND ")
+        )
+    }
+}
+
+#[async_trait]
+impl CustomFieldOperations for SDKEngine {
+    async fn create_custom_field(&self, input: CreateCustomFieldInput) -> Result<CustomField, SDKError> {
+        let options = input.options.unwrap_or_default();
+
+        if matches!(input.kind, CustomFieldKind::SingleSelect | CustomFieldKind::MultiSelect) && options.is_empty() {
+            return Err(SDKError::InvalidCustomFieldValue(input.name));
+        }
+
+        let field_info = sqlx::query!(
+            r#"
+            INSERT INTO custom_fields (owner_id, project_id, target, name, kind, options, position, description)
+            VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, (SELECT COALESCE(MAX(position) + 1, 0) FROM custom_fields WHERE project_id = $2)), $8)
+            RETURNING *
+            "#,
+            input.owner_id,
+            input.project_id,
+            input.target.unwrap_or_default().to_string(),
+            input.name,
+            input.kind.to_string(),
+            &options,
+            input.position,
+            input.description,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CustomField {
+            id: field_info.id,
+            created_at: field_info.created_at,
+            updated_at: field_info.updated_at,
+            owner_id: field_info.owner_id,
+            project_id: field_info.project_id,
+            target: CustomFieldTarget::from_str(&field_info.target).unwrap_or_default(),
+            name: field_info.name,
+            kind: CustomFieldKind::from_str(&field_info.kind).unwrap_or_default(),
+            options: field_info.options,
+            position: field_info.position,
+            description: field_info.description,
+        })
+    }
+
+    async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError> {
+        let field_info = sqlx::query!(
+            r#"
+            SELECT * FROM custom_fields
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CustomField {
+            id: field_info.id,
+            created_at: field_info.created_at,
+            updated_at: field_info.updated_at,
+            owner_id: field_info.owner_id,
+            project_id: field_info.project_id,
+            target: CustomFieldTarget::from_str(&field_info.target).unwrap_or_default(),
+            name: field_info.name,
+            kind: CustomFieldKind::from_str(&field_info.kind).unwrap_or_default(),
+            options: field_info.options,
+            position: field_info.position,
+            description: field_info.description,
+        })
+    }
+
+    async fn get_project_custom_fields(
+        &self,
+        project_id: Uuid,
+        target: Option<CustomFieldTarget>,
+    ) -> Result<Vec<CustomField>, SDKError> {
+        let fields_info = sqlx::query!(
+            r#"
+            SELECT * FROM custom_fields
+            WHERE project_id = $1 AND ($2::varchar IS NULL OR target = $2)
+            ORDER BY position, created_at
+            "#,
+            project_id,
+            target.map(|target| target.to_string()),
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(fields_info
+            .into_iter()
+            .map(|field_info| CustomField {
+                id: field_info.id,
+                created_at: field_info.created_at,
+                updated_at: field_info.updated_at,
+                owner_id: field_info.owner_id,
+                project_id: field_info.project_id,
+                target: CustomFieldTarget::from_str(&field_info.target).unwrap_or_default(),
+                name: field_info.name,
+                kind: CustomFieldKind::from_str(&field_info.kind).unwrap_or_default(),
+                options: field_info.options,
+                position: field_info.position,
+                description: field_info.description,
+            })
+            .collect())
+    }
+
+    async fn update_custom_field(&self, id: Uuid, input: UpdateCustomFieldInput) -> Result<CustomField, SDKError> {
+        let field_info = sqlx::query!(
+            r#"
+            UPDATE custom_fields
+            SET
+                name = COALESCE($1, name),
+                options = COALESCE($2, options),
+                position = COALESCE($3, position),
+                description = COALESCE($4, description)
+            WHERE id = $5
+            RETURNING *
+            "#,
+            input.name,
+            input.options.as_deref(),
+            input.position,
+            input.description,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CustomField {
+            id: field_info.id,
+            created_at: field_info.created_at,
+            updated_at: field_info.updated_at,
+            owner_id: field_info.owner_id,
+            project_id: field_info.project_id,
+            target: CustomFieldTarget::from_str(&field_info.target).unwrap_or_default(),
+            name: field_info.name,
+            kind: CustomFieldKind::from_str(&field_info.kind).unwrap_or_default(),
+            options: field_info.options,
+            position: field_info.position,
+            description: field_info.description,
+        })
+    }
+
+    async fn delete_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError> {
+        let field_info = sqlx::query!(
+            r#"
+            DELETE FROM custom_fields WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CustomField {
+            id: field_info.id,
+            created_at: field_info.created_at,
+            updated_at: field_info.updated_at,
+            owner_id: field_info.owner_id,
+            project_id: field_info.project_id,
+            target: CustomFieldTarget::from_str(&field_info.target).unwrap_or_default(),
+            name: field_info.name,
+            kind: CustomFieldKind::from_str(&field_info.kind).unwrap_or_default(),
+            options: field_info.options,
+            position: field_info.position,
+            description: field_info.description,
+        })
+    }
+
+    async fn set_custom_field_value(&self, input: SetCustomFieldValueInput) -> Result<CustomFieldValue, SDKError> {
+        let field = self.get_custom_field(input.field_id).await?;
+
+        let raw: Value = serde_json::from_str(&input.value)?;
+        let value = field.validate_value(&raw)?;
+
+        if field.kind == CustomFieldKind::Member {
+            let member = sqlx::query!(
+                r#"
+                SELECT id FROM members WHERE id = $1::text::uuid AND deleted_at IS NULL
+                "#,
+                value.as_str(),
+            )
+            .fetch_optional(self.db_pool.as_ref())
+            .await?;
+
+            if member.is_none() {
+                return Err(SDKError::InvalidCustomFieldValue(field.name));
+            }
+        }
+
+        let task_id = match field.target {
+            CustomFieldTarget::Task => {
+                // the task has to live in the project that defines the field
+                let task = sqlx::query!(
+                    r#"
+                    SELECT project_id FROM tasks WHERE id = $1 AND deleted_at IS NULL
+                    "#,
+                    input.resource_id,
+                )
+                .fetch_one(self.db_pool.as_ref())
+                .await?;
+
+                if task.project_id != Some(field.project_id) {
+                    return Err(SDKError::InvalidCustomFieldValue(field.name));
+                }
+
+                Some(input.resource_id)
+            }
+            CustomFieldTarget::Project => {
+                if input.resource_id != field.project_id {
+                    return Err(SDKError::InvalidCustomFieldValue(field.name));
+                }
+
+                None
+            }
+        };
+
+        let value_info = sqlx::query!(
+            r#"
+            INSERT INTO custom_field_values (field_id, task_id, project_id, value)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (field_id, COALESCE(task_id, project_id)) DO UPDATE SET value = EXCLUDED.value
+            RETURNING *
+            "#,
+            field.id,
+            task_id,
+            task_id.is_none().then_some(input.resource_id),
+            value,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CustomFieldValue {
+            id: value_info.id,
+            created_at: value_info.created_at,
+            updated_at: value_info.updated_at,
+            field_id: value_info.field_id,
+            task_id: value_info.task_id,
+            project_id: value_info.project_id,
+            value: value_info.value.to_string(),
+        })
+    }
+
+    async fn get_custom_field_values(&self, resource_id: Uuid) -> Result<Vec<CustomFieldValue>, SDKError> {
+        let values_info = sqlx::query!(
+            r#"
+            SELECT custom_field_values.* FROM custom_field_values
+            INNER JOIN custom_fields ON custom_fields.id = custom_field_values.field_id
+            WHERE custom_field_values.task_id = $1 OR custom_field_values.project_id = $1
+            ORDER BY custom_fields.position, custom_fields.created_at
+            "#,
+            resource_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(values_info
+            .into_iter()
+            .map(|value_info| CustomFieldValue {
+                id: value_info.id,
+                created_at: value_info.created_at,
+                updated_at: value_info.updated_at,
+                field_id: value_info.field_id,
+                task_id: value_info.task_id,
+                project_id: value_info.project_id,
+                value: value_info.value.to_string(),
+            })
+            .collect())
+    }
+
+    async fn delete_custom_field_value(&self, field_id: Uuid, resource_id: Uuid) -> Result<CustomFieldValue, SDKError> {
+        let value_info = sqlx::query!(
+            r#"
+            DELETE FROM custom_field_values
+            WHERE field_id = $1 AND (task_id = $2 OR project_id = $2)
+            RETURNING *
+            "#,
+            field_id,
+            resource_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CustomFieldValue {
+            id: value_info.id,
+            created_at: value_info.created_at,
+            updated_at: value_info.updated_at,
+            field_id: value_info.field_id,
+            task_id: value_info.task_id,
+            project_id: value_info.project_id,
+            value: value_info.value.to_string(),
+        })
+    }
+}
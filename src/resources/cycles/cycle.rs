@@ -0,0 +1,80 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCycle")]
+pub struct Cycle {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+
+    pub number: i32,
+    pub name: Option<String>,
+    pub description: Option<String>,
+
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+
+    pub status: CycleStatus,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCycleMetrics")]
+pub struct CycleMetrics {
+    pub cycle_id: Uuid,
+
+    // tasks in the cycle when it started
+    pub initial_scope: i64,
+    // tasks added after the cycle started
+    pub scope_added: i64,
+    // tasks taken out of the cycle by hand
+    pub scope_removed: i64,
+
+    pub completed: i64,
+    pub carried_over: i64,
+    pub remaining: i64,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCycleRollover")]
+pub struct CycleRollover {
+    pub cycle: Cycle,
+    // where unfinished tasks went
+    pub next_cycle: Cycle,
+    pub carried_over_task_ids: Vec<Uuid>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum CycleStatus {
+    #[default]
+    Upcoming,
+    Active,
+    Completed,
+}
+
+impl CycleStatus {
+    // a cycle stays active past its end until the rollover completes it
+    pub fn at(starts_at: DateTime<Utc>, completed_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Self {
+        if completed_at.is_some() {
+            CycleStatus::Completed
+        } else if now < starts_at {
+            CycleStatus::Upcoming
+        } else {
+            CycleStatus::Active
+        }
+    }
+}
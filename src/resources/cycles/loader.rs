@@ -0,0 +1,67 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+
+use super::{
+    cycle::{Cycle, CycleMetrics},
+    operations::cycle_from_row,
+};
+
+pub struct CycleLoader(Arc<SDKEngine>);
+
+impl CycleLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for CycleLoader {
+    type Value = Cycle;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let cycles = sqlx::query(
+            r#"
+            SELECT * FROM cycles WHERE id  = ANY($1) AND deleted_at IS NULL
+            "#,
+        )
+        .bind(keys)
+        .fetch_all(&*self.0.db_pool)
+        .await
+        .unwrap();
+
+        let cycles_map: HashMap<Uuid, Cycle> = cycles
+            .iter()
+            .map(|cycle| {
+                let cycle = cycle_from_row(cycle);
+
+                (cycle.id, cycle)
+            })
+            .collect();
+
+        Ok(cycles_map)
+    }
+}
+
+pub struct CycleMetricsLoader(Arc<SDKEngine>);
+
+impl CycleMetricsLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for CycleMetricsLoader {
+    type Value = CycleMetrics;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let metrics_map = self.0.load_cycle_metrics(keys).await.unwrap();
+
+        Ok(metrics_map)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod cycle;
+pub mod loader;
+pub mod operations;
+pub mod relations;
+pub mod runner;
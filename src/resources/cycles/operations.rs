@@ -0,0 +1,478 @@
+use std::collections::HashMap;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::cycle::{Cycle, CycleMetrics, CycleRollover, CycleStatus};
+
+#[async_trait]
+pub trait CycleCrudOperations {
+    async fn create_cycle(&self, input: CreateCycleInput) -> Result<Cycle, SDKError>;
+    async fn get_cycle(&self, id: Uuid) -> Result<Cycle, SDKError>;
+    async fn get_cycles(&self, input: GetCyclesInput, now: DateTime<Utc>) -> Result<Vec<Cycle>, SDKError>;
+    async fn update_cycle(&self, id: Uuid, input: UpdateCycleInput) -> Result<Cycle, SDKError>;
+    async fn delete_cycle(&self, id: Uuid) -> Result<Cycle, SDKError>;
+
+    async fn get_cycle_metrics(&self, id: Uuid) -> Result<CycleMetrics, SDKError>;
+
+    async fn complete_cycle(&self, id: Uuid, now: DateTime<Utc>) -> Result<CycleRollover, SDKError>;
+    async fn run_cycle_rollovers(&self, now: DateTime<Utc>) -> Result<Vec<CycleRollover>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateCycleInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+
+    // exactly one of team_id and project_id
+    #[builder(setter(strip_option), default)]
+    pub team_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateCycleInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub starts_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCyclesInput {
+    #[builder(setter(strip_option), default)]
+    pub team_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub status: Option<CycleStatus>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+pub(crate) fn cycle_from_row(row: &PgRow) -> Cycle {
+    let starts_at = row.get("starts_at");
+    let completed_at = row.get("completed_at");
+
+    Cycle {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        owner_id: row.get("owner_id"),
+        team_id: row.get("team_id"),
+        project_id: row.get("project_id"),
+        number: row.get("number"),
+        name: row.get("name"),
+        description: row.get("description"),
+        starts_at,
+        ends_at: row.get("ends_at"),
+        completed_at,
+        status: CycleStatus::at(starts_at, completed_at, Utc::now()),
+    }
+}
+
+impl SDKEngine {
+    // Project cycles take tasks of that project, team cycles take tasks of any
+    // project the team works on.
+    pub(crate) async fn validate_task_cycle(
+        &self,
+        project_id: Option<Uuid>,
+        cycle_id: Option<Uuid>,
+    ) -> Result<(), SDKError> {
+        let Some(cycle_id) = cycle_id.filter(|cycle_id| !cycle_id.is_nil()) else {
+            return Ok(());
+        };
+
+        let cycle = self.get_cycle(cycle_id).await?;
+
+        let matches = match (cycle.project_id, cycle.team_id, project_id) {
+            (Some(cycle_project_id), _, Some(project_id)) => cycle_project_id == project_id,
+            (None, Some(team_id), Some(project_id)) => self.team_has_project(team_id, project_id).await?,
+            _ => false,
+        };
+
+        if !matches {
+            return Err(SDKError::TaskCycleMismatch);
+        }
+
+        Ok(())
+    }
+
+    async fn team_has_project(&self, team_id: Uuid, project_id: Uuid) -> Result<bool, SDKError> {
+        let team_project = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM teams_by_projects WHERE team_id = $1 AND project_id = $2
+            ) AS "exists!"
+            "#,
+            team_id,
+            project_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(team_project.exists)
+    }
+
+    pub(crate) async fn load_cycle_metrics(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, CycleMetrics>, SDKError> {
+        let metrics = sqlx::query!(
+            r#"
+            SELECT
+                cycles.id,
+                count(cycle_tasks.task_id) FILTER (WHERE cycle_tasks.added_at <= cycles.starts_at) AS "initial_scope!",
+                count(cycle_tasks.task_id) FILTER (WHERE cycle_tasks.added_at > cycles.starts_at) AS "scope_added!",
+                count(cycle_tasks.task_id) FILTER (
+                    WHERE cycle_tasks.removed_at IS NOT NULL AND cycle_tasks.carried_over_to_id IS NULL
+                ) AS "scope_removed!",
+                count(cycle_tasks.task_id) FILTER (
+                    WHERE cycle_tasks.removed_at IS NULL AND tasks.status = 'Done'
+                ) AS "completed!",
+                count(cycle_tasks.task_id) FILTER (WHERE cycle_tasks.carried_over_to_id IS NOT NULL) AS "carried_over!",
+                count(cycle_tasks.task_id) FILTER (
+                    WHERE cycle_tasks.removed_at IS NULL AND COALESCE(tasks.status, '') NOT IN ('Done', 'Canceled')
+                ) AS "remaining!"
+            FROM cycles
+            LEFT JOIN (
+                cycle_tasks JOIN tasks ON tasks.id = cycle_tasks.task_id AND tasks.deleted_at IS NULL
+            ) ON cycle_tasks.cycle_id = cycles.id
+            WHERE cycles.id = ANY($1)
+            GROUP BY cycles.id
+            "#,
+            ids,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(metrics
+            .into_iter()
+            .map(|metrics| {
+                (
+                    metrics.id,
+                    CycleMetrics {
+                        cycle_id: metrics.id,
+                        initial_scope: metrics.initial_scope,
+                        scope_added: metrics.scope_added,
+                        scope_removed: metrics.scope_removed,
+                        completed: metrics.completed,
+                        carried_over: metrics.carried_over,
+                        remaining: metrics.remaining,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl CycleCrudOperations for SDKEngine {
+    async fn create_cycle(&self, input: CreateCycleInput) -> Result<Cycle, SDKError> {
+        if input.team_id.is_some() == input.project_id.is_some() {
+            return Err(SDKError::InvalidCycleTarget);
+        }
+
+        if input.ends_at <= input.starts_at {
+            return Err(SDKError::InvalidCycleDates);
+        }
+
+        let cycle_info = sqlx::query(
+            r#"
+            INSERT INTO cycles (owner_id, team_id, project_id, number, name, description, starts_at, ends_at)
+            VALUES (
+                $1, $2, $3,
+                COALESCE((SELECT max(number) FROM cycles WHERE COALESCE(team_id, project_id) = COALESCE($2, $3)), 0) + 1,
+                $4, $5, $6, $7
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(input.owner_id)
+        .bind(input.team_id)
+        .bind(input.project_id)
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.starts_at)
+        .bind(input.ends_at)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(cycle_from_row(&cycle_info))
+    }
+
+    async fn get_cycle(&self, id: Uuid) -> Result<Cycle, SDKError> {
+        let cycle_info = sqlx::query("SELECT * FROM cycles WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(cycle_from_row(&cycle_info))
+    }
+
+    async fn get_cycles(&self, input: GetCyclesInput, now: DateTime<Utc>) -> Result<Vec<Cycle>, SDKError> {
+        let mut and_clauses = vec!["deleted_at IS NULL".to_string()];
+
+        if let Some(team_id) = &input.team_id {
+            and_clauses.push(format!("team_id = '{}'", team_id));
+        }
+
+        if let Some(project_id) = &input.project_id {
+            and_clauses.push(format!("project_id = '{}'", project_id));
+        }
+
+        if let Some(status) = &input.status {
+            and_clauses.push(match status {
+                CycleStatus::Upcoming => format!("completed_at IS NULL AND starts_at > '{}'", now.to_rfc3339()),
+                CycleStatus::Active => format!("completed_at IS NULL AND starts_at <= '{}'", now.to_rfc3339()),
+                CycleStatus::Completed => "completed_at IS NOT NULL".to_string(),
+            });
+        }
+
+        let mut query = "SELECT * FROM cycles ".to_string();
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY starts_at ");
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let cycles_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        // the status is read at the same instant the filter used
+        Ok(cycles_info
+            .iter()
+            .map(cycle_from_row)
+            .map(|cycle| Cycle {
+                status: CycleStatus::at(cycle.starts_at, cycle.completed_at, now),
+                ..cycle
+            })
+            .collect())
+    }
+
+    async fn update_cycle(&self, id: Uuid, input: UpdateCycleInput) -> Result<Cycle, SDKError> {
+        let current = self.get_cycle(id).await?;
+
+        if input.ends_at.unwrap_or(current.ends_at) <= input.starts_at.unwrap_or(current.starts_at) {
+            return Err(SDKError::InvalidCycleDates);
+        }
+
+        let cycle_info = sqlx::query(
+            r#"
+            UPDATE cycles
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                starts_at = COALESCE($3, starts_at),
+                ends_at = COALESCE($4, ends_at)
+            WHERE id = $5 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.starts_at)
+        .bind(input.ends_at)
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(cycle_from_row(&cycle_info))
+    }
+
+    async fn delete_cycle(&self, id: Uuid) -> Result<Cycle, SDKError> {
+        let cycle_info =
+            sqlx::query("UPDATE cycles SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL RETURNING *")
+                .bind(id)
+                .fetch_one(self.db_pool.as_ref())
+                .await?;
+
+        Ok(cycle_from_row(&cycle_info))
+    }
+
+    async fn get_cycle_metrics(&self, id: Uuid) -> Result<CycleMetrics, SDKError> {
+        self.load_cycle_metrics(&[id])
+            .await?
+            .remove(&id)
+            .ok_or(SDKError::ResourceNotFound)
+    }
+
+    // Unfinished tasks move to the next cycle of the same team or project, which is
+    // created with the same length when none was planned.
+    async fn complete_cycle(&self, id: Uuid, now: DateTime<Utc>) -> Result<CycleRollover, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        // locked so two rollovers of the same cycle can't both carry its tasks over
+        let cycle_info = sqlx::query("SELECT * FROM cycles WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let cycle = cycle_from_row(&cycle_info);
+
+        if cycle.completed_at.is_some() {
+            return Err(SDKError::CycleAlreadyCompleted);
+        }
+
+        let next_cycle_info = sqlx::query(
+            r#"
+            SELECT * FROM cycles
+            WHERE COALESCE(team_id, project_id) = COALESCE($1, $2)
+                AND id <> $3
+                AND completed_at IS NULL
+                AND deleted_at IS NULL
+                AND starts_at >= $4
+            ORDER BY starts_at
+            LIMIT 1
+            "#,
+        )
+        .bind(cycle.team_id)
+        .bind(cycle.project_id)
+        .bind(cycle.id)
+        .bind(cycle.ends_at)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let next_cycle_info = match next_cycle_info {
+            Some(next_cycle_info) => next_cycle_info,
+            None => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO cycles (owner_id, team_id, project_id, number, starts_at, ends_at)
+                    VALUES (
+                        $1, $2, $3,
+                        (SELECT max(number) FROM cycles WHERE COALESCE(team_id, project_id) = COALESCE($2, $3)) + 1,
+                        $4, $5
+                    )
+                    RETURNING *
+                    "#,
+                )
+                .bind(cycle.owner_id)
+                .bind(cycle.team_id)
+                .bind(cycle.project_id)
+                .bind(cycle.ends_at)
+                .bind(cycle.ends_at + (cycle.ends_at - cycle.starts_at))
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+
+        let next_cycle = cycle_from_row(&next_cycle_info);
+
+        let carried_over_task_ids = sqlx::query!(
+            r#"
+            UPDATE tasks
+            SET cycle_id = $1
+            WHERE cycle_id = $2
+                AND COALESCE(status, '') NOT IN ('Done', 'Canceled')
+            RETURNING id
+            "#,
+            next_cycle.id,
+            cycle.id,
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|task| task.id)
+        .collect::<Vec<Uuid>>();
+
+        sqlx::query!(
+            r#"
+            UPDATE cycle_tasks
+            SET carried_over_to_id = $1
+            WHERE cycle_id = $2 AND task_id = ANY($3)
+            "#,
+            next_cycle.id,
+            cycle.id,
+            &carried_over_task_ids,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // carried over tasks are part of the next cycle from its start, not scope added to it
+        sqlx::query!(
+            r#"
+            UPDATE cycle_tasks
+            SET added_at = LEAST(added_at, $2)
+            WHERE cycle_id = $1 AND task_id = ANY($3)
+            "#,
+            next_cycle.id,
+            next_cycle.starts_at,
+            &carried_over_task_ids,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let cycle_info = sqlx::query("UPDATE cycles SET completed_at = $1 WHERE id = $2 RETURNING *")
+            .bind(now)
+            .bind(cycle.id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(CycleRollover {
+            cycle: cycle_from_row(&cycle_info),
+            next_cycle,
+            carried_over_task_ids,
+        })
+    }
+
+    async fn run_cycle_rollovers(&self, now: DateTime<Utc>) -> Result<Vec<CycleRollover>, SDKError> {
+        let ended = sqlx::query!(
+            r#"
+            SELECT id FROM cycles
+            WHERE completed_at IS NULL AND deleted_at IS NULL AND ends_at <= $1
+            ORDER BY ends_at
+            "#,
+            now,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let mut rollovers = Vec::with_capacity(ended.len());
+
+        for cycle in ended {
+            // completed by someone else since it was listed
+            match self.complete_cycle(cycle.id, now).await {
+                Ok(rollover) => rollovers.push(rollover),
+                Err(SDKError::CycleAlreadyCompleted) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(rollovers)
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{
+        members::member::Member,
+        projects::project::Project,
+        tasks::{
+            estimates::TaskEstimateOperations,
+            task::{EstimateRollup, Task},
+        },
+        teams::team::Team,
+    },
+};
+
+use super::cycle::{Cycle, CycleMetrics};
+
+#[async_trait]
+pub trait CycleRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn team(&self, loaders: &SDKLoaders) -> Result<Option<Team>, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
+
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+    async fn metrics(&self, loaders: &SDKLoaders) -> Result<CycleMetrics, SDKError>;
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError>;
+}
+
+#[async_trait]
+impl CycleRelations for Cycle {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn team(&self, loaders: &SDKLoaders) -> Result<Option<Team>, SDKError> {
+        let Some(team_id) = self.team_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.team_loader.load_one(team_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError> {
+        let Some(project_id) = self.project_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM tasks
+            WHERE cycle_id = $1 AND deleted_at IS NULL
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|id| id.id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        let tasks: &Vec<Task> = &ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect();
+
+        Ok(tasks.clone())
+    }
+
+    async fn metrics(&self, loaders: &SDKLoaders) -> Result<CycleMetrics, SDKError> {
+        let data = loaders.cycle_metrics_loader.load_one(self.id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError> {
+        loaders.engine.get_cycle_estimate_rollup(self.id).await
+    }
+}
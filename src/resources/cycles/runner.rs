@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        clock::Clock,
+        runner::{spawn_periodic, RunnerHandle},
+    },
+    errors::sdk::SDKError,
+};
+
+use super::{cycle::CycleRollover, operations::CycleCrudOperations};
+
+#[derive(Clone)]
+pub struct CycleRolloverRunner {
+    engine: Arc<SDKEngine>,
+    clock: Arc<dyn Clock>,
+}
+
+impl CycleRolloverRunner {
+    pub fn new(engine: Arc<SDKEngine>, clock: Arc<dyn Clock>) -> Self {
+        Self { engine, clock }
+    }
+
+    pub async fn run_once(&self) -> Result<Vec<CycleRollover>, SDKError> {
+        self.engine.run_cycle_rollovers(self.clock.now()).await
+    }
+
+    pub fn spawn(self, interval: std::time::Duration) -> RunnerHandle {
+        spawn_periodic(interval, move || {
+            let runner = self.clone();
+
+            async move {
+                if let Err(err) = runner.run_once().await {
+                    println!("Error rolling over cycles: {:?}", err);
+                }
+            }
+        })
+    }
+}
+
+impl SDKEngine {
+    pub fn spawn_cycle_rollover_runner(
+        self: &Arc<Self>,
+        clock: Arc<dyn Clock>,
+        interval: std::time::Duration,
+    ) -> RunnerHandle {
+        CycleRolloverRunner::new(self.clone(), clock).spawn(interval)
+    }
+}
@@ -0,0 +1,35 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskDependency")]
+pub struct TaskDependency {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+
+    // For `Blocks`, the source task blocks the target task.
+    pub source_task_id: Uuid,
+    pub target_task_id: Uuid,
+
+    pub kind: TaskDependencyKind,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum TaskDependencyKind {
+    #[default]
+    Blocks,
+    RelatesTo,
+    Duplicates,
+}
@@ -0,0 +1,54 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+
+use super::dependency::{TaskDependency, TaskDependencyKind};
+
+pub struct TaskDependencyLoader(Arc<SDKEngine>);
+
+impl TaskDependencyLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for TaskDependencyLoader {
+    type Value = TaskDependency;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let dependencies = sqlx::query!(
+            r#"
+            SELECT * FROM task_dependencies WHERE id  = ANY($1)
+            "#,
+            &keys
+        )
+        .fetch_all(&*self.0.db_pool)
+        .await
+        .unwrap();
+
+        let dependencies_map: HashMap<Uuid, TaskDependency> = dependencies
+            .iter()
+            .map(|dependency| {
+                (
+                    dependency.id,
+                    TaskDependency {
+                        id: dependency.id,
+                        created_at: dependency.created_at,
+                        updated_at: dependency.updated_at,
+                        owner_id: dependency.owner_id,
+                        source_task_id: dependency.source_task_id,
+                        target_task_id: dependency.target_task_id,
+                        kind: TaskDependencyKind::from_str(&dependency.kind).unwrap_or_default(),
+                    },
+                )
+            })
+            .collect();
+
+        Ok(dependencies_map)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod dependency;
+pub mod loader;
+pub mod operations;
@@ -0,0 +1,414 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::commons::SortOrder,
+    errors::sdk::SDKError,
+    resources::tasks::task::{Task, TaskPriority, TaskStatus},
+};
+
+use super::dependency::{TaskDependency, TaskDependencyKind};
+
+#[async_trait]
+pub trait TaskDependencyCrudOperations {
+    async fn create_task_dependency(&self, input: CreateTaskDependencyInput) -> Result<TaskDependency, SDKError>;
+    async fn get_task_dependency(&self, id: Uuid) -> Result<TaskDependency, SDKError>;
+    async fn get_task_dependencies(&self, input: GetTaskDependenciesInput) -> Result<Vec<TaskDependency>, SDKError>;
+    async fn update_task_dependency(
+        &self,
+        id: Uuid,
+        input: UpdateTaskDependencyInput,
+    ) -> Result<TaskDependency, SDKError>;
+    async fn delete_task_dependency(&self, id: Uuid) -> Result<TaskDependency, SDKError>;
+
+    async fn get_open_blockers(&self, task_id: Uuid) -> Result<Vec<Task>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateTaskDependencyInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub source_task_id: Uuid,
+    pub target_task_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub kind: Option<TaskDependencyKind>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateTaskDependencyInput {
+    #[builder(setter(strip_option), default)]
+    pub kind: Option<TaskDependencyKind>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetTaskDependenciesInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetTaskDependenciesWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetTaskDependenciesWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub owner_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub source_task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub target_task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub kind: Option<TaskDependencyKind>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetTaskDependenciesWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetTaskDependenciesWhere>>,
+}
+
+impl GetTaskDependenciesWhere {
+    pub fn compile_sql(&self) -> String {
+        let mut and_clauses = Vec::new();
+        let mut or_clauses = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            and_clauses.push(format!(
+                "id = ANY(array[{}]::uuid[])",
+                ids.iter()
+                    .map(|x| format!("'{}'", x))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            and_clauses.push(format!("owner_id = '{}'", owner_id));
+        }
+        if let Some(source_task_id) = &self.source_task_id {
+            and_clauses.push(format!("source_task_id = '{}'", source_task_id));
+        }
+        if let Some(target_task_id) = &self.target_task_id {
+            and_clauses.push(format!("target_task_id = '{}'", target_task_id));
+        }
+        if let Some(kind) = &self.kind {
+            and_clauses.push(format!("kind = '{}'", kind));
+        }
+
+        if let Some(ands) = &self._and {
+            for and in ands {
+                and_clauses.push(and.compile_sql());
+            }
+        }
+        if let Some(ors) = &self._or {
+            for or in ors {
+                or_clauses.push(or.compile_sql());
+            }
+        }
+
+        let mut where_clause = String::new();
+        if !and_clauses.is_empty() {
+            where_clause.push_str(&format!("({})", and_clauses.join(" AND ")));
+        }
+        if !or_clauses.is_empty() {
+            if !where_clause.is_empty() {
+                where_clause.push_str(" OR ");
+            }
+            where_clause.push_str(&format!("({})", or_clauses.join(" OR ")));
+        }
+
+        where_clause
+    }
+}
+
+async fn creates_blocking_cycle(
+    tx: &mut Transaction<'_, Postgres>,
+    source_task_id: Uuid,
+    target_task_id: Uuid,
+) -> Result<bool, SDKError> {
+    // source -> target closes a cycle if source is already reachable from target
+    let reachable = sqlx::query!(
+        r#"
+        WITH RECURSIVE reachable (task_id) AS (
+            SELECT target_task_id FROM task_dependencies
+            WHERE source_task_id = $1 AND kind = 'Blocks'
+            UNION
+            SELECT d.target_task_id FROM task_dependencies d
+            INNER JOIN reachable r ON d.source_task_id = r.task_id
+            WHERE d.kind = 'Blocks'
+        )
+        SELECT EXISTS(SELECT 1 FROM reachable WHERE task_id = $2) AS "exists!"
+        "#,
+        target_task_id,
+        source_task_id,
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(reachable.exists)
+}
+
+#[async_trait]
+impl TaskDependencyCrudOperations for SDKEngine {
+    async fn create_task_dependency(&self, input: CreateTaskDependencyInput) -> Result<TaskDependency, SDKError> {
+        let kind = input.kind.unwrap_or_default();
+
+        if input.source_task_id == input.target_task_id {
+            return Err(SDKError::TaskDependencyCycle);
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        if kind == TaskDependencyKind::Blocks {
+            // serialize concurrent inserts so two edges can't close a cycle together
+            sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('task_dependencies'))")
+                .execute(&mut *tx)
+                .await?;
+
+            if creates_blocking_cycle(&mut tx, input.source_task_id, input.target_task_id).await? {
+                return Err(SDKError::TaskDependencyCycle);
+            }
+        }
+
+        let dependency_info = sqlx::query!(
+            r#"
+            INSERT INTO task_dependencies (owner_id, source_task_id, target_task_id, kind)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            input.owner_id,
+            input.source_task_id,
+            input.target_task_id,
+            kind.to_string(),
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(TaskDependency {
+            id: dependency_info.id,
+            created_at: dependency_info.created_at,
+            updated_at: dependency_info.updated_at,
+            owner_id: dependency_info.owner_id,
+            source_task_id: dependency_info.source_task_id,
+            target_task_id: dependency_info.target_task_id,
+            kind: TaskDependencyKind::from_str(&dependency_info.kind).unwrap_or_default(),
+        })
+    }
+
+    async fn get_task_dependency(&self, id: Uuid) -> Result<TaskDependency, SDKError> {
+        let dependency_info = sqlx::query!(
+            r#"
+            SELECT * FROM task_dependencies
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(TaskDependency {
+            id: dependency_info.id,
+            created_at: dependency_info.created_at,
+            updated_at: dependency_info.updated_at,
+            owner_id: dependency_info.owner_id,
+            source_task_id: dependency_info.source_task_id,
+            target_task_id: dependency_info.target_task_id,
+            kind: TaskDependencyKind::from_str(&dependency_info.kind).unwrap_or_default(),
+        })
+    }
+
+    async fn get_task_dependencies(&self, input: GetTaskDependenciesInput) -> Result<Vec<TaskDependency>, SDKError> {
+        let mut query = "SELECT * FROM task_dependencies ".to_string();
+
+        if let Some(filter) = input.filter {
+            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+        }
+
+        if let Some(sort_by) = input.sort_by {
+            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
+        }
+
+        if let Some(sort_order) = input.sort_order {
+            query.push_str(format!("{} ", sort_order).as_str());
+        }
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let dependencies_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        let dependencies = dependencies_info
+            .into_iter()
+            .map(|dependency_info| TaskDependency {
+                id: dependency_info.get("id"),
+                created_at: dependency_info.get("created_at"),
+                updated_at: dependency_info.get("updated_at"),
+                owner_id: dependency_info.get("owner_id"),
+                source_task_id: dependency_info.get("source_task_id"),
+                target_task_id: dependency_info.get("target_task_id"),
+                kind: TaskDependencyKind::from_str(dependency_info.get::<'_, String, _>("kind").as_str())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(dependencies)
+    }
+
+    async fn update_task_dependency(
+        &self,
+        id: Uuid,
+        input: UpdateTaskDependencyInput,
+    ) -> Result<TaskDependency, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        if input.kind == Some(TaskDependencyKind::Blocks) {
+            sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('task_dependencies'))")
+                .execute(&mut *tx)
+                .await?;
+
+            let current = sqlx::query!(
+                r#"
+                SELECT source_task_id, target_task_id, kind FROM task_dependencies
+                WHERE id = $1
+                "#,
+                id,
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if current.kind != TaskDependencyKind::Blocks.to_string()
+                && creates_blocking_cycle(&mut tx, current.source_task_id, current.target_task_id).await?
+            {
+                return Err(SDKError::TaskDependencyCycle);
+            }
+        }
+
+        let dependency_info = sqlx::query!(
+            r#"
+            UPDATE task_dependencies
+            SET
+                kind = COALESCE($1, kind)
+            WHERE id = $2
+            RETURNING *
+            "#,
+            input.kind.map(|kind| kind.to_string()),
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(TaskDependency {
+            id: dependency_info.id,
+            created_at: dependency_info.created_at,
+            updated_at: dependency_info.updated_at,
+            owner_id: dependency_info.owner_id,
+            source_task_id: dependency_info.source_task_id,
+            target_task_id: dependency_info.target_task_id,
+            kind: TaskDependencyKind::from_str(&dependency_info.kind).unwrap_or_default(),
+        })
+    }
+
+    async fn delete_task_dependency(&self, id: Uuid) -> Result<TaskDependency, SDKError> {
+        let dependency_info = sqlx::query!(
+            r#"
+            DELETE FROM task_dependencies WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(TaskDependency {
+            id: dependency_info.id,
+            created_at: dependency_info.created_at,
+            updated_at: dependency_info.updated_at,
+            owner_id: dependency_info.owner_id,
+            source_task_id: dependency_info.source_task_id,
+            target_task_id: dependency_info.target_task_id,
+            kind: TaskDependencyKind::from_str(&dependency_info.kind).unwrap_or_default(),
+        })
+    }
+
+    async fn get_open_blockers(&self, task_id: Uuid) -> Result<Vec<Task>, SDKError> {
+        let blockers = sqlx::query!(
+            r#"
+            SELECT tasks.* FROM tasks
+            INNER JOIN task_dependencies ON task_dependencies.source_task_id = tasks.id
+            WHERE task_dependencies.target_task_id = $1
+                AND task_dependencies.kind = 'Blocks'
+                AND COALESCE(tasks.status, '') NOT IN ('Done', 'Canceled')
+                AND tasks.deleted_at IS NULL
+            "#,
+            task_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(blockers
+            .into_iter()
+            .map(|task_info| Task {
+                id: task_info.id,
+                created_at: task_info.created_at,
+                updated_at: task_info.updated_at,
+                title: task_info.title,
+                description: task_info.description,
+                status: task_info
+                    .status
+                    .and_then(|a| TaskStatus::from_str(&a).ok())
+                    .unwrap_or_default(),
+                priority: task_info
+                    .priority
+                    .and_then(|a| TaskPriority::from_str(&a).ok())
+                    .unwrap_or_default(),
+                due_date: task_info.due_date,
+                project_id: task_info.project_id,
+                lead_id: task_info.lead_id,
+                owner_id: task_info.owner_id,
+                count: task_info.count,
+                parent_id: task_info.parent_id,
+                state_id: task_info.state_id,
+                cycle_id: task_info.cycle_id,
+                milestone_id: task_info.milestone_id,
+                estimate: task_info.estimate,
+                number: task_info.number,
+                identifier: task_info.identifier,
+                rank: task_info.rank,
+                archived_at: task_info.archived_at,
+            })
+            .collect())
+    }
+}
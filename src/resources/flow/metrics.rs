@@ -0,0 +1,97 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKFlowMetrics")]
+pub struct FlowMetrics {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub group_by: Option<FlowGroupBy>,
+
+    pub groups: Vec<FlowMetricsGroup>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKFlowMetricsGroup")]
+pub struct FlowMetricsGroup {
+    // id of the project, team, member or label, or the priority name. None
+    // holds the tasks without one, or every task when nothing is grouped
+    pub key: Option<String>,
+
+    pub completed: i64,
+    // created to done
+    pub lead_time: FlowTimeStats,
+    // first in progress to done
+    pub cycle_time: FlowTimeStats,
+    pub throughput: Vec<ThroughputPoint>,
+
+    // in progress at the end of the range
+    pub wip: i64,
+    pub aging_wip: Vec<AgingTask>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize, Default)]
+#[graphql(name = "SDKFlowTimeStats")]
+pub struct FlowTimeStats {
+    pub count: i64,
+    pub average_days: Option<f64>,
+    pub median_days: Option<f64>,
+    pub p85_days: Option<f64>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKThroughputPoint")]
+pub struct ThroughputPoint {
+    // monday of the week
+    pub week_start: DateTime<Utc>,
+    pub completed: i64,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKAgingTask")]
+pub struct AgingTask {
+    pub task_id: Uuid,
+    pub title: String,
+
+    pub in_progress_since: DateTime<Utc>,
+    pub age_days: f64,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum FlowGroupBy {
+    #[default]
+    Project,
+    Team,
+    Assignee,
+    Label,
+    Priority,
+}
+
+impl FlowTimeStats {
+    pub fn from_days(mut days: Vec<f64>) -> Self {
+        if days.is_empty() {
+            return FlowTimeStats::default();
+        }
+
+        days.sort_by(|a, b| a.total_cmp(b));
+
+        // nearest rank
+        let percentile = |p: f64| days[((p * days.len() as f64).ceil() as usize).clamp(1, days.len()) - 1];
+
+        FlowTimeStats {
+            count: days.len() as i64,
+            average_days: Some(days.iter().sum::<f64>() / days.len() as f64),
+            median_days: Some(percentile(0.5)),
+            p85_days: Some(percentile(0.85)),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod metrics;
+pub mod operations;
+pub mod transition;
@@ -0,0 +1,328 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError, resources::tasks::task::TaskStatus};
+
+use super::{
+    metrics::{AgingTask, FlowGroupBy, FlowMetrics, FlowMetricsGroup, FlowTimeStats, ThroughputPoint},
+    transition::TaskStatusTransition,
+};
+
+#[async_trait]
+pub trait FlowMetricsOperations {
+    async fn get_task_status_transitions(&self, task_id: Uuid) -> Result<Vec<TaskStatusTransition>, SDKError>;
+    async fn get_flow_metrics(&self, input: GetFlowMetricsInput) -> Result<FlowMetrics, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetFlowMetricsInput {
+    // twelve weeks before `to` when left out
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    // now when left out
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+
+    #[builder(setter(strip_option), default)]
+    pub group_by: Option<FlowGroupBy>,
+
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub team_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub assignee_id: Option<Uuid>,
+
+    // tasks in progress for longer than this are aging, 7 days by default
+    #[builder(setter(strip_option), default)]
+    pub aging_threshold_days: Option<i32>,
+}
+
+const DEFAULT_RANGE_WEEKS: i64 = 12;
+const DEFAULT_AGING_THRESHOLD_DAYS: i32 = 7;
+
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds() as f64 / 86400.0
+}
+
+fn week_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    let date = at.date_naive() - Duration::try_days(at.weekday().num_days_from_monday().into()).unwrap_or_default();
+
+    date.and_hms_opt(0, 0, 0).unwrap().and_utc()
+}
+
+#[derive(Default)]
+struct FlowGroupAccumulator {
+    lead_times: Vec<f64>,
+    cycle_times: Vec<f64>,
+    completed_at: Vec<DateTime<Utc>>,
+    in_progress: Vec<AgingTask>,
+}
+
+impl SDKEngine {
+    // Group keys of every task, tasks can land in several groups (teams,
+    // assignees, labels) or in the None group when they have none.
+    async fn load_flow_group_keys(
+        &self,
+        group_by: Option<FlowGroupBy>,
+        task_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Option<String>)>, SDKError> {
+        let query = match group_by {
+            None => return Ok(task_ids.iter().map(|task_id| (*task_id, None)).collect()),
+            Some(FlowGroupBy::Project) => "SELECT tasks.id, tasks.project_id::text AS key FROM tasks",
+            Some(FlowGroupBy::Team) => {
+                "SELECT tasks.id, teams_by_projects.team_id::text AS key FROM tasks LEFT JOIN teams_by_projects ON teams_by_projects.project_id = tasks.project_id"
+            }
+            Some(FlowGroupBy::Assignee) => {
+                "SELECT tasks.id, tasks_by_assignees.assignee_id::text AS key FROM tasks LEFT JOIN tasks_by_assignees ON tasks_by_assignees.task_id = tasks.id"
+            }
+            Some(FlowGroupBy::Label) => {
+                "SELECT tasks.id, labels_by_tasks.label_id::text AS key FROM tasks LEFT JOIN labels_by_tasks ON labels_by_tasks.task_id = tasks.id"
+            }
+            Some(FlowGroupBy::Priority) => "SELECT tasks.id, tasks.priority AS key FROM tasks",
+        };
+
+        let keys = sqlx::query(format!("{} WHERE tasks.id = ANY($1)", query).as_str())
+            .bind(task_ids)
+            .fetch_all(self.db_pool.as_ref())
+            .await?;
+
+        Ok(keys.iter().map(|row| (row.get("id"), row.get("key"))).collect())
+    }
+}
+
+#[async_trait]
+impl FlowMetricsOperations for SDKEngine {
+    async fn get_task_status_transitions(&self, task_id: Uuid) -> Result<Vec<TaskStatusTransition>, SDKError> {
+        let transitions = sqlx::query!(
+            r#"
+            SELECT * FROM task_status_transitions
+            WHERE task_id = $1
+            ORDER BY transitioned_at
+            "#,
+            task_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(transitions
+            .into_iter()
+            .map(|transition| TaskStatusTransition {
+                id: transition.id,
+                created_at: transition.created_at,
+                task_id: transition.task_id,
+                actor_id: transition.actor_id,
+                from_status: transition
+                    .from_status
+                    .and_then(|status| TaskStatus::from_str(&status).ok()),
+                to_status: TaskStatus::from_str(&transition.to_status).unwrap_or_default(),
+                transitioned_at: transition.transitioned_at,
+            })
+            .collect())
+    }
+
+    // Lead and cycle times count the last completion of every task done in the
+    // range, cycle time starts at the first move to in progress after any
+    // earlier completion so reopened tasks are measured from their reopening.
+    async fn get_flow_metrics(&self, input: GetFlowMetricsInput) -> Result<FlowMetrics, SDKError> {
+        let to = input.to.unwrap_or_else(Utc::now);
+        let from = input
+            .from
+            .unwrap_or(to - Duration::try_weeks(DEFAULT_RANGE_WEEKS).unwrap_or_default());
+
+        if from >= to {
+            return Err(SDKError::InvalidAnalyticsRange);
+        }
+
+        let mut and_clauses = vec!["tasks.deleted_at IS NULL".to_string()];
+
+        if let Some(project_id) = input.project_id {
+            and_clauses.push(format!("tasks.project_id = '{}'", project_id));
+        }
+
+        if let Some(team_id) = input.team_id {
+            and_clauses.push(format!(
+                "tasks.project_id IN (SELECT project_id FROM teams_by_projects WHERE team_id = '{}')",
+                team_id
+            ));
+        }
+
+        if let Some(assignee_id) = input.assignee_id {
+            and_clauses.push(format!(
+                "tasks.id IN (SELECT task_id FROM tasks_by_assignees WHERE assignee_id = '{}')",
+                assignee_id
+            ));
+        }
+
+        let completions = sqlx::query(
+            format!(
+                r#"
+                SELECT tasks.id, tasks.created_at, done.transitioned_at AS done_at, (
+                    SELECT min(started.transitioned_at) FROM task_status_transitions AS started
+                    WHERE started.task_id = tasks.id
+                    AND started.to_status = 'InProgress'
+                    AND started.transitioned_at <= done.transitioned_at
+                    AND started.transitioned_at > COALESCE((
+                        SELECT max(earlier.transitioned_at) FROM task_status_transitions AS earlier
+                        WHERE earlier.task_id = tasks.id
+                        AND earlier.to_status = 'Done'
+                        AND earlier.transitioned_at < done.transitioned_at
+                    ), '-infinity')
+                ) AS started_at
+                FROM tasks
+                JOIN LATERAL (
+                    SELECT transitioned_at FROM task_status_transitions
+                    WHERE task_id = tasks.id
+                    AND to_status = 'Done'
+                    AND transitioned_at >= $1
+                    AND transitioned_at <= $2
+                    ORDER BY transitioned_at DESC
+                    LIMIT 1
+                ) AS done ON true
+                WHERE {}
+                "#,
+                and_clauses.join(" AND ")
+            )
+            .as_str(),
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let in_progress = sqlx::query(
+            format!(
+                r#"
+                SELECT tasks.id, tasks.title, current.transitioned_at AS in_progress_since
+                FROM tasks
+                JOIN LATERAL (
+                    SELECT to_status, transitioned_at FROM task_status_transitions
+                    WHERE task_id = tasks.id
+                    AND transitioned_at <= $1
+                    ORDER BY transitioned_at DESC
+                    LIMIT 1
+                ) AS current ON current.to_status = 'InProgress'
+                WHERE {}
+                "#,
+                and_clauses.join(" AND ")
+            )
+            .as_str(),
+        )
+        .bind(to)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        // a reopened task can be both completed in the range and in progress now
+        let task_ids: Vec<Uuid> = completions
+            .iter()
+            .chain(in_progress.iter())
+            .map(|row| row.get("id"))
+            .collect::<BTreeSet<Uuid>>()
+            .into_iter()
+            .collect();
+
+        let mut keys_by_task = BTreeMap::<Uuid, Vec<Option<String>>>::new();
+
+        for (task_id, key) in self.load_flow_group_keys(input.group_by, &task_ids).await? {
+            keys_by_task.entry(task_id).or_default().push(key);
+        }
+
+        let mut groups = BTreeMap::<Option<String>, FlowGroupAccumulator>::new();
+
+        if input.group_by.is_none() {
+            groups.insert(None, FlowGroupAccumulator::default());
+        }
+
+        for completion in completions.iter() {
+            let created_at: DateTime<Utc> = completion.get("created_at");
+            let done_at: DateTime<Utc> = completion.get("done_at");
+            let started_at: Option<DateTime<Utc>> = completion.get("started_at");
+
+            for key in keys_by_task.get(&completion.get("id")).into_iter().flatten() {
+                let group = groups.entry(key.clone()).or_default();
+
+                group.lead_times.push(days_between(created_at, done_at));
+                group
+                    .cycle_times
+                    .extend(started_at.map(|started_at| days_between(started_at, done_at)));
+                group.completed_at.push(done_at);
+            }
+        }
+
+        for task in in_progress.iter() {
+            let in_progress_since: DateTime<Utc> = task.get("in_progress_since");
+
+            for key in keys_by_task.get(&task.get("id")).into_iter().flatten() {
+                groups.entry(key.clone()).or_default().in_progress.push(AgingTask {
+                    task_id: task.get("id"),
+                    title: task.get("title"),
+                    in_progress_since,
+                    age_days: days_between(in_progress_since, to),
+                });
+            }
+        }
+
+        let aging_threshold_days = input.aging_threshold_days.unwrap_or(DEFAULT_AGING_THRESHOLD_DAYS) as f64;
+
+        let mut weeks = Vec::new();
+        let mut week = week_start(from);
+
+        while week <= to {
+            weeks.push(week);
+            week += Duration::try_weeks(1).unwrap_or_default();
+        }
+
+        let groups = groups
+            .into_iter()
+            .map(|(key, group)| {
+                let mut aging_wip: Vec<AgingTask> = group
+                    .in_progress
+                    .iter()
+                    .filter(|task| task.age_days >= aging_threshold_days)
+                    .cloned()
+                    .collect();
+
+                aging_wip.sort_by(|a, b| b.age_days.total_cmp(&a.age_days));
+
+                FlowMetricsGroup {
+                    key,
+                    completed: group.completed_at.len() as i64,
+                    lead_time: FlowTimeStats::from_days(group.lead_times),
+                    cycle_time: FlowTimeStats::from_days(group.cycle_times),
+                    throughput: weeks
+                        .iter()
+                        .map(|week| ThroughputPoint {
+                            week_start: *week,
+                            completed: group
+                                .completed_at
+                                .iter()
+                                .filter(|done_at| week_start(**done_at) == *week)
+                                .count() as i64,
+                        })
+                        .collect(),
+                    wip: group.in_progress.len() as i64,
+                    aging_wip,
+                }
+            })
+            .collect();
+
+        Ok(FlowMetrics {
+            from,
+            to,
+            group_by: input.group_by,
+            groups,
+        })
+    }
+}
@@ -0,0 +1,25 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+use crate::resources::tasks::task::TaskStatus;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskStatusTransition")]
+pub struct TaskStatusTransition {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub task_id: Uuid,
+    pub actor_id: Option<Uuid>,
+
+    // none when the task was created
+    pub from_status: Option<TaskStatus>,
+    pub to_status: TaskStatus,
+
+    pub transitioned_at: DateTime<Utc>,
+}
@@ -24,7 +24,7 @@ impl Loader<Uuid> for LabelLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let labels = sqlx::query!(
             r#"
-            SELECT * FROM labels WHERE id  = ANY($1)
+            SELECT * FROM labels WHERE id  = ANY($1) AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -164,7 +164,7 @@ impl LabelCrudOperations for SDKEngine {
         let label_info = sqlx::query!(
             r#"
             SELECT * FROM labels
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id,
         )
@@ -183,10 +183,10 @@ impl LabelCrudOperations for SDKEngine {
     }
 
     async fn get_labels(&self, input: GetLabelsInput) -> Result<Vec<Label>, SDKError> {
-        let mut query = "SELECT * FROM labels ".to_string();
+        let mut query = "SELECT * FROM labels WHERE deleted_at IS NULL ".to_string();
 
         if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+            query.push_str(format!("AND ({}) ", filter.compile_sql()).as_str());
         }
 
         if let Some(sort_by) = input.sort_by {
@@ -231,7 +231,7 @@ impl LabelCrudOperations for SDKEngine {
                 name = COALESCE($1, name),
                 description = COALESCE($2, description),
                 color = COALESCE($3, color)
-            WHERE id = $4
+            WHERE id = $4 AND deleted_at IS NULL
             RETURNING *
             "#,
             input.name,
@@ -256,7 +256,8 @@ impl LabelCrudOperations for SDKEngine {
     async fn delete_label(&self, id: Uuid) -> Result<Label, SDKError> {
         let label_info = sqlx::query!(
             r#"
-            DELETE FROM labels WHERE id = $1
+            UPDATE labels SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
             id,
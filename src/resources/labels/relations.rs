@@ -18,7 +18,12 @@ pub trait LabelRelations {
 #[async_trait]
 impl LabelRelations for Label {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
-        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
 
         Ok(data)
     }
@@ -40,7 +45,7 @@ impl LabelRelations for Label {
 
         let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
 
-        let tasks: &Vec<Task> = &ids.into_iter().map(|id| tasks_map.get(&id).unwrap().clone()).collect();
+        let tasks: &Vec<Task> = &ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect();
 
         Ok(tasks.clone())
     }
@@ -108,7 +108,7 @@ impl MembersExtensionOperations for SDKEngine {
         let member_info = sqlx::query!(
             "
             SELECT * FROM members
-            WHERE github_id = $1
+            WHERE github_id = $1 AND deleted_at IS NULL
             ",
             github_id,
         )
@@ -136,7 +136,7 @@ impl MembersExtensionOperations for SDKEngine {
         let member_info = sqlx::query!(
             "
             SELECT * FROM members
-            WHERE email = $1
+            WHERE email = $1 AND deleted_at IS NULL
             ",
             email,
         )
@@ -21,6 +21,8 @@ impl Loader<Uuid> for MemberLoader {
     type Value = Member;
     type Error = Arc<sqlx::Error>;
 
+    // trashed members still load, the tasks and history they own keep pointing
+    // to them, listings of members leave them out on their own
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let members = sqlx::query!(
             r#"
@@ -206,7 +206,7 @@ impl MemberCrudOperations for SDKEngine {
             r#"
             SELECT *
             FROM members
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
@@ -233,10 +233,10 @@ impl MemberCrudOperations for SDKEngine {
     }
 
     async fn get_members(&self, input: GetMembersInput) -> Result<Vec<Member>, SDKError> {
-        let mut query = "SELECT * FROM members ".to_string();
+        let mut query = "SELECT * FROM members WHERE deleted_at IS NULL ".to_string();
 
         if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+            query.push_str(format!("AND ({}) ", filter.compile_sql()).as_str());
         }
 
         if let Some(sort_by) = input.sort_by {
@@ -291,7 +291,7 @@ impl MemberCrudOperations for SDKEngine {
                 google_id = COALESCE($5, google_id),
                 photo_url = COALESCE($6, photo_url),
                 password_hash = COALESCE($7, password_hash)
-            WHERE id = $8
+            WHERE id = $8 AND deleted_at IS NULL
             RETURNING *
             "#,
             input.name,
@@ -328,7 +328,8 @@ impl MemberCrudOperations for SDKEngine {
     async fn delete_member(&self, id: Uuid) -> Result<Member, SDKError> {
         let member_info = sqlx::query!(
             r#"
-            DELETE FROM members WHERE id = $1
+            UPDATE members SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
             id
@@ -37,7 +37,8 @@ impl MemberRelations for Member {
 
         let projects: &Vec<Project> = &ids
             .into_iter()
-            .map(|id| projects_map.get(&id).unwrap().clone())
+            .filter_map(|id| projects_map.get(&id).cloned())
+            .filter(|project| project.archived_at.is_none())
             .collect();
 
         Ok(projects.clone())
@@ -60,7 +61,11 @@ impl MemberRelations for Member {
 
         let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
 
-        let tasks: &Vec<Task> = &ids.into_iter().map(|id| tasks_map.get(&id).unwrap().clone()).collect();
+        let tasks: &Vec<Task> = &ids
+            .into_iter()
+            .filter_map(|id| tasks_map.get(&id).cloned())
+            .filter(|task| task.archived_at.is_none())
+            .collect();
 
         Ok(tasks.clone())
     }
@@ -81,7 +86,7 @@ impl MemberRelations for Member {
 
         let teams_map = loaders.team_loader.load_many(ids.clone()).await.unwrap();
 
-        let teams: &Vec<Team> = &ids.into_iter().map(|id| teams_map.get(&id).unwrap().clone()).collect();
+        let teams: &Vec<Team> = &ids.into_iter().filter_map(|id| teams_map.get(&id).cloned()).collect();
 
         Ok(teams.clone())
     }
@@ -0,0 +1,67 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+
+use super::{
+    milestone::{Milestone, MilestoneProgress},
+    operations::milestone_from_row,
+};
+
+pub struct MilestoneLoader(Arc<SDKEngine>);
+
+impl MilestoneLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for MilestoneLoader {
+    type Value = Milestone;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let milestones = sqlx::query(
+            r#"
+            SELECT * FROM milestones WHERE id  = ANY($1) AND deleted_at IS NULL
+            "#,
+        )
+        .bind(keys)
+        .fetch_all(&*self.0.db_pool)
+        .await
+        .unwrap();
+
+        let milestones_map: HashMap<Uuid, Milestone> = milestones
+            .iter()
+            .map(|milestone| {
+                let milestone = milestone_from_row(milestone);
+
+                (milestone.id, milestone)
+            })
+            .collect();
+
+        Ok(milestones_map)
+    }
+}
+
+pub struct MilestoneProgressLoader(Arc<SDKEngine>);
+
+impl MilestoneProgressLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for MilestoneProgressLoader {
+    type Value = MilestoneProgress;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let progress_map = self.0.load_milestone_progress(keys).await.unwrap();
+
+        Ok(progress_map)
+    }
+}
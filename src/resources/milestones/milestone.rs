@@ -0,0 +1,59 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKMilestone")]
+pub struct Milestone {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub project_id: Uuid,
+
+    pub name: String,
+    pub description: Option<String>,
+    pub target_date: Option<DateTime<Utc>>,
+    pub status: MilestoneStatus,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKMilestoneProgress")]
+pub struct MilestoneProgress {
+    pub milestone_id: Uuid,
+
+    pub total: i64,
+    pub done: i64,
+    pub in_progress: i64,
+    pub canceled: i64,
+
+    // share of done tasks, canceled ones left out
+    pub percentage: f64,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKMilestoneTimelineEntry")]
+pub struct MilestoneTimelineEntry {
+    pub milestone: Milestone,
+    pub project_name: String,
+    pub progress: MilestoneProgress,
+    pub overdue: bool,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum MilestoneStatus {
+    #[default]
+    Planned,
+    InProgress,
+    Completed,
+    Canceled,
+}
@@ -0,0 +1,4 @@
+pub mod loader;
+pub mod milestone;
+pub mod operations;
+pub mod relations;
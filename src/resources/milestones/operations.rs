@@ -0,0 +1,339 @@
+use std::{collections::HashMap, str::FromStr};
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::milestone::{Milestone, MilestoneProgress, MilestoneStatus, MilestoneTimelineEntry};
+
+#[async_trait]
+pub trait MilestoneCrudOperations {
+    async fn create_milestone(&self, input: CreateMilestoneInput) -> Result<Milestone, SDKError>;
+    async fn get_milestone(&self, id: Uuid) -> Result<Milestone, SDKError>;
+    async fn get_milestones(&self, input: GetMilestonesInput) -> Result<Vec<Milestone>, SDKError>;
+    async fn update_milestone(&self, id: Uuid, input: UpdateMilestoneInput) -> Result<Milestone, SDKError>;
+    async fn delete_milestone(&self, id: Uuid) -> Result<Milestone, SDKError>;
+
+    async fn get_milestone_progress(&self, id: Uuid) -> Result<MilestoneProgress, SDKError>;
+    async fn get_milestones_timeline(
+        &self,
+        input: GetMilestonesTimelineInput,
+    ) -> Result<Vec<MilestoneTimelineEntry>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateMilestoneInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub project_id: Uuid,
+    pub name: String,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub target_date: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub status: Option<MilestoneStatus>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateMilestoneInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub target_date: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub status: Option<MilestoneStatus>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetMilestonesInput {
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub status: Option<MilestoneStatus>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetMilestonesTimelineInput {
+    // every project when left out
+    #[builder(setter(strip_option), default)]
+    pub project_ids: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+    // completed and canceled milestones are left out unless asked for
+    #[builder(setter(strip_option), default)]
+    pub include_closed: Option<bool>,
+}
+
+pub(crate) fn milestone_from_row(row: &PgRow) -> Milestone {
+    Milestone {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        owner_id: row.get("owner_id"),
+        project_id: row.get("project_id"),
+        name: row.get("name"),
+        description: row.get("description"),
+        target_date: row.get("target_date"),
+        status: MilestoneStatus::from_str(row.get("status")).unwrap_or_default(),
+    }
+}
+
+impl SDKEngine {
+    pub(crate) async fn validate_task_milestone(
+        &self,
+        project_id: Option<Uuid>,
+        milestone_id: Option<Uuid>,
+    ) -> Result<(), SDKError> {
+        let Some(milestone_id) = milestone_id.filter(|milestone_id| !milestone_id.is_nil()) else {
+            return Ok(());
+        };
+
+        let milestone = self.get_milestone(milestone_id).await?;
+
+        if project_id != Some(milestone.project_id) {
+            return Err(SDKError::TaskMilestoneMismatch);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn load_milestone_progress(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, MilestoneProgress>, SDKError> {
+        let progress = sqlx::query!(
+            r#"
+            SELECT
+                milestones.id,
+                count(tasks.id) AS "total!",
+                count(tasks.id) FILTER (WHERE tasks.status = 'Done') AS "done!",
+                count(tasks.id) FILTER (WHERE tasks.status = 'InProgress') AS "in_progress!",
+                count(tasks.id) FILTER (WHERE tasks.status = 'Canceled') AS "canceled!"
+            FROM milestones
+            LEFT JOIN tasks ON tasks.milestone_id = milestones.id AND tasks.deleted_at IS NULL
+            WHERE milestones.id = ANY($1)
+            GROUP BY milestones.id
+            "#,
+            ids,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(progress
+            .into_iter()
+            .map(|progress| {
+                let counted = progress.total - progress.canceled;
+
+                (
+                    progress.id,
+                    MilestoneProgress {
+                        milestone_id: progress.id,
+                        total: progress.total,
+                        done: progress.done,
+                        in_progress: progress.in_progress,
+                        canceled: progress.canceled,
+                        percentage: if counted > 0 {
+                            progress.done as f64 / counted as f64 * 100.0
+                        } else {
+                            0.0
+                        },
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl MilestoneCrudOperations for SDKEngine {
+    async fn create_milestone(&self, input: CreateMilestoneInput) -> Result<Milestone, SDKError> {
+        let milestone_info = sqlx::query(
+            r#"
+            INSERT INTO milestones (owner_id, project_id, name, description, target_date, status)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(input.owner_id)
+        .bind(input.project_id)
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.target_date)
+        .bind(input.status.unwrap_or_default().to_string())
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(milestone_from_row(&milestone_info))
+    }
+
+    async fn get_milestone(&self, id: Uuid) -> Result<Milestone, SDKError> {
+        let milestone_info = sqlx::query("SELECT * FROM milestones WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(milestone_from_row(&milestone_info))
+    }
+
+    async fn get_milestones(&self, input: GetMilestonesInput) -> Result<Vec<Milestone>, SDKError> {
+        let mut and_clauses = vec!["deleted_at IS NULL".to_string()];
+
+        if let Some(project_id) = &input.project_id {
+            and_clauses.push(format!("project_id = '{}'", project_id));
+        }
+
+        if let Some(status) = &input.status {
+            and_clauses.push(format!("status = '{}'", status));
+        }
+
+        let mut query = "SELECT * FROM milestones ".to_string();
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY target_date NULLS LAST, created_at ");
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let milestones_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(milestones_info.iter().map(milestone_from_row).collect())
+    }
+
+    async fn update_milestone(&self, id: Uuid, input: UpdateMilestoneInput) -> Result<Milestone, SDKError> {
+        let milestone_info = sqlx::query(
+            r#"
+            UPDATE milestones
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                target_date = COALESCE($3, target_date),
+                status = COALESCE($4, status)
+            WHERE id = $5 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.target_date)
+        .bind(input.status.map(|status| status.to_string()))
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(milestone_from_row(&milestone_info))
+    }
+
+    async fn delete_milestone(&self, id: Uuid) -> Result<Milestone, SDKError> {
+        let milestone_info =
+            sqlx::query("UPDATE milestones SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL RETURNING *")
+                .bind(id)
+                .fetch_one(self.db_pool.as_ref())
+                .await?;
+
+        Ok(milestone_from_row(&milestone_info))
+    }
+
+    async fn get_milestone_progress(&self, id: Uuid) -> Result<MilestoneProgress, SDKError> {
+        self.load_milestone_progress(&[id])
+            .await?
+            .remove(&id)
+            .ok_or(SDKError::ResourceNotFound)
+    }
+
+    // Milestones of every project on one axis, ordered by target date. Milestones
+    // without a target date close the list.
+    async fn get_milestones_timeline(
+        &self,
+        input: GetMilestonesTimelineInput,
+    ) -> Result<Vec<MilestoneTimelineEntry>, SDKError> {
+        let mut and_clauses = vec!["milestones.deleted_at IS NULL".to_string()];
+
+        if let Some(project_ids) = &input.project_ids {
+            and_clauses.push(format!(
+                "milestones.project_id = ANY(array[{}]::uuid[])",
+                project_ids
+                    .iter()
+                    .map(|x| format!("'{}'", x))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ));
+        }
+
+        if let Some(from) = &input.from {
+            and_clauses.push(format!("milestones.target_date >= '{}'", from));
+        }
+
+        if let Some(to) = &input.to {
+            and_clauses.push(format!("milestones.target_date <= '{}'", to));
+        }
+
+        if !input.include_closed.unwrap_or(false) {
+            and_clauses.push("milestones.status NOT IN ('Completed', 'Canceled')".to_string());
+        }
+
+        let mut query =
+            "SELECT milestones.*, projects.name AS project_name FROM milestones JOIN projects ON projects.id = milestones.project_id AND projects.deleted_at IS NULL AND projects.archived_at IS NULL "
+                .to_string();
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY milestones.target_date NULLS LAST, projects.name, milestones.created_at");
+
+        let milestones_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        let ids: Vec<Uuid> = milestones_info.iter().map(|row| row.get("id")).collect();
+        let mut progress = self.load_milestone_progress(&ids).await?;
+
+        let now = Utc::now();
+
+        // milestones deleted between the two queries have no progress and are left out
+        Ok(milestones_info
+            .iter()
+            .filter_map(|row| {
+                let milestone = milestone_from_row(row);
+                let overdue = milestone.target_date.is_some_and(|target_date| target_date < now)
+                    && !matches!(milestone.status, MilestoneStatus::Completed | MilestoneStatus::Canceled);
+
+                Some(MilestoneTimelineEntry {
+                    project_name: row.get("project_name"),
+                    progress: progress.remove(&milestone.id)?,
+                    overdue,
+                    milestone,
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{
+        members::member::Member,
+        projects::project::Project,
+        tasks::{
+            estimates::TaskEstimateOperations,
+            task::{EstimateRollup, Task},
+        },
+    },
+};
+
+use super::milestone::{Milestone, MilestoneProgress};
+
+#[async_trait]
+pub trait MilestoneRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Project, SDKError>;
+
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+    async fn progress(&self, loaders: &SDKLoaders) -> Result<MilestoneProgress, SDKError>;
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError>;
+}
+
+#[async_trait]
+impl MilestoneRelations for Milestone {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Project, SDKError> {
+        let data = loaders
+            .project_loader
+            .load_one(self.project_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM tasks
+            WHERE milestone_id = $1 AND deleted_at IS NULL
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|id| id.id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        let tasks: &Vec<Task> = &ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect();
+
+        Ok(tasks.clone())
+    }
+
+    async fn progress(&self, loaders: &SDKLoaders) -> Result<MilestoneProgress, SDKError> {
+        let data = loaders
+            .milestone_progress_loader
+            .load_one(self.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        Ok(data)
+    }
+
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError> {
+        loaders.engine.get_milestone_estimate_rollup(self.id).await
+    }
+}
@@ -1,7 +1,23 @@
 pub mod assets;
+pub mod automations;
+pub mod capacity;
 pub mod changes;
+pub mod checklists;
+pub mod comments;
+pub mod custom_fields;
+pub mod cycles;
+pub mod dependencies;
+pub mod flow;
 pub mod labels;
 pub mod members;
+pub mod milestones;
+pub mod notifications;
 pub mod projects;
+pub mod recurrences;
+pub mod reminders;
 pub mod tasks;
 pub mod teams;
+pub mod templates;
+pub mod time_entries;
+pub mod trash;
+pub mod workflows;
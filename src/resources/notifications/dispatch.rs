@@ -0,0 +1,158 @@
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError, resources::tasks::task::Task};
+
+use super::notification::{NotificationKind, WatchReason};
+
+pub(crate) struct NotificationDraft {
+    pub kind: NotificationKind,
+    pub actor_id: Option<Uuid>,
+
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+
+    pub title: String,
+    pub body: Option<String>,
+
+    // members notified directly, on top of the active watchers of `watched`
+    pub recipients: Vec<Uuid>,
+    pub watched: Vec<Uuid>,
+    pub excluded: Vec<Uuid>,
+}
+
+impl NotificationDraft {
+    pub fn new(kind: NotificationKind, title: String) -> Self {
+        Self {
+            kind,
+            actor_id: None,
+            task_id: None,
+            project_id: None,
+            comment_id: None,
+            title,
+            body: None,
+            recipients: Vec::new(),
+            watched: Vec::new(),
+            excluded: Vec::new(),
+        }
+    }
+
+    fn group_key(&self) -> String {
+        match (self.task_id, self.project_id) {
+            (Some(task_id), _) => format!("task:{}", task_id),
+            (None, Some(project_id)) => format!("project:{}", project_id),
+            (None, None) => format!("{}", self.kind).to_lowercase(),
+        }
+    }
+}
+
+pub(crate) fn task_label(task: &Task) -> String {
+    match &task.identifier {
+        Some(identifier) => format!("{} {}", identifier, task.title),
+        None => task.title.clone(),
+    }
+}
+
+impl SDKEngine {
+    // automatic subscriptions never revive one the member explicitly turned off
+    pub(crate) async fn auto_watch(
+        &self,
+        member_ids: &[Uuid],
+        task_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+        reason: WatchReason,
+    ) -> Result<(), SDKError> {
+        if member_ids.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO watch_subscriptions (member_id, task_id, project_id, reason)
+            SELECT member_id, $2, $3, $4 FROM unnest($1::uuid[]) AS member_id
+            ON CONFLICT (member_id, COALESCE(task_id, project_id)) DO NOTHING
+            "#,
+            member_ids,
+            task_id,
+            project_id,
+            reason.to_string(),
+        )
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    // the actor never gets notified about their own changes
+    pub(crate) async fn notify(&self, draft: NotificationDraft) -> Result<Vec<Uuid>, SDKError> {
+        let recipients = sqlx::query!(
+            r#"
+            INSERT INTO notifications (recipient_id, actor_id, kind, task_id, project_id, comment_id, group_key, title, body)
+            SELECT recipient_id, $3, $4, $5, $6, $7, $8, $9, $10
+            FROM (
+                SELECT unnest($1::uuid[]) AS recipient_id
+                UNION
+                SELECT member_id FROM watch_subscriptions
+                WHERE active AND COALESCE(task_id, project_id) = ANY($2)
+            ) recipients
+            WHERE recipient_id IS DISTINCT FROM $3 AND recipient_id <> ALL($11)
+            RETURNING recipient_id
+            "#,
+            &draft.recipients,
+            &draft.watched,
+            draft.actor_id,
+            draft.kind.to_string(),
+            draft.task_id,
+            draft.project_id,
+            draft.comment_id,
+            draft.group_key(),
+            draft.title,
+            draft.body,
+            &draft.excluded,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(recipients.into_iter().map(|r| r.recipient_id).collect())
+    }
+
+    // runs once the task is committed, a failure here is logged and doesn't undo
+    // or fail the creation
+    pub(crate) async fn notify_task_created(&self, task: &Task, assignees: &[Uuid]) {
+        if let Err(err) = self.try_notify_task_created(task, assignees).await {
+            println!("Error notifying about task {}: {:?}", task.id, err);
+        }
+    }
+
+    async fn try_notify_task_created(&self, task: &Task, assignees: &[Uuid]) -> Result<(), SDKError> {
+        self.auto_watch(&[task.owner_id], Some(task.id), None, WatchReason::Created)
+            .await?;
+        self.auto_watch(assignees, Some(task.id), None, WatchReason::Assigned)
+            .await?;
+
+        let label = task_label(task);
+
+        self.notify(NotificationDraft {
+            actor_id: Some(task.owner_id),
+            task_id: Some(task.id),
+            project_id: task.project_id,
+            recipients: assignees.to_vec(),
+            ..NotificationDraft::new(NotificationKind::Assigned, format!("You were assigned to {}", label))
+        })
+        .await?;
+
+        if let Some(project_id) = task.project_id {
+            self.notify(NotificationDraft {
+                actor_id: Some(task.owner_id),
+                task_id: Some(task.id),
+                project_id: Some(project_id),
+                watched: vec![project_id],
+                excluded: assignees.to_vec(),
+                ..NotificationDraft::new(NotificationKind::Created, format!("{} was created", label))
+            })
+            .await?;
+        }
+
+        Ok(())
+    }
+}
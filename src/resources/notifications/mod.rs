@@ -0,0 +1,3 @@
+pub mod dispatch;
+pub mod notification;
+pub mod operations;
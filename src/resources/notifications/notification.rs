@@ -0,0 +1,91 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKNotification")]
+pub struct Notification {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub recipient_id: Uuid,
+    pub actor_id: Option<Uuid>,
+
+    pub kind: NotificationKind,
+
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+
+    // notifications about the same task or project share a group
+    pub group_key: String,
+
+    pub title: String,
+    pub body: Option<String>,
+
+    pub read_at: Option<DateTime<Utc>>,
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKNotificationGroup")]
+pub struct NotificationGroup {
+    pub group_key: String,
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+
+    pub count: i64,
+    pub unread_count: i64,
+
+    pub latest: Notification,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKWatchSubscription")]
+pub struct WatchSubscription {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub member_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+
+    pub reason: WatchReason,
+    pub active: bool,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum NotificationKind {
+    #[default]
+    Updated,
+    Created,
+    Assigned,
+    StatusChanged,
+    Commented,
+    Mentioned,
+    DueSoon,
+    Overdue,
+    Escalated,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum WatchReason {
+    #[default]
+    Manual,
+    Created,
+    Assigned,
+    Commented,
+    Mentioned,
+}
@@ -0,0 +1,343 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::notification::{Notification, NotificationGroup, NotificationKind, WatchReason, WatchSubscription};
+
+#[async_trait]
+pub trait NotificationOperations {
+    async fn watch(&self, input: WatchInput) -> Result<WatchSubscription, SDKError>;
+    async fn unwatch(&self, input: WatchInput) -> Result<WatchSubscription, SDKError>;
+    async fn get_watchers(&self, resource_id: Uuid) -> Result<Vec<WatchSubscription>, SDKError>;
+    async fn get_member_subscriptions(&self, member_id: Uuid) -> Result<Vec<WatchSubscription>, SDKError>;
+
+    async fn get_notifications(&self, input: GetNotificationsInput) -> Result<Vec<Notification>, SDKError>;
+    async fn get_notification_groups(&self, input: GetNotificationsInput) -> Result<Vec<NotificationGroup>, SDKError>;
+    async fn get_unread_notifications_count(&self, recipient_id: Uuid) -> Result<i64, SDKError>;
+
+    async fn mark_notifications_read(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError>;
+    async fn mark_notifications_unread(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError>;
+    async fn archive_notifications(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError>;
+    async fn unarchive_notifications(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct WatchInput {
+    #[graphql(skip)]
+    pub member_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetNotificationsInput {
+    #[graphql(skip)]
+    pub recipient_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub unread: Option<bool>,
+    // archived notifications are left out unless asked for
+    #[builder(setter(strip_option), default)]
+    pub archived: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub kind: Option<NotificationKind>,
+    #[builder(setter(strip_option), default)]
+    pub group_key: Option<String>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+impl GetNotificationsInput {
+    fn compile_sql(&self) -> String {
+        let mut and_clauses = vec![format!("recipient_id = '{}'", self.recipient_id)];
+
+        if let Some(unread) = &self.unread {
+            and_clauses.push(format!("read_at IS {}NULL", if *unread { "" } else { "NOT " }));
+        }
+
+        and_clauses.push(format!(
+            "archived_at IS {}NULL",
+            if self.archived.unwrap_or(false) { "NOT " } else { "" }
+        ));
+
+        if let Some(kind) = &self.kind {
+            and_clauses.push(format!("kind = '{}'", kind));
+        }
+        if let Some(group_key) = &self.group_key {
+            and_clauses.push(format!("group_key = '{}'", group_key.replace('\'', "''")));
+        }
+
+        and_clauses.join(" AND ")
+    }
+}
+
+// without ids or group key every notification of the recipient is marked
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct MarkNotificationsInput {
+    #[graphql(skip)]
+    pub recipient_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub group_key: Option<String>,
+}
+
+fn notification_from_row(notification_info: &PgRow) -> Notification {
+    Notification {
+        id: notification_info.get("id"),
+        created_at: notification_info.get("created_at"),
+        updated_at: notification_info.get("updated_at"),
+        recipient_id: notification_info.get("recipient_id"),
+        actor_id: notification_info.get("actor_id"),
+        kind: NotificationKind::from_str(notification_info.get("kind")).unwrap_or_default(),
+        task_id: notification_info.get("task_id"),
+        project_id: notification_info.get("project_id"),
+        comment_id: notification_info.get("comment_id"),
+        group_key: notification_info.get("group_key"),
+        title: notification_info.get("title"),
+        body: notification_info.get("body"),
+        read_at: notification_info.get("read_at"),
+        archived_at: notification_info.get("archived_at"),
+    }
+}
+
+fn subscription_from_row(subscription_info: &PgRow) -> WatchSubscription {
+    WatchSubscription {
+        id: subscription_info.get("id"),
+        created_at: subscription_info.get("created_at"),
+        updated_at: subscription_info.get("updated_at"),
+        member_id: subscription_info.get("member_id"),
+        task_id: subscription_info.get("task_id"),
+        project_id: subscription_info.get("project_id"),
+        reason: WatchReason::from_str(subscription_info.get("reason")).unwrap_or_default(),
+        active: subscription_info.get("active"),
+    }
+}
+
+impl SDKEngine {
+    async fn mark_notifications(
+        &self,
+        set: &str,
+        input: MarkNotificationsInput,
+    ) -> Result<Vec<Notification>, SDKError> {
+        let notifications_info = sqlx::query(
+            format!(
+                r#"
+                UPDATE notifications
+                SET {}
+                WHERE recipient_id = $1
+                    AND ($2::uuid[] IS NULL OR id = ANY($2))
+                    AND ($3::varchar IS NULL OR group_key = $3)
+                RETURNING *
+                "#,
+                set
+            )
+            .as_str(),
+        )
+        .bind(input.recipient_id)
+        .bind(input.ids)
+        .bind(input.group_key)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(notifications_info.iter().map(notification_from_row).collect())
+    }
+}
+
+#[async_trait]
+impl NotificationOperations for SDKEngine {
+    async fn watch(&self, input: WatchInput) -> Result<WatchSubscription, SDKError> {
+        if input.task_id.is_some() == input.project_id.is_some() {
+            return Err(SDKError::InvalidWatchTarget);
+        }
+
+        let subscription_info = sqlx::query(
+            r#"
+            INSERT INTO watch_subscriptions (member_id, task_id, project_id, reason)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (member_id, COALESCE(task_id, project_id))
+            DO UPDATE SET active = true, reason = EXCLUDED.reason
+            RETURNING *
+            "#,
+        )
+        .bind(input.member_id)
+        .bind(input.task_id)
+        .bind(input.project_id)
+        .bind(WatchReason::Manual.to_string())
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(subscription_from_row(&subscription_info))
+    }
+
+    async fn unwatch(&self, input: WatchInput) -> Result<WatchSubscription, SDKError> {
+        if input.task_id.is_some() == input.project_id.is_some() {
+            return Err(SDKError::InvalidWatchTarget);
+        }
+
+        // the row stays behind so auto watching doesn't subscribe the member again
+        let subscription_info = sqlx::query(
+            r#"
+            INSERT INTO watch_subscriptions (member_id, task_id, project_id, reason, active)
+            VALUES ($1, $2, $3, $4, false)
+            ON CONFLICT (member_id, COALESCE(task_id, project_id))
+            DO UPDATE SET active = false
+            RETURNING *
+            "#,
+        )
+        .bind(input.member_id)
+        .bind(input.task_id)
+        .bind(input.project_id)
+        .bind(WatchReason::Manual.to_string())
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(subscription_from_row(&subscription_info))
+    }
+
+    async fn get_watchers(&self, resource_id: Uuid) -> Result<Vec<WatchSubscription>, SDKError> {
+        let subscriptions_info = sqlx::query(
+            r#"
+            SELECT * FROM watch_subscriptions
+            WHERE active AND COALESCE(task_id, project_id) = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(resource_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(subscriptions_info.iter().map(subscription_from_row).collect())
+    }
+
+    async fn get_member_subscriptions(&self, member_id: Uuid) -> Result<Vec<WatchSubscription>, SDKError> {
+        let subscriptions_info = sqlx::query(
+            r#"
+            SELECT * FROM watch_subscriptions
+            WHERE active AND member_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(member_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(subscriptions_info.iter().map(subscription_from_row).collect())
+    }
+
+    async fn get_notifications(&self, input: GetNotificationsInput) -> Result<Vec<Notification>, SDKError> {
+        let mut query = format!(
+            "SELECT * FROM notifications WHERE {} ORDER BY created_at DESC ",
+            input.compile_sql()
+        );
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let notifications_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(notifications_info.iter().map(notification_from_row).collect())
+    }
+
+    async fn get_notification_groups(&self, input: GetNotificationsInput) -> Result<Vec<NotificationGroup>, SDKError> {
+        // groups come back newest first, like the flat inbox
+        let mut query = format!(
+            r#"
+            SELECT * FROM (
+                SELECT DISTINCT ON (group_key) *,
+                    count(*) OVER (PARTITION BY group_key) AS group_count,
+                    count(*) FILTER (WHERE read_at IS NULL) OVER (PARTITION BY group_key) AS group_unread_count
+                FROM notifications
+                WHERE {}
+                ORDER BY group_key, created_at DESC
+            ) latest_by_group
+            ORDER BY created_at DESC
+            "#,
+            input.compile_sql()
+        );
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let groups_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(groups_info
+            .iter()
+            .map(|group_info| {
+                let latest = notification_from_row(group_info);
+
+                NotificationGroup {
+                    group_key: latest.group_key.clone(),
+                    task_id: latest.task_id,
+                    project_id: latest.project_id,
+                    count: group_info.get("group_count"),
+                    unread_count: group_info.get("group_unread_count"),
+                    latest,
+                }
+            })
+            .collect())
+    }
+
+    async fn get_unread_notifications_count(&self, recipient_id: Uuid) -> Result<i64, SDKError> {
+        let count = sqlx::query!(
+            r#"
+            SELECT count(*) AS "count!" FROM notifications
+            WHERE recipient_id = $1 AND read_at IS NULL AND archived_at IS NULL
+            "#,
+            recipient_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(count.count)
+    }
+
+    async fn mark_notifications_read(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError> {
+        self.mark_notifications("read_at = COALESCE(read_at, now())", input)
+            .await
+    }
+
+    async fn mark_notifications_unread(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError> {
+        self.mark_notifications("read_at = NULL", input).await
+    }
+
+    async fn archive_notifications(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError> {
+        self.mark_notifications(
+            "archived_at = COALESCE(archived_at, now()), read_at = COALESCE(read_at, now())",
+            input,
+        )
+        .await
+    }
+
+    async fn unarchive_notifications(&self, input: MarkNotificationsInput) -> Result<Vec<Notification>, SDKError> {
+        self.mark_notifications("archived_at = NULL", input).await
+    }
+}
@@ -0,0 +1,420 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::{Enum as OpenApiEnum, Object};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::tasks::{
+        estimates::TaskEstimateOperations,
+        task::{EstimateRollup, TaskStatus},
+    },
+};
+
+use super::operations::ProjectCrudOperations;
+
+#[async_trait]
+pub trait ProjectAnalyticsOperations {
+    async fn get_project_progress(&self, project_id: Uuid) -> Result<ProjectProgress, SDKError>;
+    async fn get_project_burndown(&self, input: GetProjectBurndownInput) -> Result<ProjectBurndown, SDKError>;
+    async fn get_project_health(&self, input: GetProjectHealthInput) -> Result<ProjectHealth, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetProjectBurndownInput {
+    pub project_id: Uuid,
+
+    // project start date, or its first task when it has none
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    // now when left out
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetProjectHealthInput {
+    pub project_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub at: Option<DateTime<Utc>>,
+    // throughput is measured over this many days before `at`
+    #[builder(setter(strip_option), default)]
+    pub velocity_window_days: Option<i32>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKProjectProgress")]
+pub struct ProjectProgress {
+    pub project_id: Uuid,
+
+    pub total: i64,
+    pub done: i64,
+    pub in_progress: i64,
+    pub canceled: i64,
+
+    // canceled tasks are left out of the count
+    pub percentage_by_count: f64,
+    // share of the rolled up estimate that is done, none without estimates
+    pub percentage_by_estimate: Option<f64>,
+    // what the percentage by estimate is taken from, with the tasks it leaves out
+    pub estimates: EstimateRollup,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKBurndownPoint")]
+pub struct BurndownPoint {
+    // start of the day, counts are taken at its end
+    pub date: DateTime<Utc>,
+
+    pub scope: i64,
+    pub completed: i64,
+    pub remaining: i64,
+
+    pub scope_added: i64,
+    pub scope_removed: i64,
+
+    // even pace from the start of the series to the project due date, over the
+    // scope of the day so that scope changes move the line too
+    pub ideal_remaining: Option<f64>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKProjectBurndown")]
+pub struct ProjectBurndown {
+    pub project_id: Uuid,
+
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub due_date: Option<DateTime<Utc>>,
+
+    pub initial_scope: i64,
+    pub points: Vec<BurndownPoint>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKProjectHealth")]
+pub struct ProjectHealth {
+    pub project_id: Uuid,
+    pub status: ProjectHealthStatus,
+
+    pub due_date: Option<DateTime<Utc>>,
+    pub projected_completion: Option<DateTime<Utc>>,
+
+    pub remaining: i64,
+    // completed tasks per day over the velocity window
+    pub velocity: f64,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum ProjectHealthStatus {
+    #[default]
+    OnTrack,
+    AtRisk,
+    OffTrack,
+}
+
+const DEFAULT_VELOCITY_WINDOW_DAYS: i32 = 14;
+
+#[derive(Deserialize)]
+struct TaskSnapshot {
+    project_id: Option<Uuid>,
+    status: Option<String>,
+    #[serde(default)]
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+struct TaskChange {
+    task_id: Uuid,
+    at: DateTime<Utc>,
+    // none once the task is deleted for good
+    task: Option<TaskSnapshot>,
+}
+
+#[derive(Default)]
+struct ProjectState {
+    scope: HashSet<Uuid>,
+    completed: i64,
+}
+
+impl ProjectState {
+    fn remaining(&self) -> i64 {
+        self.scope.len() as i64 - self.completed
+    }
+}
+
+// Replays the task history of a project and takes its state at every boundary,
+// boundaries must be sorted.
+fn replay_project_history(project_id: Uuid, changes: &[TaskChange], boundaries: &[DateTime<Utc>]) -> Vec<ProjectState> {
+    let mut tasks = HashMap::<Uuid, TaskStatus>::new();
+    let mut changes = changes.iter().peekable();
+
+    boundaries
+        .iter()
+        .map(|boundary| {
+            while let Some(change) = changes.next_if(|change| change.at <= *boundary) {
+                match change
+                    .task
+                    .as_ref()
+                    .filter(|task| task.project_id == Some(project_id) && task.deleted_at.is_none())
+                {
+                    Some(task) => {
+                        let status = task
+                            .status
+                            .as_deref()
+                            .and_then(|status| TaskStatus::from_str(status).ok())
+                            .unwrap_or_default();
+
+                        tasks.insert(change.task_id, status);
+                    }
+                    None => {
+                        tasks.remove(&change.task_id);
+                    }
+                }
+            }
+
+            let mut state = ProjectState::default();
+
+            for (task_id, status) in tasks.iter() {
+                match status {
+                    TaskStatus::Canceled => continue,
+                    TaskStatus::Done => state.completed += 1,
+                    _ => {}
+                }
+
+                state.scope.insert(*task_id);
+            }
+
+            state
+        })
+        .collect()
+}
+
+impl SDKEngine {
+    // Changes of every task that was in the project at some point, moved and
+    // deleted tasks included.
+    async fn load_project_task_changes(
+        &self,
+        project_id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<TaskChange>, SDKError> {
+        let changes = sqlx::query!(
+            r#"
+            SELECT resource_id, created_at, diff_json FROM changes
+            WHERE resource_type = 'Tasks'
+            AND created_at <= $2
+            AND resource_id IN (
+                SELECT resource_id FROM changes
+                WHERE resource_type = 'Tasks'
+                AND diff_json LIKE $1
+            )
+            ORDER BY created_at
+            "#,
+            format!("%\"project_id\": \"{}\"%", project_id),
+            until,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(changes
+            .into_iter()
+            .filter_map(|change| {
+                let mut diff = serde_json::from_str::<Value>(&change.diff_json).ok()?;
+
+                Some(TaskChange {
+                    task_id: change.resource_id,
+                    at: change.created_at,
+                    task: serde_json::from_value(diff.get_mut("new")?.take()).ok()?,
+                })
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ProjectAnalyticsOperations for SDKEngine {
+    async fn get_project_progress(&self, project_id: Uuid) -> Result<ProjectProgress, SDKError> {
+        let progress = sqlx::query!(
+            r#"
+            SELECT
+                count(*) AS "total!",
+                count(*) FILTER (WHERE status = 'Done') AS "done!",
+                count(*) FILTER (WHERE status = 'InProgress') AS "in_progress!",
+                count(*) FILTER (WHERE status = 'Canceled') AS "canceled!"
+            FROM tasks
+            WHERE project_id = $1 AND deleted_at IS NULL
+            "#,
+            project_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        let counted = progress.total - progress.canceled;
+        let estimates = self.get_project_estimate_rollup(project_id).await?;
+
+        Ok(ProjectProgress {
+            project_id,
+            total: progress.total,
+            done: progress.done,
+            in_progress: progress.in_progress,
+            canceled: progress.canceled,
+            percentage_by_count: if counted > 0 {
+                progress.done as f64 / counted as f64 * 100.0
+            } else {
+                0.0
+            },
+            percentage_by_estimate: (estimates.total > 0.0).then(|| estimates.completed / estimates.total * 100.0),
+            estimates,
+        })
+    }
+
+    // One point per day between from and to, rebuilt from the task changes so
+    // past days keep the scope they had back then.
+    async fn get_project_burndown(&self, input: GetProjectBurndownInput) -> Result<ProjectBurndown, SDKError> {
+        let project = self.get_project(input.project_id).await?;
+
+        let to = input.to.unwrap_or_else(Utc::now);
+        let changes = self.load_project_task_changes(project.id, to).await?;
+
+        let from = input
+            .from
+            .or(project.start_date)
+            .or(changes.first().map(|change| change.at))
+            .unwrap_or(project.created_at);
+
+        if from >= to {
+            return Err(SDKError::InvalidAnalyticsRange);
+        }
+
+        let mut days = Vec::new();
+        let mut day = from.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        while day < to {
+            days.push(day);
+            day += Duration::try_days(1).unwrap_or_default();
+        }
+
+        let boundaries: Vec<DateTime<Utc>> = std::iter::once(from)
+            .chain(
+                days.iter()
+                    .map(|day| (*day + Duration::try_days(1).unwrap_or_default()).min(to)),
+            )
+            .collect();
+
+        let states = replay_project_history(project.id, &changes, &boundaries);
+        let initial_scope = states[0].scope.len() as i64;
+
+        let ideal_remaining = |at: DateTime<Utc>, scope: i64| {
+            let due_date = project.due_date.filter(|due_date| *due_date > from)?;
+            let left = (due_date - at).num_seconds() as f64 / (due_date - from).num_seconds() as f64;
+
+            Some(scope as f64 * left.clamp(0.0, 1.0))
+        };
+
+        let points = days
+            .iter()
+            .enumerate()
+            .map(|(i, day)| {
+                let (previous, state) = (&states[i], &states[i + 1]);
+
+                BurndownPoint {
+                    date: *day,
+                    scope: state.scope.len() as i64,
+                    completed: state.completed,
+                    remaining: state.remaining(),
+                    scope_added: state.scope.difference(&previous.scope).count() as i64,
+                    scope_removed: previous.scope.difference(&state.scope).count() as i64,
+                    ideal_remaining: ideal_remaining(boundaries[i + 1], state.scope.len() as i64),
+                }
+            })
+            .collect();
+
+        Ok(ProjectBurndown {
+            project_id: project.id,
+            from,
+            to,
+            due_date: project.due_date,
+            initial_scope,
+            points,
+        })
+    }
+
+    // Projects the completion date from the recent throughput and compares it
+    // with the project due date. Projections that land within a fifth of the
+    // project span after the due date count as at risk.
+    async fn get_project_health(&self, input: GetProjectHealthInput) -> Result<ProjectHealth, SDKError> {
+        let project = self.get_project(input.project_id).await?;
+
+        let at = input.at.unwrap_or_else(Utc::now);
+        let start_date = project.start_date.unwrap_or(project.created_at);
+
+        // young projects are measured over their whole life instead
+        let window_start = (at
+            - Duration::try_days(
+                input
+                    .velocity_window_days
+                    .unwrap_or(DEFAULT_VELOCITY_WINDOW_DAYS)
+                    .into(),
+            )
+            .unwrap_or_default())
+        .max(start_date)
+        .min(at - Duration::try_days(1).unwrap_or_default());
+        let window_days = (at - window_start).num_seconds() as f64 / 86400.0;
+
+        let changes = self.load_project_task_changes(project.id, at).await?;
+        let states = replay_project_history(project.id, &changes, &[window_start, at]);
+
+        let remaining = states[1].remaining();
+        let velocity = (states[1].completed - states[0].completed).max(0) as f64 / window_days;
+
+        let projected_completion = if remaining == 0 {
+            Some(at)
+        } else if velocity > 0.0 {
+            Some(at + Duration::try_seconds((remaining as f64 / velocity * 86400.0) as i64).unwrap_or_default())
+        } else {
+            None
+        };
+
+        let status = match (project.due_date, projected_completion) {
+            _ if remaining == 0 => ProjectHealthStatus::OnTrack,
+            (None, _) => ProjectHealthStatus::OnTrack,
+            (Some(due_date), _) if due_date < at => ProjectHealthStatus::OffTrack,
+            (Some(_), None) => ProjectHealthStatus::AtRisk,
+            (Some(due_date), Some(projected)) => {
+                let margin = ((due_date - start_date) / 5).max(Duration::try_days(1).unwrap_or_default());
+
+                if projected <= due_date {
+                    ProjectHealthStatus::OnTrack
+                } else if projected <= due_date + margin {
+                    ProjectHealthStatus::AtRisk
+                } else {
+                    ProjectHealthStatus::OffTrack
+                }
+            }
+        };
+
+        Ok(ProjectHealth {
+            project_id: project.id,
+            status,
+            due_date: project.due_date,
+            projected_completion,
+            remaining,
+            velocity,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::{operations::ProjectCrudOperations, project::Project};
+
+#[async_trait]
+pub trait ProjectArchiveOperations {
+    async fn archive_project(&self, id: Uuid) -> Result<Project, SDKError>;
+    async fn unarchive_project(&self, id: Uuid) -> Result<Project, SDKError>;
+}
+
+#[async_trait]
+impl ProjectArchiveOperations for SDKEngine {
+    // The tasks of the project are archived along with it, the ones archived
+    // before keep their own date.
+    async fn archive_project(&self, id: Uuid) -> Result<Project, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let archived_at = sqlx::query!(
+            r#"
+            UPDATE projects SET archived_at = COALESCE(archived_at, now())
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING archived_at
+            "#,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .archived_at;
+
+        sqlx::query!(
+            r#"
+            UPDATE tasks SET archived_at = $2
+            WHERE project_id = $1 AND archived_at IS NULL AND deleted_at IS NULL
+            "#,
+            id,
+            archived_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_project(id).await
+    }
+
+    // Only the tasks archived along with the project come back with it.
+    async fn unarchive_project(&self, id: Uuid) -> Result<Project, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let project = sqlx::query!(
+            r#"
+            SELECT archived_at FROM projects
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(archived_at) = project.archived_at {
+            sqlx::query!(
+                r#"
+                UPDATE tasks SET archived_at = NULL
+                WHERE project_id = $1 AND archived_at = $2
+                "#,
+                id,
+                archived_at,
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!("UPDATE projects SET archived_at = NULL WHERE id = $1", id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        self.get_project(id).await
+    }
+}
@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 use crate::backend::engine::SDKEngine;
 
-use super::project::{Project, ProjectStatus, ProjectVisibility};
+use super::project::{EstimateScale, Project, ProjectStatus, ProjectVisibility};
 
 pub struct ProjectLoader(Arc<SDKEngine>);
 
@@ -23,7 +23,7 @@ impl Loader<Uuid> for ProjectLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let projects = sqlx::query!(
             r#"
-            SELECT * FROM projects WHERE id  = ANY($1)
+            SELECT * FROM projects WHERE id  = ANY($1) AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -58,6 +58,8 @@ impl Loader<Uuid> for ProjectLoader {
                             .clone()
                             .and_then(|a| ProjectVisibility::from_str(&a).ok())
                             .unwrap_or_default(),
+                        estimate_scale: EstimateScale::from_str(&project.estimate_scale).unwrap_or_default(),
+                        archived_at: project.archived_at,
                     },
                 )
             })
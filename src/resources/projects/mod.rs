@@ -1,4 +1,8 @@
+pub mod analytics;
+pub mod archive;
 pub mod loader;
 pub mod operations;
 pub mod project;
 pub mod relations;
+pub mod schedule;
+pub mod transfers;
@@ -6,16 +6,17 @@ use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{Postgres, Row, Transaction};
 use uuid::Uuid;
 
 use crate::{
     backend::engine::SDKEngine,
     common::commons::{SortOrder, UpdateListInput},
     errors::sdk::SDKError,
+    resources::notifications::notification::WatchReason,
 };
 
-use super::project::{Project, ProjectStatus, ProjectVisibility};
+use super::project::{EstimateScale, Project, ProjectStatus, ProjectVisibility};
 
 #[async_trait]
 pub trait ProjectCrudOperations {
@@ -38,6 +39,8 @@ pub struct CreateProjectInput {
     pub status: Option<ProjectStatus>,
     #[builder(setter(strip_option), default)]
     pub visibility: Option<ProjectVisibility>,
+    #[builder(setter(strip_option), default)]
+    pub estimate_scale: Option<EstimateScale>,
 
     #[builder(setter(strip_option), default)]
     pub prefix: Option<String>,
@@ -76,6 +79,9 @@ pub struct UpdateProjectInput {
     pub status: Option<ProjectStatus>,
     #[builder(setter(strip_option), default)]
     pub visibility: Option<ProjectVisibility>,
+    // estimates already set keep their value when the scale changes
+    #[builder(setter(strip_option), default)]
+    pub estimate_scale: Option<EstimateScale>,
 
     #[builder(setter(strip_option), default)]
     pub members: Option<UpdateListInput>,
@@ -94,6 +100,9 @@ pub struct GetProjectsInput {
     #[builder(setter(strip_option), default)]
     pub sort_order: Option<SortOrder>,
 
+    #[builder(setter(strip_option), default)]
+    pub include_archived: Option<bool>,
+
     #[builder(setter(into, strip_option), default = "Some(100)")]
     pub limit: Option<i32>,
     #[builder(setter(into, strip_option), default = "Some(0)")]
@@ -187,23 +196,32 @@ impl GetProjectsWhere {
     }
 }
 
-#[async_trait]
-impl ProjectCrudOperations for SDKEngine {
-    async fn create_project(&self, input: CreateProjectInput) -> Result<Project, SDKError> {
-        let mut tx = self.db_pool.as_ref().begin().await?;
-
+impl SDKEngine {
+    // Inserts the project and its members and teams, watchers are left to the
+    // caller once the transaction commits.
+    pub(crate) async fn create_project_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        input: CreateProjectInput,
+    ) -> Result<Project, SDKError> {
         let project = sqlx::query!(
             r#"
-            INSERT INTO projects (name, description, owner_id, status)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO projects (name, description, owner_id, status, visibility, prefix, lead_id, start_date, due_date, estimate_scale)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             RETURNING *
             "#,
             input.name,
             input.description,
             input.owner_id,
             input.status.unwrap_or_default().to_string(),
+            input.visibility.unwrap_or_default().to_string(),
+            input.prefix,
+            input.lead_id,
+            input.start_date,
+            input.due_date,
+            input.estimate_scale.unwrap_or_default().to_string(),
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(&mut **tx)
         .await?;
 
         if let Some(members) = input.members {
@@ -216,7 +234,7 @@ impl ProjectCrudOperations for SDKEngine {
                     member,
                     project.id,
                 )
-                .execute(&mut *tx)
+                .execute(&mut **tx)
                 .await
                 .unwrap();
             }
@@ -232,14 +250,12 @@ impl ProjectCrudOperations for SDKEngine {
                     team,
                     project.id,
                 )
-                .execute(&mut *tx)
+                .execute(&mut **tx)
                 .await
                 .unwrap();
             }
         }
 
-        tx.commit().await?;
-
         Ok(Project {
             id: project.id,
             created_at: project.created_at,
@@ -259,13 +275,31 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            estimate_scale: EstimateScale::from_str(&project.estimate_scale).unwrap_or_default(),
+            archived_at: project.archived_at,
         })
     }
+}
+
+#[async_trait]
+impl ProjectCrudOperations for SDKEngine {
+    async fn create_project(&self, input: CreateProjectInput) -> Result<Project, SDKError> {
+        let mut tx = self.db_pool.as_ref().begin().await?;
+
+        let project = self.create_project_in_tx(&mut tx, input).await?;
+
+        tx.commit().await?;
+
+        self.auto_watch(&[project.owner_id], None, Some(project.id), WatchReason::Created)
+            .await?;
+
+        Ok(project)
+    }
 
     async fn get_project(&self, id: Uuid) -> Result<Project, SDKError> {
         let project_info = sqlx::query!(
             r#"
-            SELECT * FROM projects WHERE id = $1
+            SELECT * FROM projects WHERE id = $1 AND deleted_at IS NULL
             "#,
             id,
         )
@@ -291,6 +325,8 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            estimate_scale: EstimateScale::from_str(&project_info.estimate_scale).unwrap_or_default(),
+            archived_at: project_info.archived_at,
         })
     }
 
@@ -308,8 +344,9 @@ impl ProjectCrudOperations for SDKEngine {
                 start_date = COALESCE($5, start_date),
                 due_date = COALESCE($6, due_date),
                 status = COALESCE($7, status),
-                visibility = COALESCE($8, visibility)
-            WHERE id = $9
+                visibility = COALESCE($8, visibility),
+                estimate_scale = COALESCE($9, estimate_scale)
+            WHERE id = $10 AND deleted_at IS NULL
             RETURNING *
             "#,
             input.name,
@@ -320,6 +357,7 @@ impl ProjectCrudOperations for SDKEngine {
             input.due_date,
             input.status.map(|a| a.to_string()),
             input.visibility.map(|a| a.to_string()),
+            input.estimate_scale.map(|a| a.to_string()),
             id,
         )
         .fetch_one(&mut *tx)
@@ -406,20 +444,46 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            estimate_scale: EstimateScale::from_str(&project_final_info.estimate_scale).unwrap_or_default(),
+            archived_at: project_final_info.archived_at,
         })
     }
 
+    // The tasks of the project and their subtasks go to the trash with it.
     async fn delete_project(&self, id: Uuid) -> Result<Project, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
         let project_info = sqlx::query!(
             r#"
-            DELETE FROM projects WHERE id = $1
+            UPDATE projects SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
             id,
         )
-        .fetch_one(self.db_pool.as_ref())
+        .fetch_one(&mut *tx)
         .await?;
 
+        sqlx::query!(
+            r#"
+            WITH RECURSIVE trashed AS (
+                SELECT id FROM tasks WHERE project_id = $1 AND deleted_at IS NULL
+                UNION
+                SELECT tasks.id FROM tasks JOIN trashed ON tasks.parent_id = trashed.id
+                WHERE tasks.deleted_at IS NULL
+            )
+            UPDATE tasks SET deleted_at = $2
+            FROM trashed
+            WHERE tasks.id = trashed.id
+            "#,
+            id,
+            project_info.deleted_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
         Ok(Project {
             id: project_info.id,
             created_at: project_info.created_at,
@@ -439,14 +503,20 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            estimate_scale: EstimateScale::from_str(&project_info.estimate_scale).unwrap_or_default(),
+            archived_at: project_info.archived_at,
         })
     }
 
     async fn get_projects(&self, input: GetProjectsInput) -> Result<Vec<Project>, SDKError> {
-        let mut query = "SELECT * FROM projects ".to_string();
+        let mut query = "SELECT * FROM projects WHERE deleted_at IS NULL ".to_string();
+
+        if !input.include_archived.unwrap_or(false) {
+            query.push_str("AND archived_at IS NULL ");
+        }
 
         if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+            query.push_str(format!("AND ({}) ", filter.compile_sql()).as_str());
         }
 
         if let Some(sort_by) = input.sort_by {
@@ -488,6 +558,8 @@ impl ProjectCrudOperations for SDKEngine {
                     .get::<'_, Option<String>, _>("visibility")
                     .and_then(|a| ProjectVisibility::from_str(&a).ok())
                     .unwrap_or_default(),
+                estimate_scale: EstimateScale::from_str(x.get("estimate_scale")).unwrap_or_default(),
+                archived_at: x.get("archived_at"),
             })
             .collect::<Vec<Project>>();
 
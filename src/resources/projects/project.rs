@@ -19,6 +19,7 @@ pub struct Project {
     pub name: String,
     pub status: ProjectStatus,
     pub visibility: ProjectVisibility,
+    pub estimate_scale: EstimateScale,
     pub owner_id: Uuid,
 
     pub prefix: Option<String>,
@@ -27,6 +28,7 @@ pub struct Project {
     pub lead_id: Option<Uuid>,
     pub start_date: Option<DateTime<Utc>>,
     pub due_date: Option<DateTime<Utc>>,
+    pub archived_at: Option<DateTime<Utc>>,
 }
 
 #[derive(
@@ -54,3 +56,44 @@ pub enum ProjectVisibility {
     Public,
     // Shared,
 }
+
+// T-shirt sizes are stored as points so they can be summed, XS = 1, S = 2,
+// M = 3, L = 5 and XL = 8.
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum EstimateScale {
+    #[default]
+    Points,
+    TShirt,
+    Hours,
+}
+
+const T_SHIRT_SIZES: [(f64, &str); 5] = [(1.0, "XS"), (2.0, "S"), (3.0, "M"), (5.0, "L"), (8.0, "XL")];
+
+impl EstimateScale {
+    pub fn validate(&self, estimate: f64) -> Result<(), String> {
+        if !estimate.is_finite() || estimate < 0.0 {
+            return Err("it must be a non negative number".to_string());
+        }
+
+        match self {
+            EstimateScale::TShirt if !T_SHIRT_SIZES.iter().any(|(size, _)| *size == estimate) => {
+                Err("t-shirt sizes are 1 (XS), 2 (S), 3 (M), 5 (L) or 8 (XL)".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn label(&self, estimate: f64) -> String {
+        match self {
+            EstimateScale::Points => format!("{} points", estimate),
+            EstimateScale::Hours => format!("{} hours", estimate),
+            EstimateScale::TShirt => T_SHIRT_SIZES
+                .iter()
+                .find(|(size, _)| *size == estimate)
+                .map(|(_, label)| label.to_string())
+                .unwrap_or_else(|| estimate.to_string()),
+        }
+    }
+}
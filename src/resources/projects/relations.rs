@@ -9,8 +9,15 @@ use crate::{
     resources::{
         assets::asset::{Asset, AssetKind},
         changes::change::{Change, ChangeOperation, ChangeResourceType},
+        comments::comment::Comment,
+        custom_fields::{custom_field::CustomFieldValue, operations::CustomFieldOperations},
+        cycles::cycle::Cycle,
         members::member::Member,
-        tasks::task::{Task, TaskPriority, TaskStatus},
+        milestones::milestone::Milestone,
+        tasks::{
+            estimates::TaskEstimateOperations,
+            task::{EstimateRollup, Task, TaskPriority, TaskStatus},
+        },
         teams::team::Team,
     },
 };
@@ -26,14 +33,25 @@ pub trait ProjectRelations {
     async fn members(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError>;
     async fn assets(&self, loaders: &SDKLoaders) -> Result<Vec<Asset>, SDKError>;
     async fn teams(&self, loaders: &SDKLoaders) -> Result<Vec<Team>, SDKError>;
+    async fn cycles(&self, loaders: &SDKLoaders) -> Result<Vec<Cycle>, SDKError>;
+    async fn active_cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError>;
+    async fn milestones(&self, loaders: &SDKLoaders) -> Result<Vec<Milestone>, SDKError>;
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError>;
 
     async fn changes(&self, loaders: &SDKLoaders) -> Result<Vec<Change>, SDKError>;
+    async fn custom_field_values(&self, loaders: &SDKLoaders) -> Result<Vec<CustomFieldValue>, SDKError>;
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError>;
 }
 
 #[async_trait]
 impl ProjectRelations for Project {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
-        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
 
         Ok(data)
     }
@@ -43,16 +61,16 @@ impl ProjectRelations for Project {
             return Ok(None);
         };
 
-        let data = loaders.member_loader.load_one(lead_id).await.unwrap().unwrap();
+        let data = loaders.member_loader.load_one(lead_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
     }
 
     async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
         let tasks = sqlx::query!(
             r#"
         SELECT * FROM tasks
-        WHERE project_id = $1"#,
+        WHERE project_id = $1 AND deleted_at IS NULL AND archived_at IS NULL"#,
             &self.id
         )
         .fetch_all(&*loaders.engine.db_pool)
@@ -83,6 +101,14 @@ impl ProjectRelations for Project {
                 owner_id: task.owner_id,
                 count: task.count,
                 parent_id: task.parent_id,
+                state_id: task.state_id,
+                cycle_id: task.cycle_id,
+                milestone_id: task.milestone_id,
+                estimate: task.estimate,
+                number: task.number,
+                identifier: task.identifier.clone(),
+                rank: task.rank.clone(),
+                archived_at: task.archived_at,
             })
             .collect())
     }
@@ -91,7 +117,8 @@ impl ProjectRelations for Project {
         let ids: Vec<Uuid> = sqlx::query!(
             r#"
             SELECT member_id FROM members_by_projects
-            WHERE project_id = $1
+            JOIN members ON members.id = members_by_projects.member_id
+            WHERE project_id = $1 AND members.deleted_at IS NULL
             "#,
             &self.id
         )
@@ -104,10 +131,7 @@ impl ProjectRelations for Project {
 
         let members_map = loaders.member_loader.load_many(ids.clone()).await.unwrap();
 
-        let members: &Vec<Member> = &ids
-            .into_iter()
-            .map(|id| members_map.get(&id).unwrap().clone())
-            .collect();
+        let members: &Vec<Member> = &ids.into_iter().filter_map(|id| members_map.get(&id).cloned()).collect();
 
         Ok(members.clone())
     }
@@ -116,7 +140,7 @@ impl ProjectRelations for Project {
         let assets = sqlx::query!(
             r#"
         SELECT * FROM assets
-        WHERE project_id = $1"#,
+        WHERE project_id = $1 AND deleted_at IS NULL"#,
             &self.id
         )
         .fetch_all(&*loaders.engine.db_pool)
@@ -158,7 +182,7 @@ impl ProjectRelations for Project {
 
         let teams_map = loaders.team_loader.load_many(ids.clone()).await.unwrap();
 
-        let teams: &Vec<Team> = &ids.into_iter().map(|id| teams_map.get(&id).unwrap().clone()).collect();
+        let teams: &Vec<Team> = &ids.into_iter().filter_map(|id| teams_map.get(&id).cloned()).collect();
 
         Ok(teams.clone())
     }
@@ -188,4 +212,106 @@ impl ProjectRelations for Project {
             })
             .collect())
     }
+    async fn custom_field_values(&self, loaders: &SDKLoaders) -> Result<Vec<CustomFieldValue>, SDKError> {
+        loaders.engine.get_custom_field_values(self.id).await
+    }
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM comments
+            WHERE project_id = $1 AND parent_id IS NULL
+            ORDER BY created_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+
+        let comments_map = loaders.comment_loader.load_many(ids.clone()).await.unwrap();
+
+        let comments: &Vec<Comment> = &ids
+            .into_iter()
+            .map(|id| comments_map.get(&id).unwrap().clone())
+            .collect();
+
+        Ok(comments.clone())
+    }
+
+    async fn cycles(&self, loaders: &SDKLoaders) -> Result<Vec<Cycle>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM cycles
+            WHERE project_id = $1 AND deleted_at IS NULL
+            ORDER BY starts_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+
+        let cycles_map = loaders.cycle_loader.load_many(ids.clone()).await.unwrap();
+
+        let cycles: &Vec<Cycle> = &ids.into_iter().map(|id| cycles_map.get(&id).unwrap().clone()).collect();
+
+        Ok(cycles.clone())
+    }
+
+    async fn active_cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError> {
+        let Some(cycle) = sqlx::query!(
+            r#"
+            SELECT id FROM cycles
+            WHERE project_id = $1 AND completed_at IS NULL AND starts_at <= now() AND deleted_at IS NULL
+            ORDER BY starts_at DESC
+            LIMIT 1
+            "#,
+            &self.id
+        )
+        .fetch_optional(&*loaders.engine.db_pool)
+        .await
+        .unwrap() else {
+            return Ok(None);
+        };
+
+        let data = loaders.cycle_loader.load_one(cycle.id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn milestones(&self, loaders: &SDKLoaders) -> Result<Vec<Milestone>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM milestones
+            WHERE project_id = $1 AND deleted_at IS NULL
+            ORDER BY target_date NULLS LAST, created_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+
+        let milestones_map = loaders.milestone_loader.load_many(ids.clone()).await.unwrap();
+
+        let milestones: &Vec<Milestone> = &ids
+            .into_iter()
+            .map(|id| milestones_map.get(&id).unwrap().clone())
+            .collect();
+
+        Ok(milestones.clone())
+    }
+
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError> {
+        loaders.engine.get_project_estimate_rollup(self.id).await
+    }
 }
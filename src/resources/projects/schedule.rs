@@ -0,0 +1,315 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+use async_graphql::{InputObject, SimpleObject};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::tasks::task::{Task, TaskPriority, TaskStatus},
+};
+
+use super::{operations::ProjectCrudOperations, project::EstimateScale};
+
+#[async_trait]
+pub trait ProjectScheduleOperations {
+    async fn compute_schedule(&self, input: ComputeScheduleInput) -> Result<ProjectSchedule, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct ComputeScheduleInput {
+    pub project_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub start_date: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub default_task_duration_days: Option<i32>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKScheduledTask")]
+pub struct ScheduledTask {
+    pub task_id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+
+    pub earliest_start: DateTime<Utc>,
+    pub earliest_finish: DateTime<Utc>,
+    pub latest_start: DateTime<Utc>,
+    pub latest_finish: DateTime<Utc>,
+    pub slack_days: f64,
+
+    pub critical: bool,
+    pub due_date: Option<DateTime<Utc>>,
+    pub infeasible_due_date: bool,
+
+    pub blocked_by: Vec<Uuid>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKProjectSchedule")]
+pub struct ProjectSchedule {
+    pub project_id: Uuid,
+
+    pub start_date: DateTime<Utc>,
+    pub finish_date: DateTime<Utc>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub feasible: bool,
+
+    pub tasks: Vec<ScheduledTask>,
+    pub critical_path: Vec<Uuid>,
+}
+
+impl ProjectSchedule {
+    pub fn to_gantt_json(&self) -> Result<String, SDKError> {
+        let tasks = self
+            .tasks
+            .iter()
+            .map(|task| {
+                let custom_class = match (task.infeasible_due_date, task.critical) {
+                    (true, _) => "infeasible",
+                    (false, true) => "critical",
+                    (false, false) => "",
+                };
+
+                json!({
+                    "id": task.task_id,
+                    "name": task.title,
+                    "start": task.earliest_start.to_rfc3339(),
+                    "end": task.earliest_finish.to_rfc3339(),
+                    "progress": match task.status {
+                        TaskStatus::Done => 100,
+                        TaskStatus::InProgress => 50,
+                        _ => 0,
+                    },
+                    "dependencies": task
+                        .blocked_by
+                        .iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                    "critical": task.critical,
+                    "custom_class": custom_class,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(serde_json::to_string(&json!({
+            "project_id": self.project_id,
+            "start": self.start_date.to_rfc3339(),
+            "end": self.finish_date.to_rfc3339(),
+            "tasks": tasks,
+        }))?)
+    }
+}
+
+// working hours in a scheduled day, estimates in points take a day per point
+const HOURS_PER_DAY: f64 = 8.0;
+
+// finished and canceled tasks hold nothing up, the rest take their estimate
+// when they have one
+fn task_duration(task: &Task, scale: EstimateScale, default_duration: Duration) -> Duration {
+    match (task.status, task.estimate) {
+        (TaskStatus::Done | TaskStatus::Canceled, _) => Duration::zero(),
+        (_, Some(estimate)) => {
+            let days = match scale {
+                EstimateScale::Hours => estimate / HOURS_PER_DAY,
+                EstimateScale::Points | EstimateScale::TShirt => estimate,
+            };
+
+            Duration::try_seconds((days.max(0.0) * 86400.0) as i64).unwrap_or(default_duration)
+        }
+        _ => default_duration,
+    }
+}
+
+#[async_trait]
+impl ProjectScheduleOperations for SDKEngine {
+    async fn compute_schedule(&self, input: ComputeScheduleInput) -> Result<ProjectSchedule, SDKError> {
+        let project = self.get_project(input.project_id).await?;
+
+        let tasks_info = sqlx::query!(
+            r#"
+            SELECT * FROM tasks
+            WHERE project_id = $1 AND deleted_at IS NULL
+            ORDER BY created_at
+            "#,
+            input.project_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let tasks: Vec<Task> = tasks_info
+            .into_iter()
+            .map(|task_info| Task {
+                id: task_info.id,
+                created_at: task_info.created_at,
+                updated_at: task_info.updated_at,
+                title: task_info.title,
+                description: task_info.description,
+                status: task_info
+                    .status
+                    .and_then(|a| TaskStatus::from_str(&a).ok())
+                    .unwrap_or_default(),
+                priority: task_info
+                    .priority
+                    .and_then(|a| TaskPriority::from_str(&a).ok())
+                    .unwrap_or_default(),
+                due_date: task_info.due_date,
+                project_id: task_info.project_id,
+                lead_id: task_info.lead_id,
+                owner_id: task_info.owner_id,
+                count: task_info.count,
+                parent_id: task_info.parent_id,
+                state_id: task_info.state_id,
+                cycle_id: task_info.cycle_id,
+                milestone_id: task_info.milestone_id,
+                estimate: task_info.estimate,
+                number: task_info.number,
+                identifier: task_info.identifier,
+                rank: task_info.rank,
+                archived_at: task_info.archived_at,
+            })
+            .collect();
+
+        // only edges between tasks of this project take part in the schedule
+        let edges = sqlx::query!(
+            r#"
+            SELECT d.source_task_id, d.target_task_id FROM task_dependencies d
+            INNER JOIN tasks s ON s.id = d.source_task_id
+            INNER JOIN tasks t ON t.id = d.target_task_id
+            WHERE d.kind = 'Blocks' AND s.project_id = $1 AND t.project_id = $1
+            "#,
+            input.project_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let default_duration =
+            Duration::try_days(input.default_task_duration_days.unwrap_or(1).max(0) as i64).unwrap_or_default();
+        let start_date = input
+            .start_date
+            .or(project.start_date)
+            .or(tasks.iter().map(|t| t.created_at).min())
+            .unwrap_or_else(Utc::now);
+
+        let index: HashMap<Uuid, usize> = tasks.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+
+        for edge in edges.iter() {
+            let (Some(&source), Some(&target)) = (index.get(&edge.source_task_id), index.get(&edge.target_task_id))
+            else {
+                continue;
+            };
+
+            predecessors[target].push(source);
+            successors[source].push(target);
+        }
+
+        // Kahn's algorithm, keeping creation order among ready tasks
+        let mut in_degree: Vec<usize> = predecessors.iter().map(|p| p.len()).collect();
+        let mut ready: VecDeque<usize> = (0..tasks.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(tasks.len());
+
+        while let Some(i) = ready.pop_front() {
+            order.push(i);
+
+            for &next in successors[i].iter() {
+                in_degree[next] -= 1;
+
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if order.len() != tasks.len() {
+            return Err(SDKError::TaskDependencyCycle);
+        }
+
+        let durations: Vec<Duration> = tasks
+            .iter()
+            .map(|t| task_duration(t, project.estimate_scale, default_duration))
+            .collect();
+
+        let mut earliest_start = vec![start_date; tasks.len()];
+        let mut earliest_finish = vec![start_date; tasks.len()];
+
+        for &i in order.iter() {
+            earliest_start[i] = predecessors[i]
+                .iter()
+                .map(|&p| earliest_finish[p])
+                .max()
+                .unwrap_or(start_date);
+            earliest_finish[i] = earliest_start[i] + durations[i];
+        }
+
+        let finish_date = earliest_finish.iter().copied().max().unwrap_or(start_date);
+
+        let mut latest_finish = vec![finish_date; tasks.len()];
+        let mut latest_start = vec![finish_date; tasks.len()];
+
+        for &i in order.iter().rev() {
+            latest_finish[i] = successors[i]
+                .iter()
+                .map(|&s| latest_start[s])
+                .min()
+                .unwrap_or(finish_date);
+            latest_start[i] = latest_finish[i] - durations[i];
+        }
+
+        let scheduled_tasks: Vec<ScheduledTask> = order
+            .iter()
+            .map(|&i| {
+                let task = &tasks[i];
+                let slack = latest_start[i] - earliest_start[i];
+
+                ScheduledTask {
+                    task_id: task.id,
+                    title: task.title.clone(),
+                    status: task.status,
+                    earliest_start: earliest_start[i],
+                    earliest_finish: earliest_finish[i],
+                    latest_start: latest_start[i],
+                    latest_finish: latest_finish[i],
+                    slack_days: slack.num_seconds() as f64 / 86_400.0,
+                    critical: slack.is_zero(),
+                    due_date: task.due_date,
+                    infeasible_due_date: task.due_date.is_some_and(|due| due < earliest_finish[i]),
+                    blocked_by: predecessors[i].iter().map(|&p| tasks[p].id).collect(),
+                }
+            })
+            .collect();
+
+        let critical_path = scheduled_tasks
+            .iter()
+            .filter(|t| t.critical)
+            .map(|t| t.task_id)
+            .collect();
+
+        let feasible = project.due_date.is_none_or(|due| finish_date <= due)
+            && scheduled_tasks.iter().all(|t| !t.infeasible_due_date);
+
+        Ok(ProjectSchedule {
+            project_id: project.id,
+            start_date,
+            finish_date,
+            due_date: project.due_date,
+            feasible,
+            tasks: scheduled_tasks,
+            critical_path,
+        })
+    }
+}
@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{notifications::notification::WatchReason, tasks::transfers::TaskCopyTarget},
+};
+
+use super::{
+    operations::{CreateProjectInput, ProjectCrudOperations},
+    project::Project,
+};
+
+#[async_trait]
+pub trait ProjectTransferOperations {
+    async fn duplicate_project(&self, id: Uuid, owner_id: Uuid) -> Result<Project, SDKError>;
+}
+
+#[async_trait]
+impl ProjectTransferOperations for SDKEngine {
+    // The copy gets the members, teams, workflow and tasks of the project.
+    // Milestones, project cycles and custom fields are not copied.
+    async fn duplicate_project(&self, id: Uuid, owner_id: Uuid) -> Result<Project, SDKError> {
+        let project = self.get_project(id).await?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let members: Vec<Uuid> = sqlx::query_scalar("SELECT member_id FROM members_by_projects WHERE project_id = $1")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let teams: Vec<Uuid> = sqlx::query_scalar("SELECT team_id FROM teams_by_projects WHERE project_id = $1")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let copy = self
+            .create_project_in_tx(
+                &mut tx,
+                CreateProjectInput {
+                    name: format!("{} (copy)", project.name),
+                    owner_id,
+                    status: Some(project.status),
+                    visibility: Some(project.visibility),
+                    estimate_scale: Some(project.estimate_scale),
+                    prefix: project.prefix,
+                    description: project.description,
+                    lead_id: project.lead_id,
+                    start_date: project.start_date,
+                    due_date: project.due_date,
+                    members: Some(members),
+                    teams: Some(teams),
+                },
+            )
+            .await?;
+
+        let workflow = sqlx::query(
+            r#"
+            INSERT INTO workflows (owner_id, project_id, name, description)
+            SELECT $3, $2, name, description FROM workflows WHERE project_id = $1
+            RETURNING id, (SELECT id FROM workflows WHERE project_id = $1) AS source_id
+            "#,
+        )
+        .bind(id)
+        .bind(copy.id)
+        .bind(owner_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let workflow_id = match workflow {
+            Some(workflow) => {
+                let workflow_id: Uuid = workflow.get("id");
+                let source_id: Uuid = workflow.get("source_id");
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO workflow_states (workflow_id, name, category, position, color, is_default)
+                    SELECT $2, name, category, position, color, is_default
+                    FROM workflow_states WHERE workflow_id = $1
+                    "#,
+                )
+                .bind(source_id)
+                .bind(workflow_id)
+                .execute(&mut *tx)
+                .await?;
+
+                // state names are unique within a workflow
+                sqlx::query(
+                    r#"
+                    INSERT INTO workflow_transitions (workflow_id, from_state_id, to_state_id)
+                    SELECT $2, from_copies.id, to_copies.id
+                    FROM workflow_transitions transitions
+                        JOIN workflow_states from_states ON from_states.id = transitions.from_state_id
+                        JOIN workflow_states to_states ON to_states.id = transitions.to_state_id
+                        JOIN workflow_states from_copies
+                            ON from_copies.workflow_id = $2 AND from_copies.name = from_states.name
+                        JOIN workflow_states to_copies
+                            ON to_copies.workflow_id = $2 AND to_copies.name = to_states.name
+                    WHERE transitions.workflow_id = $1
+                    "#,
+                )
+                .bind(source_id)
+                .bind(workflow_id)
+                .execute(&mut *tx)
+                .await?;
+
+                Some(workflow_id)
+            }
+            None => None,
+        };
+
+        let task_ids: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM tasks WHERE project_id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .fetch_all(&mut *tx)
+                .await?;
+
+        let copy_ids = self
+            .copy_tasks_in_tx(
+                &mut tx,
+                &task_ids,
+                owner_id,
+                Some(TaskCopyTarget {
+                    project_id: copy.id,
+                    workflow_id,
+                }),
+            )
+            .await?;
+
+        tx.commit().await?;
+
+        self.auto_watch(&[copy.owner_id], None, Some(copy.id), WatchReason::Created)
+            .await?;
+
+        self.notify_tasks_copied(&copy_ids).await?;
+
+        Ok(copy)
+    }
+}
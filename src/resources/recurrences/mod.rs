@@ -0,0 +1,3 @@
+pub mod operations;
+pub mod recurrence;
+pub mod runner;
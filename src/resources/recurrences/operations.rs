@@ -0,0 +1,735 @@
+use std::{collections::HashSet, str::FromStr};
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use rrule::{RRule, Tz, Unvalidated};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::tasks::{
+        operations::{TaskCrudOperations, UpdateTaskInput},
+        task::{Task, TaskPriority, TaskStatus},
+    },
+};
+
+use super::recurrence::{TaskOccurrence, TaskOccurrenceStatus, TaskRecurrence};
+
+const MAX_OCCURRENCES_PER_RUN: u16 = 100;
+
+#[async_trait]
+pub trait TaskRecurrenceOperations {
+    async fn create_task_recurrence(&self, input: CreateTaskRecurrenceInput) -> Result<TaskRecurrence, SDKError>;
+    async fn get_task_recurrence(&self, id: Uuid) -> Result<TaskRecurrence, SDKError>;
+    async fn get_task_recurrences(&self, template_task_id: Option<Uuid>) -> Result<Vec<TaskRecurrence>, SDKError>;
+    async fn update_task_recurrence(
+        &self,
+        id: Uuid,
+        input: UpdateTaskRecurrenceInput,
+    ) -> Result<TaskRecurrence, SDKError>;
+    async fn delete_task_recurrence(&self, id: Uuid) -> Result<TaskRecurrence, SDKError>;
+
+    async fn get_task_occurrences(&self, recurrence_id: Uuid) -> Result<Vec<TaskOccurrence>, SDKError>;
+    async fn skip_task_occurrence(
+        &self,
+        recurrence_id: Uuid,
+        occurrence_at: DateTime<Utc>,
+    ) -> Result<TaskOccurrence, SDKError>;
+    async fn edit_task_occurrence(
+        &self,
+        recurrence_id: Uuid,
+        occurrence_at: DateTime<Utc>,
+        input: UpdateTaskInput,
+    ) -> Result<Task, SDKError>;
+
+    async fn generate_next_occurrence(&self, recurrence_id: Uuid) -> Result<Option<Task>, SDKError>;
+    async fn generate_due_occurrences(&self, now: DateTime<Utc>, lookahead: Duration) -> Result<Vec<Task>, SDKError>;
+    async fn generate_occurrence_after_completion(&self, task_id: Uuid) -> Result<Option<Task>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateTaskRecurrenceInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub template_task_id: Uuid,
+    pub rrule: String,
+
+    #[builder(setter(strip_option), default)]
+    pub dtstart: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateTaskRecurrenceInput {
+    #[builder(setter(strip_option), default)]
+    pub rrule: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub dtstart: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub active: Option<bool>,
+}
+
+// Returns the occurrences of the rule strictly after `after` (and up to `until`, inclusive).
+fn occurrences_after(
+    rrule: &str,
+    dtstart: DateTime<Utc>,
+    after: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+    limit: u16,
+) -> Result<Vec<DateTime<Utc>>, SDKError> {
+    let rule: RRule<Unvalidated> = rrule.parse()?;
+    let mut set = rule
+        .build(dtstart.with_timezone(&Tz::UTC))?
+        .after((after + Duration::try_seconds(1).unwrap_or_default()).with_timezone(&Tz::UTC));
+
+    if let Some(until) = until {
+        set = set.before(until.with_timezone(&Tz::UTC));
+    }
+
+    Ok(set
+        .all(limit)
+        .dates
+        .into_iter()
+        .map(|d| d.with_timezone(&Utc))
+        .collect())
+}
+
+fn is_occurrence_of(recurrence: &TaskRecurrence, occurrence_at: DateTime<Utc>) -> Result<bool, SDKError> {
+    let before = occurrence_at - Duration::try_seconds(1).unwrap_or_default();
+    let dates = occurrences_after(&recurrence.rrule, recurrence.dtstart, before, Some(occurrence_at), 1)?;
+
+    Ok(dates.first() == Some(&occurrence_at))
+}
+
+impl SDKEngine {
+    async fn recorded_occurrences(&self, recurrence_id: Uuid) -> Result<HashSet<DateTime<Utc>>, SDKError> {
+        let recorded = sqlx::query!(
+            r#"
+            SELECT occurrence_at FROM task_occurrences
+            WHERE recurrence_id = $1
+            "#,
+            recurrence_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(recorded.into_iter().map(|r| r.occurrence_at).collect())
+    }
+
+    async fn materialize_occurrence(
+        &self,
+        recurrence: &TaskRecurrence,
+        occurrence_at: DateTime<Utc>,
+    ) -> Result<Option<Task>, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            SELECT id FROM task_recurrences WHERE id = $1 FOR UPDATE
+            "#,
+            recurrence.id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let existing = sqlx::query!(
+            r#"
+            SELECT id FROM task_occurrences
+            WHERE recurrence_id = $1 AND occurrence_at = $2
+            "#,
+            recurrence.id,
+            occurrence_at,
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if existing.is_some() {
+            return Ok(None);
+        }
+
+        let template = sqlx::query!(
+            r#"
+            SELECT * FROM tasks WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            recurrence.template_task_id,
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // a template in the trash pauses the recurrence until it is restored
+        let Some(template) = template else {
+            return Ok(None);
+        };
+
+        let state = self
+            .resolve_task_state(template.project_id, None, None, Some(TaskStatus::ToDo), None)
+            .await?;
+
+        let task = sqlx::query!(
+            r#"
+            INSERT INTO tasks (title, description, owner_id, status, priority, due_date, project_id, lead_id, parent_id, state_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+            template.title,
+            template.description,
+            template.owner_id,
+            state
+                .as_ref()
+                .map(|(_, status)| *status)
+                .unwrap_or(TaskStatus::ToDo)
+                .to_string(),
+            template.priority,
+            occurrence_at,
+            template.project_id,
+            template.lead_id,
+            template.parent_id,
+            state.map(|(state, _)| state.id),
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO labels_by_tasks (task_id, label_id)
+            SELECT $1, label_id FROM labels_by_tasks WHERE task_id = $2
+            "#,
+            task.id,
+            template.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO tasks_by_assignees (task_id, assignee_id)
+            SELECT $1, assignee_id FROM tasks_by_assignees WHERE task_id = $2
+            "#,
+            task.id,
+            template.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO task_occurrences (recurrence_id, occurrence_at, task_id, status)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            recurrence.id,
+            occurrence_at,
+            task.id,
+            TaskOccurrenceStatus::Generated.to_string(),
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE task_recurrences
+            SET last_occurrence_at = GREATEST(COALESCE(last_occurrence_at, $2), $2)
+            WHERE id = $1
+            "#,
+            recurrence.id,
+            occurrence_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(Task {
+            id: task.id,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            title: task.title,
+            description: task.description,
+            status: task
+                .status
+                .and_then(|a| TaskStatus::from_str(&a).ok())
+                .unwrap_or_default(),
+            priority: task
+                .priority
+                .and_then(|a| TaskPriority::from_str(&a).ok())
+                .unwrap_or_default(),
+            due_date: task.due_date,
+            project_id: task.project_id,
+            lead_id: task.lead_id,
+            owner_id: task.owner_id,
+            count: task.count,
+            parent_id: task.parent_id,
+            state_id: task.state_id,
+            cycle_id: task.cycle_id,
+            milestone_id: task.milestone_id,
+            estimate: task.estimate,
+            number: task.number,
+            identifier: task.identifier,
+            rank: task.rank,
+            archived_at: task.archived_at,
+        }))
+    }
+}
+
+#[async_trait]
+impl TaskRecurrenceOperations for SDKEngine {
+    async fn create_task_recurrence(&self, input: CreateTaskRecurrenceInput) -> Result<TaskRecurrence, SDKError> {
+        let template = self.get_task(input.template_task_id).await?;
+        let dtstart = input.dtstart.or(template.due_date).unwrap_or_else(Utc::now);
+
+        // fail early on rules we can't expand
+        occurrences_after(&input.rrule, dtstart, dtstart, None, 1)?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let recurrence_info = sqlx::query!(
+            r#"
+            INSERT INTO task_recurrences (owner_id, template_task_id, rrule, dtstart)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            input.owner_id,
+            input.template_task_id,
+            input.rrule,
+            dtstart,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut recurrence = TaskRecurrence {
+            id: recurrence_info.id,
+            created_at: recurrence_info.created_at,
+            updated_at: recurrence_info.updated_at,
+            owner_id: recurrence_info.owner_id,
+            template_task_id: recurrence_info.template_task_id,
+            rrule: recurrence_info.rrule,
+            dtstart: recurrence_info.dtstart,
+            active: recurrence_info.active,
+            last_occurrence_at: recurrence_info.last_occurrence_at,
+        };
+
+        // a template due on the first occurrence is that occurrence, it isn't generated again
+        if template.due_date == Some(dtstart) && is_occurrence_of(&recurrence, dtstart)? {
+            sqlx::query!(
+                r#"
+                INSERT INTO task_occurrences (recurrence_id, occurrence_at, task_id, status)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                recurrence.id,
+                dtstart,
+                template.id,
+                TaskOccurrenceStatus::Generated.to_string(),
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            recurrence.last_occurrence_at = sqlx::query!(
+                r#"
+                UPDATE task_recurrences SET last_occurrence_at = $2
+                WHERE id = $1
+                RETURNING last_occurrence_at
+                "#,
+                recurrence.id,
+                dtstart,
+            )
+            .fetch_one(&mut *tx)
+            .await?
+            .last_occurrence_at;
+        }
+
+        tx.commit().await?;
+
+        Ok(recurrence)
+    }
+
+    async fn get_task_recurrence(&self, id: Uuid) -> Result<TaskRecurrence, SDKError> {
+        let recurrence_info = sqlx::query!(
+            r#"
+            SELECT * FROM task_recurrences
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(TaskRecurrence {
+            id: recurrence_info.id,
+            created_at: recurrence_info.created_at,
+            updated_at: recurrence_info.updated_at,
+            owner_id: recurrence_info.owner_id,
+            template_task_id: recurrence_info.template_task_id,
+            rrule: recurrence_info.rrule,
+            dtstart: recurrence_info.dtstart,
+            active: recurrence_info.active,
+            last_occurrence_at: recurrence_info.last_occurrence_at,
+        })
+    }
+
+    async fn get_task_recurrences(&self, template_task_id: Option<Uuid>) -> Result<Vec<TaskRecurrence>, SDKError> {
+        let recurrences_info = sqlx::query!(
+            r#"
+            SELECT * FROM task_recurrences
+            WHERE $1::uuid IS NULL OR template_task_id = $1
+            ORDER BY created_at
+            "#,
+            template_task_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(recurrences_info
+            .into_iter()
+            .map(|recurrence_info| TaskRecurrence {
+                id: recurrence_info.id,
+                created_at: recurrence_info.created_at,
+                updated_at: recurrence_info.updated_at,
+                owner_id: recurrence_info.owner_id,
+                template_task_id: recurrence_info.template_task_id,
+                rrule: recurrence_info.rrule,
+                dtstart: recurrence_info.dtstart,
+                active: recurrence_info.active,
+                last_occurrence_at: recurrence_info.last_occurrence_at,
+            })
+            .collect())
+    }
+
+    async fn update_task_recurrence(
+        &self,
+        id: Uuid,
+        input: UpdateTaskRecurrenceInput,
+    ) -> Result<TaskRecurrence, SDKError> {
+        if input.rrule.is_some() || input.dtstart.is_some() {
+            let current = self.get_task_recurrence(id).await?;
+            let rrule = input.rrule.clone().unwrap_or(current.rrule);
+            let dtstart = input.dtstart.unwrap_or(current.dtstart);
+
+            occurrences_after(&rrule, dtstart, dtstart, None, 1)?;
+        }
+
+        let recurrence_info = sqlx::query!(
+            r#"
+            UPDATE task_recurrences
+            SET
+                rrule = COALESCE($1, rrule),
+                dtstart = COALESCE($2, dtstart),
+                active = COALESCE($3, active)
+            WHERE id = $4
+            RETURNING *
+            "#,
+            input.rrule,
+            input.dtstart,
+            input.active,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(TaskRecurrence {
+            id: recurrence_info.id,
+            created_at: recurrence_info.created_at,
+            updated_at: recurrence_info.updated_at,
+            owner_id: recurrence_info.owner_id,
+            template_task_id: recurrence_info.template_task_id,
+            rrule: recurrence_info.rrule,
+            dtstart: recurrence_info.dtstart,
+            active: recurrence_info.active,
+            last_occurrence_at: recurrence_info.last_occurrence_at,
+        })
+    }
+
+    async fn delete_task_recurrence(&self, id: Uuid) -> Result<TaskRecurrence, SDKError> {
+        let recurrence_info = sqlx::query!(
+            r#"
+            DELETE FROM task_recurrences WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(TaskRecurrence {
+            id: recurrence_info.id,
+            created_at: recurrence_info.created_at,
+            updated_at: recurrence_info.updated_at,
+            owner_id: recurrence_info.owner_id,
+            template_task_id: recurrence_info.template_task_id,
+            rrule: recurrence_info.rrule,
+            dtstart: recurrence_info.dtstart,
+            active: recurrence_info.active,
+            last_occurrence_at: recurrence_info.last_occurrence_at,
+        })
+    }
+
+    async fn get_task_occurrences(&self, recurrence_id: Uuid) -> Result<Vec<TaskOccurrence>, SDKError> {
+        let occurrences_info = sqlx::query!(
+            r#"
+            SELECT * FROM task_occurrences
+            WHERE recurrence_id = $1
+            ORDER BY occurrence_at
+            "#,
+            recurrence_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(occurrences_info
+            .into_iter()
+            .map(|occurrence_info| TaskOccurrence {
+                id: occurrence_info.id,
+                created_at: occurrence_info.created_at,
+                updated_at: occurrence_info.updated_at,
+                recurrence_id: occurrence_info.recurrence_id,
+                occurrence_at: occurrence_info.occurrence_at,
+                status: TaskOccurrenceStatus::from_str(&occurrence_info.status).unwrap_or_default(),
+                task_id: occurrence_info.task_id,
+            })
+            .collect())
+    }
+
+    async fn skip_task_occurrence(
+        &self,
+        recurrence_id: Uuid,
+        occurrence_at: DateTime<Utc>,
+    ) -> Result<TaskOccurrence, SDKError> {
+        let recurrence = self.get_task_recurrence(recurrence_id).await?;
+
+        if !is_occurrence_of(&recurrence, occurrence_at)? {
+            return Err(SDKError::InvalidTaskOccurrence);
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let occurrence_info = sqlx::query!(
+            r#"
+            INSERT INTO task_occurrences (recurrence_id, occurrence_at, status)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (recurrence_id, occurrence_at) DO UPDATE SET status = EXCLUDED.status
+            RETURNING *
+            "#,
+            recurrence_id,
+            occurrence_at,
+            TaskOccurrenceStatus::Skipped.to_string(),
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // the template stands for the first occurrence, skipping it would cancel the
+        // template itself
+        if occurrence_info.task_id == Some(recurrence.template_task_id) {
+            return Err(SDKError::InvalidTaskOccurrence);
+        }
+
+        // an already generated occurrence is kept for history but taken off the board
+        if let Some(task_id) = occurrence_info.task_id {
+            let task = sqlx::query!(
+                r#"
+                SELECT project_id, state_id, status FROM tasks WHERE id = $1
+                "#,
+                task_id,
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            // the workflow state follows the status like for any other update
+            let state = self
+                .resolve_task_state(
+                    task.project_id,
+                    task.state_id,
+                    task.status.and_then(|status| TaskStatus::from_str(&status).ok()),
+                    Some(TaskStatus::Canceled),
+                    None,
+                )
+                .await?;
+
+            sqlx::query!(
+                r#"
+                UPDATE tasks SET status = $1, state_id = COALESCE($2, state_id) WHERE id = $3
+                "#,
+                state
+                    .as_ref()
+                    .map(|(_, status)| *status)
+                    .unwrap_or(TaskStatus::Canceled)
+                    .to_string(),
+                state.as_ref().map(|(state, _)| state.id),
+                task_id,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(TaskOccurrence {
+            id: occurrence_info.id,
+            created_at: occurrence_info.created_at,
+            updated_at: occurrence_info.updated_at,
+            recurrence_id: occurrence_info.recurrence_id,
+            occurrence_at: occurrence_info.occurrence_at,
+            status: TaskOccurrenceStatus::from_str(&occurrence_info.status).unwrap_or_default(),
+            task_id: occurrence_info.task_id,
+        })
+    }
+
+    async fn edit_task_occurrence(
+        &self,
+        recurrence_id: Uuid,
+        occurrence_at: DateTime<Utc>,
+        input: UpdateTaskInput,
+    ) -> Result<Task, SDKError> {
+        let recurrence = self.get_task_recurrence(recurrence_id).await?;
+
+        if !is_occurrence_of(&recurrence, occurrence_at)? {
+            return Err(SDKError::InvalidTaskOccurrence);
+        }
+
+        // edits ahead of schedule materialize the occurrence first
+        self.materialize_occurrence(&recurrence, occurrence_at).await?;
+
+        let occurrence = sqlx::query!(
+            r#"
+            SELECT task_id, status FROM task_occurrences
+            WHERE recurrence_id = $1 AND occurrence_at = $2
+            "#,
+            recurrence_id,
+            occurrence_at,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        let (Some(task_id), false) = (
+            occurrence.task_id,
+            occurrence.status == TaskOccurrenceStatus::Skipped.to_string(),
+        ) else {
+            return Err(SDKError::InvalidTaskOccurrence);
+        };
+
+        // edits to the template go to every later occurrence, they are made on the task
+        if task_id == recurrence.template_task_id {
+            return Err(SDKError::InvalidTaskOccurrence);
+        }
+
+        let task = self.update_task(task_id, input).await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE task_occurrences SET status = $1
+            WHERE recurrence_id = $2 AND occurrence_at = $3
+            "#,
+            TaskOccurrenceStatus::Edited.to_string(),
+            recurrence_id,
+            occurrence_at,
+        )
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        Ok(task)
+    }
+
+    async fn generate_next_occurrence(&self, recurrence_id: Uuid) -> Result<Option<Task>, SDKError> {
+        let recurrence = self.get_task_recurrence(recurrence_id).await?;
+
+        if !recurrence.active {
+            return Ok(None);
+        }
+
+        let recorded = self.recorded_occurrences(recurrence.id).await?;
+        let after = recurrence
+            .last_occurrence_at
+            .unwrap_or(recurrence.dtstart - Duration::try_seconds(1).unwrap_or_default());
+
+        let next = occurrences_after(
+            &recurrence.rrule,
+            recurrence.dtstart,
+            after,
+            None,
+            recorded.len() as u16 + 1,
+        )?
+        .into_iter()
+        .find(|date| !recorded.contains(date));
+
+        match next {
+            Some(occurrence_at) => self.materialize_occurrence(&recurrence, occurrence_at).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn generate_due_occurrences(&self, now: DateTime<Utc>, lookahead: Duration) -> Result<Vec<Task>, SDKError> {
+        let recurrences = self.get_task_recurrences(None).await?;
+        let mut generated = Vec::new();
+
+        for recurrence in recurrences.into_iter().filter(|r| r.active) {
+            let recorded = self.recorded_occurrences(recurrence.id).await?;
+            let after = recurrence
+                .last_occurrence_at
+                .unwrap_or(recurrence.dtstart - Duration::try_seconds(1).unwrap_or_default());
+
+            let due = occurrences_after(
+                &recurrence.rrule,
+                recurrence.dtstart,
+                after,
+                Some(now + lookahead),
+                MAX_OCCURRENCES_PER_RUN,
+            )?;
+
+            for occurrence_at in due.into_iter().filter(|date| !recorded.contains(date)) {
+                if let Some(task) = self.materialize_occurrence(&recurrence, occurrence_at).await? {
+                    generated.push(task);
+                }
+            }
+        }
+
+        Ok(generated)
+    }
+
+    async fn generate_occurrence_after_completion(&self, task_id: Uuid) -> Result<Option<Task>, SDKError> {
+        let recurrence = sqlx::query!(
+            r#"
+            SELECT task_recurrences.id FROM task_recurrences
+            LEFT JOIN task_occurrences ON task_occurrences.recurrence_id = task_recurrences.id
+            WHERE task_recurrences.active
+                AND (task_recurrences.template_task_id = $1 OR task_occurrences.task_id = $1)
+            LIMIT 1
+            "#,
+            task_id,
+        )
+        .fetch_optional(self.db_pool.as_ref())
+        .await?;
+
+        let Some(recurrence) = recurrence else {
+            return Ok(None);
+        };
+
+        // don't stack occurrences if the schedule already produced an open one
+        let pending = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM task_occurrences
+                INNER JOIN tasks ON tasks.id = task_occurrences.task_id
+                WHERE task_occurrences.recurrence_id = $1
+                    AND task_occurrences.task_id <> $2
+                    AND task_occurrences.status <> 'Skipped'
+                    AND COALESCE(tasks.status, '') NOT IN ('Done', 'Canceled')
+                    AND tasks.deleted_at IS NULL
+            ) AS "exists!"
+            "#,
+            recurrence.id,
+            task_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        if pending.exists {
+            return Ok(None);
+        }
+
+        self.generate_next_occurrence(recurrence.id).await
+    }
+}
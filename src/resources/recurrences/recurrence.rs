@@ -0,0 +1,50 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskRecurrence")]
+pub struct TaskRecurrence {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub template_task_id: Uuid,
+
+    pub rrule: String,
+    pub dtstart: DateTime<Utc>,
+    pub active: bool,
+
+    pub last_occurrence_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskOccurrence")]
+pub struct TaskOccurrence {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub recurrence_id: Uuid,
+    pub occurrence_at: DateTime<Utc>,
+    pub status: TaskOccurrenceStatus,
+
+    pub task_id: Option<Uuid>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum TaskOccurrenceStatus {
+    #[default]
+    Generated,
+    Skipped,
+    Edited,
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        clock::Clock,
+        runner::{spawn_periodic, RunnerHandle},
+    },
+    errors::sdk::SDKError,
+    resources::tasks::task::Task,
+};
+
+use super::operations::TaskRecurrenceOperations;
+
+#[derive(Clone)]
+pub struct RecurrenceRunner {
+    engine: Arc<SDKEngine>,
+    clock: Arc<dyn Clock>,
+    lookahead: Duration,
+}
+
+impl RecurrenceRunner {
+    pub fn new(engine: Arc<SDKEngine>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            engine,
+            clock,
+            lookahead: Duration::zero(),
+        }
+    }
+
+    pub fn with_lookahead(mut self, lookahead: Duration) -> Self {
+        self.lookahead = lookahead;
+        self
+    }
+
+    pub async fn run_once(&self) -> Result<Vec<Task>, SDKError> {
+        self.engine
+            .generate_due_occurrences(self.clock.now(), self.lookahead)
+            .await
+    }
+
+    pub fn spawn(self, interval: std::time::Duration) -> RunnerHandle {
+        spawn_periodic(interval, move || {
+            let runner = self.clone();
+
+            async move {
+                if let Err(err) = runner.run_once().await {
+                    println!("Error generating task occurrences: {:?}", err);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,3 @@
+pub mod operations;
+pub mod reminder;
+pub mod scheduler;
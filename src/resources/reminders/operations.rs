@@ -0,0 +1,224 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{
+        notifications::{
+            dispatch::{task_label, NotificationDraft},
+            notification::NotificationKind,
+        },
+        tasks::{operations::TaskCrudOperations, task::TaskPriority},
+    },
+};
+
+use super::reminder::{ReminderConfig, ReminderKind, TaskReminder};
+
+#[async_trait]
+pub trait TaskReminderOperations {
+    async fn run_task_reminders(
+        &self,
+        now: DateTime<Utc>,
+        config: &ReminderConfig,
+    ) -> Result<Vec<TaskReminder>, SDKError>;
+    async fn get_task_reminders(&self, task_id: Uuid) -> Result<Vec<TaskReminder>, SDKError>;
+}
+
+fn next_priority(priority: TaskPriority) -> TaskPriority {
+    match priority {
+        TaskPriority::None => TaskPriority::Low,
+        TaskPriority::Low => TaskPriority::Medium,
+        TaskPriority::Medium => TaskPriority::High,
+        TaskPriority::High | TaskPriority::Urgent => TaskPriority::Urgent,
+    }
+}
+
+#[async_trait]
+impl TaskReminderOperations for SDKEngine {
+    // Each reminder kind fires once per task and due date, so running this on every
+    // tick of the scheduler only reports what is new.
+    async fn run_task_reminders(
+        &self,
+        now: DateTime<Utc>,
+        config: &ReminderConfig,
+    ) -> Result<Vec<TaskReminder>, SDKError> {
+        let candidates = sqlx::query!(
+            r#"
+            SELECT
+                tasks.id,
+                tasks.owner_id,
+                tasks.lead_id,
+                tasks.priority,
+                tasks.due_date AS "due_date!",
+                projects.lead_id AS "project_lead_id?",
+                array_remove(array_agg(tasks_by_assignees.assignee_id), NULL) AS "assignee_ids!"
+            FROM tasks
+            LEFT JOIN projects ON projects.id = tasks.project_id
+            LEFT JOIN tasks_by_assignees ON tasks_by_assignees.task_id = tasks.id
+            WHERE tasks.due_date IS NOT NULL
+                AND tasks.due_date < $1
+                AND tasks.deleted_at IS NULL
+                AND tasks.archived_at IS NULL
+                AND COALESCE(tasks.status, '') NOT IN ('Done', 'Canceled')
+            GROUP BY tasks.id, projects.lead_id
+            ORDER BY tasks.due_date
+            "#,
+            now + config.due_soon_window,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let mut reminders = Vec::new();
+
+        for candidate in candidates {
+            let mut kinds = Vec::new();
+
+            if candidate.due_date >= now {
+                kinds.push(ReminderKind::DueSoon);
+            } else {
+                kinds.push(ReminderKind::Overdue);
+
+                if config
+                    .escalate_after
+                    .is_some_and(|escalate_after| candidate.due_date + escalate_after <= now)
+                {
+                    kinds.push(ReminderKind::Escalated);
+                }
+            }
+
+            // assignees and the task lead hear about it, the owner when nobody else would
+            let mut recipients = candidate.assignee_ids.clone();
+
+            if let Some(lead_id) = candidate.lead_id {
+                recipients.push(lead_id);
+            }
+
+            if recipients.is_empty() {
+                recipients.push(candidate.owner_id);
+            }
+
+            for kind in kinds {
+                let mut recipients = recipients.clone();
+
+                if kind == ReminderKind::Escalated && config.notify_project_lead {
+                    if let Some(project_lead_id) = candidate.project_lead_id {
+                        recipients.push(project_lead_id);
+                    }
+                }
+
+                recipients.sort();
+                recipients.dedup();
+
+                // the reminder and the bump land together, a retry would otherwise skip the bump
+                let mut tx = self.db_pool.begin().await?;
+
+                let reminder_info = sqlx::query!(
+                    r#"
+                    INSERT INTO task_reminders (task_id, kind, due_date, recipient_ids)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (task_id, kind, due_date) DO NOTHING
+                    RETURNING *
+                    "#,
+                    candidate.id,
+                    kind.to_string(),
+                    candidate.due_date,
+                    &recipients,
+                )
+                .fetch_optional(&mut *tx)
+                .await?;
+
+                let Some(reminder_info) = reminder_info else {
+                    continue;
+                };
+
+                if kind == ReminderKind::Escalated && config.bump_priority {
+                    let priority = candidate
+                        .priority
+                        .as_deref()
+                        .and_then(|priority| TaskPriority::from_str(priority).ok())
+                        .unwrap_or_default();
+
+                    sqlx::query!(
+                        r#"
+                        UPDATE tasks SET priority = $1 WHERE id = $2
+                        "#,
+                        next_priority(priority).to_string(),
+                        candidate.id,
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+
+                tx.commit().await?;
+
+                // the reminder is recorded, a failed notification is logged and doesn't fail the run
+                let notified = async {
+                    let task = self.get_task(candidate.id).await?;
+                    let label = task_label(&task);
+
+                    let (notification_kind, title) = match kind {
+                        ReminderKind::DueSoon => (NotificationKind::DueSoon, format!("{} is due soon", label)),
+                        ReminderKind::Overdue => (NotificationKind::Overdue, format!("{} is overdue", label)),
+                        ReminderKind::Escalated => (
+                            NotificationKind::Escalated,
+                            format!("{} is still overdue and was escalated", label),
+                        ),
+                    };
+
+                    self.notify(NotificationDraft {
+                        task_id: Some(task.id),
+                        project_id: task.project_id,
+                        recipients: recipients.clone(),
+                        ..NotificationDraft::new(notification_kind, title)
+                    })
+                    .await
+                }
+                .await;
+
+                if let Err(err) = notified {
+                    println!("Error notifying about reminder {}: {:?}", reminder_info.id, err);
+                }
+
+                reminders.push(TaskReminder {
+                    id: reminder_info.id,
+                    created_at: reminder_info.created_at,
+                    task_id: reminder_info.task_id,
+                    kind,
+                    due_date: reminder_info.due_date,
+                    recipient_ids: reminder_info.recipient_ids,
+                });
+            }
+        }
+
+        Ok(reminders)
+    }
+
+    async fn get_task_reminders(&self, task_id: Uuid) -> Result<Vec<TaskReminder>, SDKError> {
+        let reminders_info = sqlx::query!(
+            r#"
+            SELECT * FROM task_reminders
+            WHERE task_id = $1
+            ORDER BY created_at
+            "#,
+            task_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(reminders_info
+            .into_iter()
+            .map(|reminder_info| TaskReminder {
+                id: reminder_info.id,
+                created_at: reminder_info.created_at,
+                task_id: reminder_info.task_id,
+                kind: ReminderKind::from_str(&reminder_info.kind).unwrap_or_default(),
+                due_date: reminder_info.due_date,
+                recipient_ids: reminder_info.recipient_ids,
+            })
+            .collect())
+    }
+}
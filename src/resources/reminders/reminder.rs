@@ -0,0 +1,53 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Duration, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskReminder")]
+pub struct TaskReminder {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub task_id: Uuid,
+    pub kind: ReminderKind,
+    pub due_date: DateTime<Utc>,
+
+    pub recipient_ids: Vec<Uuid>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum ReminderKind {
+    #[default]
+    DueSoon,
+    Overdue,
+    Escalated,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReminderConfig {
+    // tasks due within this window get a due soon reminder
+    pub due_soon_window: Duration,
+    // escalation is off when None
+    pub escalate_after: Option<Duration>,
+    pub bump_priority: bool,
+    pub notify_project_lead: bool,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        Self {
+            due_soon_window: Duration::try_days(1).unwrap_or_default(),
+            escalate_after: Duration::try_days(3),
+            bump_priority: true,
+            notify_project_lead: true,
+        }
+    }
+}
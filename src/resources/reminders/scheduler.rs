@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        clock::Clock,
+        runner::{spawn_periodic, RunnerHandle},
+    },
+    errors::sdk::SDKError,
+};
+
+use super::{
+    operations::TaskReminderOperations,
+    reminder::{ReminderConfig, TaskReminder},
+};
+
+#[derive(Clone)]
+pub struct ReminderScheduler {
+    engine: Arc<SDKEngine>,
+    clock: Arc<dyn Clock>,
+    config: ReminderConfig,
+}
+
+impl ReminderScheduler {
+    pub fn new(engine: Arc<SDKEngine>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            engine,
+            clock,
+            config: ReminderConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: ReminderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub async fn run_once(&self) -> Result<Vec<TaskReminder>, SDKError> {
+        self.engine.run_task_reminders(self.clock.now(), &self.config).await
+    }
+
+    pub fn spawn(self, interval: std::time::Duration) -> RunnerHandle {
+        spawn_periodic(interval, move || {
+            let scheduler = self.clone();
+
+            async move {
+                if let Err(err) = scheduler.run_once().await {
+                    println!("Error sending task reminders: {:?}", err);
+                }
+            }
+        })
+    }
+}
+
+impl SDKEngine {
+    pub fn spawn_reminder_scheduler(
+        self: &Arc<Self>,
+        clock: Arc<dyn Clock>,
+        config: ReminderConfig,
+        interval: std::time::Duration,
+    ) -> RunnerHandle {
+        ReminderScheduler::new(self.clone(), clock)
+            .with_config(config)
+            .spawn(interval)
+    }
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::{operations::TaskCrudOperations, task::Task};
+
+#[async_trait]
+pub trait TaskArchiveOperations {
+    async fn archive_task(&self, id: Uuid) -> Result<Task, SDKError>;
+    async fn unarchive_task(&self, id: Uuid) -> Result<Task, SDKError>;
+
+    async fn archive_completed_tasks(&self, completed_before: DateTime<Utc>) -> Result<i64, SDKError>;
+}
+
+#[async_trait]
+impl TaskArchiveOperations for SDKEngine {
+    async fn archive_task(&self, id: Uuid) -> Result<Task, SDKError> {
+        sqlx::query!(
+            r#"
+            UPDATE tasks SET archived_at = COALESCE(archived_at, now())
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        self.get_task(id).await
+    }
+
+    async fn unarchive_task(&self, id: Uuid) -> Result<Task, SDKError> {
+        sqlx::query!(
+            r#"
+            UPDATE tasks SET archived_at = NULL
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        self.get_task(id).await
+    }
+
+    // Archives the Done and Canceled tasks completed before the given date, a task
+    // counts as completed at its last status change. Returns how many were archived.
+    async fn archive_completed_tasks(&self, completed_before: DateTime<Utc>) -> Result<i64, SDKError> {
+        let archived = sqlx::query!(
+            r#"
+            UPDATE tasks SET archived_at = now()
+            WHERE status IN ('Done', 'Canceled')
+                AND archived_at IS NULL
+                AND deleted_at IS NULL
+                AND COALESCE(
+                    (SELECT max(transitioned_at) FROM task_status_transitions WHERE task_id = tasks.id),
+                    updated_at
+                ) < $1
+            "#,
+            completed_before,
+        )
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        Ok(archived.rows_affected() as i64)
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        clock::Clock,
+        runner::{spawn_periodic, RunnerHandle},
+    },
+    errors::sdk::SDKError,
+};
+
+use super::archive::TaskArchiveOperations;
+
+const DEFAULT_ARCHIVE_AFTER_DAYS: i64 = 14;
+
+#[derive(Clone)]
+pub struct TaskArchiver {
+    engine: Arc<SDKEngine>,
+    clock: Arc<dyn Clock>,
+    archive_after: Duration,
+}
+
+impl TaskArchiver {
+    pub fn new(engine: Arc<SDKEngine>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            engine,
+            clock,
+            archive_after: Duration::try_days(DEFAULT_ARCHIVE_AFTER_DAYS).unwrap_or_default(),
+        }
+    }
+
+    pub fn with_archive_after(mut self, archive_after: Duration) -> Self {
+        self.archive_after = archive_after;
+        self
+    }
+
+    pub async fn run_once(&self) -> Result<i64, SDKError> {
+        self.engine
+            .archive_completed_tasks(self.clock.now() - self.archive_after)
+            .await
+    }
+
+    pub fn spawn(self, interval: std::time::Duration) -> RunnerHandle {
+        spawn_periodic(interval, move || {
+            let archiver = self.clone();
+
+            async move {
+                if let Err(err) = archiver.run_once().await {
+                    println!("Error archiving completed tasks: {:?}", err);
+                }
+            }
+        })
+    }
+}
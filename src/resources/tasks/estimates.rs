@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::projects::{operations::ProjectCrudOperations, project::EstimateScale},
+};
+
+use super::task::EstimateRollup;
+
+#[async_trait]
+pub trait TaskEstimateOperations {
+    async fn get_task_estimate_rollup(&self, task_id: Uuid) -> Result<EstimateRollup, SDKError>;
+    async fn get_project_estimate_rollup(&self, project_id: Uuid) -> Result<EstimateRollup, SDKError>;
+    async fn get_milestone_estimate_rollup(&self, milestone_id: Uuid) -> Result<EstimateRollup, SDKError>;
+    async fn get_cycle_estimate_rollup(&self, cycle_id: Uuid) -> Result<EstimateRollup, SDKError>;
+}
+
+impl SDKEngine {
+    // Tasks without a project take any non negative estimate.
+    pub(crate) async fn validate_task_estimate(
+        &self,
+        project_id: Option<Uuid>,
+        estimate: Option<f64>,
+    ) -> Result<(), SDKError> {
+        let Some(estimate) = estimate else {
+            return Ok(());
+        };
+
+        let scale = match project_id.filter(|project_id| !project_id.is_nil()) {
+            Some(project_id) => self.get_project(project_id).await?.estimate_scale,
+            None => EstimateScale::default(),
+        };
+
+        scale.validate(estimate).map_err(SDKError::InvalidEstimate)
+    }
+
+    // Rolls up the tasks matched by `roots` together with all their subtasks,
+    // `roots` is a condition over tasks bound to $1.
+    async fn load_estimate_rollup(&self, roots: &str, id: Uuid) -> Result<EstimateRollup, SDKError> {
+        let rollup = sqlx::query(
+            format!(
+                r#"
+                WITH RECURSIVE tree AS (
+                    SELECT id, parent_id, status, estimate FROM tasks
+                    WHERE {} AND COALESCE(status, '') <> 'Canceled' AND deleted_at IS NULL
+                    UNION
+                    SELECT tasks.id, tasks.parent_id, tasks.status, tasks.estimate FROM tasks
+                    JOIN tree ON tasks.parent_id = tree.id
+                    WHERE COALESCE(tasks.status, '') <> 'Canceled' AND tasks.deleted_at IS NULL
+                ), descendants AS (
+                    SELECT tree.id AS ancestor_id, child.id, child.estimate FROM tree
+                    JOIN tree AS child ON child.parent_id = tree.id
+                    UNION
+                    SELECT descendants.ancestor_id, child.id, child.estimate FROM descendants
+                    JOIN tree AS child ON child.parent_id = descendants.id
+                ), leaves AS (
+                    SELECT tree.*,
+                        EXISTS (
+                            SELECT 1 FROM descendants
+                            WHERE descendants.ancestor_id = tree.id AND descendants.estimate IS NOT NULL
+                        ) AS has_estimated_descendant,
+                        EXISTS (
+                            SELECT 1 FROM descendants
+                            JOIN tree AS ancestor ON ancestor.id = descendants.ancestor_id
+                            WHERE descendants.id = tree.id AND ancestor.estimate IS NOT NULL
+                        ) AS has_estimated_ancestor,
+                        EXISTS (SELECT 1 FROM tree AS child WHERE child.parent_id = tree.id) AS has_subtasks
+                    FROM tree
+                )
+                SELECT
+                    COALESCE(sum(estimate) FILTER (WHERE estimate IS NOT NULL AND NOT has_estimated_descendant), 0)::float8 AS total,
+                    COALESCE(sum(estimate) FILTER (WHERE estimate IS NOT NULL AND NOT has_estimated_descendant AND status = 'Done'), 0)::float8 AS completed,
+                    count(*) FILTER (WHERE estimate IS NOT NULL AND NOT has_estimated_descendant) AS estimated_tasks,
+                    count(*) FILTER (WHERE estimate IS NULL AND NOT has_subtasks AND NOT has_estimated_ancestor) AS unestimated_tasks
+                FROM leaves
+                "#,
+                roots
+            )
+            .as_str(),
+        )
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        let total: f64 = rollup.get("total");
+        let completed: f64 = rollup.get("completed");
+
+        Ok(EstimateRollup {
+            total,
+            completed,
+            remaining: total - completed,
+            estimated_tasks: rollup.get("estimated_tasks"),
+            unestimated_tasks: rollup.get("unestimated_tasks"),
+        })
+    }
+}
+
+#[async_trait]
+impl TaskEstimateOperations for SDKEngine {
+    async fn get_task_estimate_rollup(&self, task_id: Uuid) -> Result<EstimateRollup, SDKError> {
+        self.load_estimate_rollup("id = $1", task_id).await
+    }
+
+    async fn get_project_estimate_rollup(&self, project_id: Uuid) -> Result<EstimateRollup, SDKError> {
+        self.load_estimate_rollup("project_id = $1", project_id).await
+    }
+
+    async fn get_milestone_estimate_rollup(&self, milestone_id: Uuid) -> Result<EstimateRollup, SDKError> {
+        self.load_estimate_rollup("milestone_id = $1", milestone_id).await
+    }
+
+    async fn get_cycle_estimate_rollup(&self, cycle_id: Uuid) -> Result<EstimateRollup, SDKError> {
+        self.load_estimate_rollup("cycle_id = $1", cycle_id).await
+    }
+}
@@ -4,10 +4,10 @@ use async_graphql::InputObject;
 use async_trait::async_trait;
 use derive_builder::Builder;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{Postgres, Row, Transaction};
 use uuid::Uuid;
 
-use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError, resources::workflows::workflow::WorkflowState};
 
 use super::{
     operations::{CreateTaskInput, TaskCrudOperations},
@@ -23,27 +23,36 @@ pub struct CreateTasksInput {
 #[async_trait]
 pub trait TasksExtensionOperations {
     async fn create_tasks(&self, input: CreateTasksInput) -> Result<Vec<Task>, SDKError>;
+    async fn get_task_by_identifier(&self, identifier: String) -> Result<Task, SDKError>;
 }
 
-#[async_trait]
-impl TasksExtensionOperations for SDKEngine {
-    async fn create_tasks(&self, input: CreateTasksInput) -> Result<Vec<Task>, SDKError> {
-        let mut tx = self.db_pool.begin().await?;
-        // let saved_input = input.clone();
-
+impl SDKEngine {
+    // Inserts the tasks with their labels and assignees, subtasks and
+    // notifications are left to the caller.
+    pub(crate) async fn create_tasks_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        input: &CreateTasksInput,
+        states: &[Option<(WorkflowState, TaskStatus)>],
+    ) -> Result<Vec<Task>, SDKError> {
         let values = input
             .tasks
             .iter()
-            .map(|task| {
+            .zip(states.iter())
+            .map(|(task, state)| {
                 format!(
-                    "('{}', '{}', {}, '{}', '{}', {}, {}, {}, {})",
-                    task.title,
+                    "('{}', '{}', {}, '{}', '{}', {}, {}, {}, {}, {}, {}, {}, {}, '{}')",
+                    task.title.replace('\'', "''"),
                     task.owner_id,
                     task.description
                         .clone()
-                        .map(|d| format!("'{}'", d))
+                        .map(|d| format!("'{}'", d.replace('\'', "''")))
                         .unwrap_or("null".to_string()),
-                    task.status.unwrap_or_default(),
+                    state
+                        .as_ref()
+                        .map(|(_, status)| *status)
+                        .or(task.status)
+                        .unwrap_or_default(),
                     task.priority.unwrap_or_default(),
                     task.due_date
                         .map(|dd| format!("'{}'", dd))
@@ -53,22 +62,31 @@ impl TasksExtensionOperations for SDKEngine {
                         .unwrap_or("null".to_string()),
                     task.lead_id.map(|l| format!("'{}'", l)).unwrap_or("null".to_string()),
                     task.parent_id.map(|p| format!("'{}'", p)).unwrap_or("null".to_string()),
+                    state
+                        .as_ref()
+                        .map(|(state, _)| format!("'{}'", state.id))
+                        .unwrap_or("null".to_string()),
+                    task.cycle_id.map(|c| format!("'{}'", c)).unwrap_or("null".to_string()),
+                    task.milestone_id
+                        .map(|m| format!("'{}'", m))
+                        .unwrap_or("null".to_string()),
+                    task.estimate.map(|e| e.to_string()).unwrap_or("null".to_string()),
+                    task.owner_id,
                 )
             })
             .collect::<Vec<String>>();
 
         let query = format!(
-            "INSERT INTO tasks (title, owner_id, description, status, priority, due_date, project_id, lead_id, parent_id) VALUES {} RETURNING *",
+            "INSERT INTO tasks (title, owner_id, description, status, priority, due_date, project_id, lead_id, parent_id, state_id, cycle_id, milestone_id, estimate, last_actor_id) VALUES {} RETURNING *",
             values.join(", ")
         );
 
-        let tasks = sqlx::query(query.as_str()).fetch_all(&mut *tx).await?;
+        let tasks = sqlx::query(query.as_str()).fetch_all(&mut **tx).await?;
 
         for (i, input_task) in input.tasks.iter().enumerate() {
             let task = &tasks[i];
 
             let task_id = task.get::<Uuid, _>("id");
-            let task_owner_id = task.get::<Uuid, _>("owner_id");
 
             if let Some(labels) = input_task.labels.clone() {
                 for label in labels {
@@ -80,7 +98,7 @@ impl TasksExtensionOperations for SDKEngine {
                         task_id,
                         label,
                     )
-                    .execute(&mut *tx)
+                    .execute(&mut **tx)
                     .await?;
                 }
             }
@@ -95,26 +113,12 @@ impl TasksExtensionOperations for SDKEngine {
                         task_id,
                         assignee,
                     )
-                    .execute(&mut *tx)
+                    .execute(&mut **tx)
                     .await?;
                 }
             }
-
-            if let Some(subtasks) = input_task.subtasks.clone() {
-                for mut subtask in subtasks {
-                    subtask.owner_id = task_owner_id;
-
-                    if subtask.parent_id.is_none() {
-                        subtask.parent_id = Some(task_id);
-                    }
-
-                    self.create_task(subtask).await?;
-                }
-            }
         }
 
-        tx.commit().await?;
-
         let tasks: Vec<Task> = tasks
             .iter()
             .map(|task_info| Task {
@@ -137,9 +141,63 @@ impl TasksExtensionOperations for SDKEngine {
                 owner_id: task_info.get("owner_id"),
                 count: task_info.get("count"),
                 parent_id: task_info.get("parent_id"),
+                state_id: task_info.get("state_id"),
+                cycle_id: task_info.get("cycle_id"),
+                milestone_id: task_info.get("milestone_id"),
+                estimate: task_info.get("estimate"),
+                number: task_info.get("number"),
+                identifier: task_info.get("identifier"),
+                rank: task_info.get("rank"),
+                archived_at: task_info.get("archived_at"),
             })
             .collect();
 
+        Ok(tasks)
+    }
+}
+
+#[async_trait]
+impl TasksExtensionOperations for SDKEngine {
+    async fn create_tasks(&self, input: CreateTasksInput) -> Result<Vec<Task>, SDKError> {
+        let mut states = Vec::with_capacity(input.tasks.len());
+
+        for task in input.tasks.iter() {
+            states.push(
+                self.resolve_task_state(task.project_id, None, None, task.status, task.state_id)
+                    .await?,
+            );
+
+            self.validate_task_cycle(task.project_id, task.cycle_id).await?;
+            self.validate_task_milestone(task.project_id, task.milestone_id).await?;
+            self.validate_task_estimate(task.project_id, task.estimate).await?;
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+        // let saved_input = input.clone();
+
+        let tasks = self.create_tasks_in_tx(&mut tx, &input, &states).await?;
+
+        for (task, input_task) in tasks.iter().zip(input.tasks.iter()) {
+            if let Some(subtasks) = input_task.subtasks.clone() {
+                for mut subtask in subtasks {
+                    subtask.owner_id = task.owner_id;
+
+                    if subtask.parent_id.is_none() {
+                        subtask.parent_id = Some(task.id);
+                    }
+
+                    self.create_task(subtask).await?;
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        for (task, input_task) in tasks.iter().zip(input.tasks.iter()) {
+            self.notify_task_created(task, &input_task.assignees.clone().unwrap_or_default())
+                .await;
+        }
+
         // if self.config.with_changes_registration {
         //     let tasks = tasks.clone();
         //     let engine = self.clone();
@@ -173,4 +231,28 @@ impl TasksExtensionOperations for SDKEngine {
 
         Ok(tasks)
     }
+    async fn get_task_by_identifier(&self, identifier: String) -> Result<Task, SDKError> {
+        let identifier = identifier.trim().to_uppercase();
+
+        // identifiers are unique, the current ones win over redirects left behind
+        // by moved tasks
+        let task_id = sqlx::query!(
+            r#"
+            SELECT id AS "id!" FROM (
+                SELECT id, 0 AS precedence FROM tasks WHERE identifier = $1
+                UNION ALL
+                SELECT task_id AS id, 1 AS precedence FROM task_identifier_redirects WHERE identifier = $1
+            ) AS matches
+            ORDER BY precedence
+            LIMIT 1
+            "#,
+            identifier,
+        )
+        .fetch_optional(self.db_pool.as_ref())
+        .await?
+        .map(|r| r.id)
+        .ok_or(SDKError::ResourceNotFound)?;
+
+        self.get_task(task_id).await
+    }
 }
@@ -24,7 +24,7 @@ impl Loader<Uuid> for TaskLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let tasks = sqlx::query!(
             r#"
-            SELECT * FROM tasks WHERE id  = ANY($1)
+            SELECT * FROM tasks WHERE id  = ANY($1) AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -60,6 +60,14 @@ impl Loader<Uuid> for TaskLoader {
                         owner_id: task.owner_id,
                         count: task.count,
                         parent_id: task.parent_id,
+                        state_id: task.state_id,
+                        cycle_id: task.cycle_id,
+                        milestone_id: task.milestone_id,
+                        estimate: task.estimate,
+                        number: task.number,
+                        identifier: task.identifier.clone(),
+                        rank: task.rank.clone(),
+                        archived_at: task.archived_at,
                     },
                 )
             })
@@ -1,6 +1,11 @@
+pub mod archive;
+pub mod archiver;
+pub mod estimates;
 pub mod extensions;
 pub mod loader;
 pub mod operations;
+pub mod ranks;
 pub mod relations;
 pub mod task;
+pub mod transfers;
 pub mod watchers;
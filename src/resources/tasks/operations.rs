@@ -6,7 +6,7 @@ use chrono::{DateTime, Utc};
 
 use derive_builder::Builder;
 use poem_openapi::Object;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 // use serde_json::json;
 use sqlx::Row;
 // use tokio::task;
@@ -15,6 +15,11 @@ use uuid::Uuid;
 use crate::backend::engine::SDKEngine;
 use crate::common::commons::{SortOrder, UpdateListInput};
 use crate::errors::sdk::SDKError;
+use crate::resources::custom_fields::operations::CustomFieldFilter;
+use crate::resources::dependencies::operations::TaskDependencyCrudOperations;
+use crate::resources::notifications::dispatch::{task_label, NotificationDraft};
+use crate::resources::notifications::notification::{NotificationKind, WatchReason};
+use crate::resources::recurrences::operations::TaskRecurrenceOperations;
 // use crate::resources::changes::change::{ChangeOperation, ChangeResourceType};
 // use crate::resources::changes::operations::{ChangeCrudOperations, CreateChangeInputBuilder};
 use crate::resources::tasks::task::{Task, TaskPriority, TaskStatus};
@@ -34,11 +39,17 @@ pub struct GetTasksInput {
     #[builder(setter(strip_option), default)]
     pub filter: Option<GetTasksWhere>,
 
+    // a column of tasks, `rank` gives the manual order within a project and status
     #[builder(setter(strip_option), default)]
     pub sort_by: Option<String>,
     #[builder(setter(strip_option), default)]
+    pub sort_by_custom_field: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
     pub sort_order: Option<SortOrder>,
 
+    #[builder(setter(strip_option), default)]
+    pub include_archived: Option<bool>,
+
     #[builder(setter(into, strip_option), default = "Some(100)")]
     pub limit: Option<i32>,
     #[builder(setter(into, strip_option), default = "Some(0)")]
@@ -68,6 +79,14 @@ pub struct CreateTaskInput {
     pub lead_id: Option<Uuid>,
     #[builder(setter(strip_option), default)]
     pub parent_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub state_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub cycle_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub milestone_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub estimate: Option<f64>,
 
     #[builder(setter(strip_option), default)]
     pub labels: Option<Vec<Uuid>>,
@@ -100,6 +119,15 @@ pub struct UpdateTaskInput {
     pub lead_id: Option<Uuid>,
     #[builder(setter(strip_option), default)]
     pub parent_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub state_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub cycle_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub milestone_id: Option<Uuid>,
+    // a negative estimate clears it
+    #[builder(setter(strip_option), default)]
+    pub estimate: Option<f64>,
 
     #[builder(setter(strip_option), default)]
     pub labels: Option<UpdateListInput>,
@@ -107,9 +135,19 @@ pub struct UpdateTaskInput {
     pub assignees: Option<UpdateListInput>,
     #[builder(setter(strip_option), default)]
     pub assets: Option<UpdateListInput>,
+
+    // member making the change, left out of the notifications it triggers
+    #[graphql(skip)]
+    #[builder(setter(strip_option), default)]
+    pub actor_id: Option<Uuid>,
+    // automation rule making the change, its events continue the rule's chain
+    #[graphql(skip)]
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub automation_rule_id: Option<Uuid>,
 }
 
-#[derive(Default, Builder, Object, InputObject)]
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize, Deserialize)]
 #[builder(pattern = "owned")]
 pub struct GetTasksWhere {
     #[builder(setter(strip_option), default)]
@@ -132,6 +170,14 @@ pub struct GetTasksWhere {
     pub lead_id: Option<Uuid>,
     #[builder(setter(strip_option), default)]
     pub parent_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub state_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub cycle_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub milestone_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub custom_fields: Option<Vec<CustomFieldFilter>>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -192,6 +238,24 @@ impl GetTasksWhere {
             conditions.push(format!("parent_id = '{}'", parent_id));
         }
 
+        if let Some(state_id) = &self.state_id {
+            conditions.push(format!("state_id = '{}'", state_id));
+        }
+
+        if let Some(cycle_id) = &self.cycle_id {
+            conditions.push(format!("cycle_id = '{}'", cycle_id));
+        }
+
+        if let Some(milestone_id) = &self.milestone_id {
+            conditions.push(format!("milestone_id = '{}'", milestone_id));
+        }
+
+        if let Some(custom_fields) = &self.custom_fields {
+            for custom_field in custom_fields {
+                conditions.push(custom_field.compile_sql());
+            }
+        }
+
         if let Some(ands) = &self._and {
             let and_conditions: Vec<String> = ands.iter().map(|and| and.compile_sql()).collect();
             conditions.push(format!("({})", and_conditions.join(" AND ")));
@@ -209,24 +273,44 @@ impl GetTasksWhere {
 #[async_trait]
 impl TaskCrudOperations for SDKEngine {
     async fn create_task(&self, input: CreateTaskInput) -> Result<Task, SDKError> {
+        let state = self
+            .resolve_task_state(input.project_id, None, None, input.status, input.state_id)
+            .await?;
+
+        self.validate_task_cycle(input.project_id, input.cycle_id).await?;
+        self.validate_task_milestone(input.project_id, input.milestone_id)
+            .await?;
+        self.validate_task_estimate(input.project_id, input.estimate).await?;
+
+        let assignees = input.assignees.clone().unwrap_or_default();
+
         let mut tx = self.db_pool.begin().await?;
         // let saved_input = input.clone();
 
         let task = sqlx::query!(
             r#"
-            INSERT INTO tasks (title, description, owner_id, status, priority, due_date, project_id, lead_id, parent_id)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO tasks (title, description, owner_id, status, priority, due_date, project_id, lead_id, parent_id, state_id, cycle_id, milestone_id, estimate, last_actor_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $3)
             RETURNING *
             "#,
             input.title,
             input.description,
             input.owner_id,
-            input.status.unwrap_or_default().to_string(),
+            state
+                .as_ref()
+                .map(|(_, status)| *status)
+                .or(input.status)
+                .unwrap_or_default()
+                .to_string(),
             input.priority.unwrap_or_default().to_string(),
             input.due_date,
             input.project_id,
             input.lead_id,
             input.parent_id,
+            state.map(|(state, _)| state.id),
+            input.cycle_id,
+            input.milestone_id,
+            input.estimate,
         )
         .fetch_one(&mut *tx)
         .await?;
@@ -308,6 +392,14 @@ impl TaskCrudOperations for SDKEngine {
             owner_id: task.owner_id,
             count: task.count,
             parent_id: task.parent_id,
+            state_id: task.state_id,
+            cycle_id: task.cycle_id,
+            milestone_id: task.milestone_id,
+            estimate: task.estimate,
+            number: task.number,
+            identifier: task.identifier,
+            rank: task.rank,
+            archived_at: task.archived_at,
         };
 
         // if self.config.with_changes_registration {
@@ -340,13 +432,15 @@ impl TaskCrudOperations for SDKEngine {
         //     });
         // }
 
+        self.notify_task_created(&task, &assignees).await;
+
         Ok(task)
     }
 
     async fn get_task(&self, id: Uuid) -> Result<Task, SDKError> {
         let task_info = sqlx::query!(
             r#"
-            SELECT * FROM tasks WHERE id = $1
+            SELECT * FROM tasks WHERE id = $1 AND deleted_at IS NULL
             "#,
             id,
         )
@@ -373,12 +467,108 @@ impl TaskCrudOperations for SDKEngine {
             owner_id: task_info.owner_id,
             count: task_info.count,
             parent_id: task_info.parent_id,
+            state_id: task_info.state_id,
+            cycle_id: task_info.cycle_id,
+            milestone_id: task_info.milestone_id,
+            estimate: task_info.estimate,
+            number: task_info.number,
+            identifier: task_info.identifier,
+            rank: task_info.rank,
+            archived_at: task_info.archived_at,
         };
 
         Ok(task)
     }
 
     async fn update_task(&self, id: Uuid, input: UpdateTaskInput) -> Result<Task, SDKError> {
+        let mut status = input.status;
+        let mut previous_status = None;
+
+        // only touch the workflow state when something that decides it changes
+        let state = if input.status.is_some() || input.state_id.is_some() || input.project_id.is_some() {
+            let current = sqlx::query!(
+                r#"
+                SELECT project_id, state_id, status FROM tasks WHERE id = $1 AND deleted_at IS NULL
+                "#,
+                id,
+            )
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+            let project_id = match input.project_id {
+                Some(project_id) if project_id.is_nil() => None,
+                Some(project_id) => Some(project_id),
+                None => current.project_id,
+            };
+
+            let current_status = current.status.and_then(|a| TaskStatus::from_str(&a).ok());
+            previous_status = current_status;
+
+            let state = self
+                .resolve_task_state(
+                    project_id,
+                    current.state_id,
+                    current_status,
+                    input.status,
+                    input.state_id,
+                )
+                .await?;
+
+            if let Some((_, resolved_status)) = &state {
+                status = Some(*resolved_status);
+            }
+
+            Some(state.map(|(state, _)| state.id))
+        } else {
+            None
+        };
+
+        // only starting the task is blocked, edits to a task already in progress go through
+        if self.config.enforce_task_blockers
+            && status == Some(TaskStatus::InProgress)
+            && previous_status != Some(TaskStatus::InProgress)
+            && !self.get_open_blockers(id).await?.is_empty()
+        {
+            return Err(SDKError::TaskBlocked);
+        }
+
+        let cycle_id = input.cycle_id.filter(|cycle_id| !cycle_id.is_nil());
+        let milestone_id = input.milestone_id.filter(|milestone_id| !milestone_id.is_nil());
+
+        let estimate = input.estimate.filter(|estimate| *estimate >= 0.0);
+
+        if cycle_id.is_some() || milestone_id.is_some() || estimate.is_some() {
+            let project_id = match input.project_id {
+                Some(project_id) if project_id.is_nil() => None,
+                Some(project_id) => Some(project_id),
+                None => self.get_task(id).await?.project_id,
+            };
+
+            self.validate_task_cycle(project_id, cycle_id).await?;
+            self.validate_task_milestone(project_id, milestone_id).await?;
+            self.validate_task_estimate(project_id, estimate).await?;
+        }
+
+        let completed = status == Some(TaskStatus::Done);
+
+        let added_assignees = input
+            .assignees
+            .as_ref()
+            .map(|assignees| assignees.add.clone())
+            .unwrap_or_default();
+
+        let edited = input.priority.is_some()
+            || input.title.is_some()
+            || input.description.is_some()
+            || input.due_date.is_some()
+            || input.project_id.is_some()
+            || input.lead_id.is_some()
+            || input.parent_id.is_some()
+            || input.cycle_id.is_some()
+            || input.milestone_id.is_some()
+            || input.estimate.is_some()
+            || input.labels.is_some();
+
         let mut tx = self.db_pool.begin().await?;
 
         let task_final_info = sqlx::query!(
@@ -392,11 +582,17 @@ impl TaskCrudOperations for SDKEngine {
                 due_date = COALESCE($5, due_date),
                 project_id = NULLIF(COALESCE($6, project_id), '00000000-0000-0000-0000-000000000000'),
                 lead_id = NULLIF(COALESCE($7, lead_id), '00000000-0000-0000-0000-000000000000'),
-                parent_id = NULLIF(COALESCE($8, parent_id), '00000000-0000-0000-0000-000000000000')
-            WHERE id = $9
+                parent_id = NULLIF(COALESCE($8, parent_id), '00000000-0000-0000-0000-000000000000'),
+                state_id = CASE WHEN $9::bool THEN $10::uuid ELSE state_id END,
+                last_actor_id = $12,
+                cycle_id = NULLIF(COALESCE($13, cycle_id), '00000000-0000-0000-0000-000000000000'),
+                milestone_id = NULLIF(COALESCE($14, milestone_id), '00000000-0000-0000-0000-000000000000'),
+                estimate = CASE WHEN $15::float8 < 0 THEN NULL ELSE COALESCE($15, estimate) END,
+                last_automation_rule_id = $16
+            WHERE id = $11 AND deleted_at IS NULL
             RETURNING *
             "#,
-            input.status.map(|status| status.to_string()),
+            status.map(|status| status.to_string()),
             input.priority.map(|priority| priority.to_string()),
             input.title,
             input.description,
@@ -404,11 +600,23 @@ impl TaskCrudOperations for SDKEngine {
             input.project_id,
             input.lead_id,
             input.parent_id,
+            state.is_some(),
+            state.flatten(),
             id,
+            input.actor_id,
+            input.cycle_id,
+            input.milestone_id,
+            input.estimate,
+            input.automation_rule_id,
         )
         .fetch_one(&mut *tx)
         .await?;
 
+        if input.project_id.is_some() {
+            self.move_subtasks_in_tx(&mut tx, id, task_final_info.project_id, input.actor_id)
+                .await?;
+        }
+
         if let Some(labels) = input.labels {
             for label in labels.add {
                 sqlx::query!(
@@ -512,8 +720,71 @@ impl TaskCrudOperations for SDKEngine {
             owner_id: task_final_info.owner_id,
             count: task_final_info.count,
             parent_id: task_final_info.parent_id,
+            state_id: task_final_info.state_id,
+            cycle_id: task_final_info.cycle_id,
+            milestone_id: task_final_info.milestone_id,
+            estimate: task_final_info.estimate,
+            number: task_final_info.number,
+            identifier: task_final_info.identifier,
+            rank: task_final_info.rank,
+            archived_at: task_final_info.archived_at,
+        };
+
+        // the update is committed, side effects that fail are logged and don't fail it
+        let notified = async {
+            self.auto_watch(&added_assignees, Some(task.id), None, WatchReason::Assigned)
+                .await?;
+
+            let label = task_label(&task);
+
+            self.notify(NotificationDraft {
+                actor_id: input.actor_id,
+                task_id: Some(task.id),
+                project_id: task.project_id,
+                recipients: added_assignees.clone(),
+                ..NotificationDraft::new(NotificationKind::Assigned, format!("You were assigned to {}", label))
+            })
+            .await?;
+
+            let draft = if previous_status.is_some_and(|previous| previous != task.status) {
+                Some(NotificationDraft::new(
+                    NotificationKind::StatusChanged,
+                    format!("{} moved to {}", label, task.status),
+                ))
+            } else if edited {
+                Some(NotificationDraft::new(
+                    NotificationKind::Updated,
+                    format!("{} was updated", label),
+                ))
+            } else {
+                None
+            };
+
+            if let Some(draft) = draft {
+                self.notify(NotificationDraft {
+                    actor_id: input.actor_id,
+                    task_id: Some(task.id),
+                    project_id: task.project_id,
+                    watched: [Some(task.id), task.project_id].into_iter().flatten().collect(),
+                    excluded: added_assignees.clone(),
+                    ..draft
+                })
+                .await?;
+            }
+
+            Ok::<(), SDKError>(())
         };
 
+        if let Err(err) = notified.await {
+            println!("Error notifying about task {}: {:?}", task.id, err);
+        }
+
+        if completed {
+            if let Err(err) = self.generate_occurrence_after_completion(task.id).await {
+                println!("Error generating the next occurrence of task {}: {:?}", task.id, err);
+            }
+        }
+
         // if self.config.with_changes_registration {
         //     let task = task.clone();
         //     let engine = self.clone();
@@ -544,17 +815,42 @@ impl TaskCrudOperations for SDKEngine {
         Ok(task)
     }
 
+    // Moves the task and its subtasks to the trash, restoring the task brings
+    // back the subtasks trashed along with it.
     async fn delete_task(&self, id: Uuid) -> Result<Task, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
         let task_info = sqlx::query!(
             r#"
-            DELETE FROM tasks WHERE id = $1
+            UPDATE tasks SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
             id,
         )
-        .fetch_one(self.db_pool.as_ref())
+        .fetch_one(&mut *tx)
         .await?;
 
+        sqlx::query!(
+            r#"
+            WITH RECURSIVE subtasks AS (
+                SELECT id FROM tasks WHERE parent_id = $1 AND deleted_at IS NULL
+                UNION
+                SELECT tasks.id FROM tasks JOIN subtasks ON tasks.parent_id = subtasks.id
+                WHERE tasks.deleted_at IS NULL
+            )
+            UPDATE tasks SET deleted_at = $2
+            FROM subtasks
+            WHERE tasks.id = subtasks.id
+            "#,
+            id,
+            task_info.deleted_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
         let task = Task {
             id: task_info.id,
             created_at: task_info.created_at,
@@ -575,6 +871,14 @@ impl TaskCrudOperations for SDKEngine {
             owner_id: task_info.owner_id,
             count: task_info.count,
             parent_id: task_info.parent_id,
+            state_id: task_info.state_id,
+            cycle_id: task_info.cycle_id,
+            milestone_id: task_info.milestone_id,
+            estimate: task_info.estimate,
+            number: task_info.number,
+            identifier: task_info.identifier,
+            rank: task_info.rank,
+            archived_at: task_info.archived_at,
         };
 
         // if self.config.with_changes_registration {
@@ -609,15 +913,29 @@ impl TaskCrudOperations for SDKEngine {
     }
 
     async fn get_tasks(&self, input: Option<GetTasksInput>) -> Result<Vec<Task>, SDKError> {
-        let mut query = "SELECT * FROM tasks ".to_string();
+        let mut query = "SELECT * FROM tasks WHERE deleted_at IS NULL ".to_string();
+
+        if !input.as_ref().and_then(|input| input.include_archived).unwrap_or(false) {
+            query.push_str("AND archived_at IS NULL ");
+        }
 
         let query = match input {
             Some(input) => {
-                if let Some(filter) = input.filter {
-                    query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+                if let Some(filter) = input.filter.map(|filter| filter.compile_sql()) {
+                    if !filter.is_empty() {
+                        query.push_str(format!("AND ({}) ", filter).as_str());
+                    }
                 }
 
-                if let Some(sort_by) = input.sort_by {
+                if let Some(field_id) = input.sort_by_custom_field {
+                    query.push_str(
+                        format!(
+                            "ORDER BY (SELECT value FROM custom_field_values WHERE field_id = '{}' AND task_id = tasks.id) ",
+                            field_id
+                        )
+                        .as_str(),
+                    );
+                } else if let Some(sort_by) = input.sort_by {
                     query.push_str(format!("ORDER BY {} ", sort_by).as_str());
                 }
 
@@ -664,6 +982,14 @@ impl TaskCrudOperations for SDKEngine {
                 owner_id: task_info.get("owner_id"),
                 count: task_info.get("count"),
                 parent_id: task_info.get("parent_id"),
+                state_id: task_info.get("state_id"),
+                cycle_id: task_info.get("cycle_id"),
+                milestone_id: task_info.get("milestone_id"),
+                estimate: task_info.get("estimate"),
+                number: task_info.get("number"),
+                identifier: task_info.get("identifier"),
+                rank: task_info.get("rank"),
+                archived_at: task_info.get("archived_at"),
             })
             .collect())
     }
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::{operations::TaskCrudOperations, task::Task};
+
+#[async_trait]
+pub trait TaskRankOperations {
+    async fn reorder_task(&self, id: Uuid, before_id: Option<Uuid>, after_id: Option<Uuid>) -> Result<Task, SDKError>;
+}
+
+#[async_trait]
+impl TaskRankOperations for SDKEngine {
+    // Places the task right after `before_id` and right before `after_id`, both
+    // from the same project and status. A missing side is taken from the tasks
+    // around the other one, with neither the task goes to the end. Only the rank
+    // of the task itself changes, unless neighbours share a rank and have to be
+    // told apart first.
+    async fn reorder_task(&self, id: Uuid, before_id: Option<Uuid>, after_id: Option<Uuid>) -> Result<Task, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let locked = sqlx::query("SELECT lock_task_rank_scope(project_id, status) FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if locked.is_none() {
+            return Err(SDKError::ResourceNotFound);
+        }
+
+        let mut separated = false;
+
+        let (lower, upper) = loop {
+            let bounds = sqlx::query(
+                r#"
+                WITH scope AS (
+                    SELECT tasks.id, tasks.rank
+                    FROM tasks JOIN tasks AS task
+                        ON tasks.project_id IS NOT DISTINCT FROM task.project_id
+                        AND tasks.status IS NOT DISTINCT FROM task.status
+                    WHERE task.id = $1 AND tasks.id <> $1
+                )
+                SELECT
+                    (SELECT rank FROM scope WHERE id = $2) AS before_rank,
+                    (SELECT rank FROM scope WHERE id = $3) AS after_rank,
+                    (SELECT max(rank) FROM scope WHERE rank < (SELECT rank FROM scope WHERE id = $3)) AS previous_rank,
+                    (SELECT min(rank) FROM scope WHERE rank > (SELECT rank FROM scope WHERE id = $2)) AS next_rank,
+                    (SELECT max(rank) FROM scope) AS last_rank
+                "#,
+            )
+            .bind(id)
+            .bind(before_id)
+            .bind(after_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            let before_rank: Option<String> = bounds.get("before_rank");
+            let after_rank: Option<String> = bounds.get("after_rank");
+
+            if before_id.is_some() && before_rank.is_none() || after_id.is_some() && after_rank.is_none() {
+                return Err(SDKError::InvalidTaskOrder);
+            }
+
+            let (lower, upper): (Option<String>, Option<String>) = match (before_id, after_id) {
+                (Some(_), Some(_)) => (before_rank, after_rank),
+                (Some(_), None) => (before_rank, bounds.get("next_rank")),
+                (None, Some(_)) => (bounds.get("previous_rank"), after_rank),
+                (None, None) => (bounds.get("last_rank"), None),
+            };
+
+            match (&lower, &upper) {
+                (Some(lower), Some(upper)) if lower >= upper => {
+                    // still out of order once ties are gone, `after_id` comes before `before_id`
+                    if separated {
+                        return Err(SDKError::InvalidTaskOrder);
+                    }
+
+                    sqlx::query("SELECT separate_task_ranks(project_id, status) FROM tasks WHERE id = $1")
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    separated = true;
+                }
+                _ => break (lower, upper),
+            }
+        };
+
+        sqlx::query("UPDATE tasks SET rank = task_rank_between($2, $3) WHERE id = $1")
+            .bind(id)
+            .bind(lower)
+            .bind(upper)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        self.get_task(id).await
+    }
+}
@@ -8,13 +8,29 @@ use crate::{
     errors::sdk::SDKError,
     resources::{
         changes::change::{Change, ChangeOperation, ChangeResourceType},
+        checklists::{
+            checklist::{ChecklistItem, ChecklistProgress},
+            operations::ChecklistOperations,
+        },
+        comments::comment::Comment,
+        custom_fields::{custom_field::CustomFieldValue, operations::CustomFieldOperations},
+        cycles::cycle::Cycle,
         labels::label::Label,
         members::member::Member,
+        milestones::milestone::Milestone,
         projects::project::Project,
+        time_entries::{
+            operations::{GetTimeEntriesInput, TimeEntryOperations},
+            time_entry::TimeEntry,
+        },
+        workflows::workflow::WorkflowState,
     },
 };
 
-use super::task::Task;
+use super::{
+    estimates::TaskEstimateOperations,
+    task::{EstimateRollup, Task},
+};
 
 #[async_trait]
 pub trait TaskRelations {
@@ -27,12 +43,30 @@ pub trait TaskRelations {
     async fn labels(&self, loaders: &SDKLoaders) -> Result<Vec<Label>, SDKError>;
     async fn subtasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
     async fn changes(&self, loaders: &SDKLoaders) -> Result<Vec<Change>, SDKError>;
+
+    async fn blocked_by(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+    async fn blocking(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+
+    async fn state(&self, loaders: &SDKLoaders) -> Result<Option<WorkflowState>, SDKError>;
+    async fn cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError>;
+    async fn milestone(&self, loaders: &SDKLoaders) -> Result<Option<Milestone>, SDKError>;
+    async fn custom_field_values(&self, loaders: &SDKLoaders) -> Result<Vec<CustomFieldValue>, SDKError>;
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError>;
+    async fn time_entries(&self, loaders: &SDKLoaders) -> Result<Vec<TimeEntry>, SDKError>;
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError>;
+    async fn checklist(&self, loaders: &SDKLoaders) -> Result<Vec<ChecklistItem>, SDKError>;
+    async fn checklist_progress(&self, loaders: &SDKLoaders) -> Result<ChecklistProgress, SDKError>;
 }
 
 #[async_trait]
 impl TaskRelations for Task {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
-        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
 
         Ok(data)
     }
@@ -42,9 +76,9 @@ impl TaskRelations for Task {
             return Ok(None);
         };
 
-        let data = loaders.project_loader.load_one(project_id).await.unwrap().unwrap();
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
     }
 
     async fn lead(&self, loaders: &SDKLoaders) -> Result<Option<Member>, SDKError> {
@@ -52,9 +86,9 @@ impl TaskRelations for Task {
             return Ok(None);
         };
 
-        let data = loaders.member_loader.load_one(lead_id).await.unwrap().unwrap();
+        let data = loaders.member_loader.load_one(lead_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
     }
 
     async fn parent(&self, loaders: &SDKLoaders) -> Result<Option<Task>, SDKError> {
@@ -62,16 +96,17 @@ impl TaskRelations for Task {
             return Ok(None);
         };
 
-        let data = loaders.task_loader.load_one(parent_id).await.unwrap().unwrap();
+        let data = loaders.task_loader.load_one(parent_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
     }
 
     async fn assignees(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError> {
         let ids: Vec<Uuid> = sqlx::query!(
             r#"
             SELECT assignee_id FROM tasks_by_assignees
-            WHERE task_id = $1
+            JOIN members ON members.id = tasks_by_assignees.assignee_id
+            WHERE task_id = $1 AND members.deleted_at IS NULL
             "#,
             &self.id
         )
@@ -84,10 +119,7 @@ impl TaskRelations for Task {
 
         let members_map = loaders.member_loader.load_many(ids.clone()).await.unwrap();
 
-        let members: &Vec<Member> = &ids
-            .into_iter()
-            .map(|id| members_map.get(&id).unwrap().clone())
-            .collect();
+        let members: &Vec<Member> = &ids.into_iter().filter_map(|id| members_map.get(&id).cloned()).collect();
 
         Ok(members.clone())
     }
@@ -109,7 +141,7 @@ impl TaskRelations for Task {
 
         let labels_map = loaders.label_loader.load_many(ids.clone()).await.unwrap();
 
-        let labels: &Vec<Label> = &ids.into_iter().map(|id| labels_map.get(&id).unwrap().clone()).collect();
+        let labels: &Vec<Label> = &ids.into_iter().filter_map(|id| labels_map.get(&id).cloned()).collect();
 
         Ok(labels.clone())
     }
@@ -118,7 +150,7 @@ impl TaskRelations for Task {
         let ids: Vec<Uuid> = sqlx::query!(
             r#"
             SELECT id FROM tasks
-            WHERE parent_id = $1
+            WHERE parent_id = $1 AND deleted_at IS NULL
             "#,
             &self.id
         )
@@ -164,4 +196,124 @@ impl TaskRelations for Task {
             })
             .collect())
     }
+
+    async fn blocked_by(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT source_task_id FROM task_dependencies
+            WHERE target_task_id = $1 AND kind = 'Blocks'
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|id| id.source_task_id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        let tasks: &Vec<Task> = &ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect();
+
+        Ok(tasks.clone())
+    }
+
+    async fn blocking(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT target_task_id FROM task_dependencies
+            WHERE source_task_id = $1 AND kind = 'Blocks'
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|id| id.target_task_id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        let tasks: &Vec<Task> = &ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect();
+
+        Ok(tasks.clone())
+    }
+    async fn state(&self, loaders: &SDKLoaders) -> Result<Option<WorkflowState>, SDKError> {
+        let Some(state_id) = self.state_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.workflow_state_loader.load_one(state_id).await.unwrap();
+
+        Ok(data)
+    }
+    async fn cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError> {
+        let Some(cycle_id) = self.cycle_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.cycle_loader.load_one(cycle_id).await.unwrap();
+
+        Ok(data)
+    }
+    async fn milestone(&self, loaders: &SDKLoaders) -> Result<Option<Milestone>, SDKError> {
+        let Some(milestone_id) = self.milestone_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.milestone_loader.load_one(milestone_id).await.unwrap();
+
+        Ok(data)
+    }
+    async fn custom_field_values(&self, loaders: &SDKLoaders) -> Result<Vec<CustomFieldValue>, SDKError> {
+        loaders.engine.get_custom_field_values(self.id).await
+    }
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM comments
+            WHERE task_id = $1 AND parent_id IS NULL
+            ORDER BY created_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+
+        let comments_map = loaders.comment_loader.load_many(ids.clone()).await.unwrap();
+
+        let comments: &Vec<Comment> = &ids
+            .into_iter()
+            .map(|id| comments_map.get(&id).unwrap().clone())
+            .collect();
+
+        Ok(comments.clone())
+    }
+    async fn time_entries(&self, loaders: &SDKLoaders) -> Result<Vec<TimeEntry>, SDKError> {
+        loaders
+            .engine
+            .get_time_entries(GetTimeEntriesInput {
+                task_id: Some(self.id),
+                ..Default::default()
+            })
+            .await
+    }
+
+    async fn estimate_rollup(&self, loaders: &SDKLoaders) -> Result<EstimateRollup, SDKError> {
+        loaders.engine.get_task_estimate_rollup(self.id).await
+    }
+
+    async fn checklist(&self, loaders: &SDKLoaders) -> Result<Vec<ChecklistItem>, SDKError> {
+        loaders.engine.get_checklist_items(self.id).await
+    }
+
+    async fn checklist_progress(&self, loaders: &SDKLoaders) -> Result<ChecklistProgress, SDKError> {
+        loaders.engine.get_checklist_progress(self.id).await
+    }
 }
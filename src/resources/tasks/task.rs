@@ -27,6 +27,17 @@ pub struct Task {
     pub project_id: Option<Uuid>,
     pub lead_id: Option<Uuid>,
     pub parent_id: Option<Uuid>,
+    pub state_id: Option<Uuid>,
+    pub cycle_id: Option<Uuid>,
+    pub milestone_id: Option<Uuid>,
+    // in the unit of the project estimate scale
+    pub estimate: Option<f64>,
+
+    pub number: Option<i32>,
+    pub identifier: Option<String>,
+    // orders the task within its project and status
+    pub rank: String,
+    pub archived_at: Option<DateTime<Utc>>,
 }
 
 #[derive(
@@ -56,3 +67,18 @@ pub enum TaskPriority {
     High,
     Urgent,
 }
+
+// Estimates of subtasks replace the estimate of their parent, so only the
+// deepest estimated tasks are summed. Canceled tasks and their subtasks are
+// left out.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize, Default)]
+#[graphql(name = "SDKEstimateRollup")]
+pub struct EstimateRollup {
+    pub total: f64,
+    pub completed: f64,
+    pub remaining: f64,
+
+    pub estimated_tasks: i64,
+    // tasks without subtasks that no estimate covers
+    pub unestimated_tasks: i64,
+}
@@ -0,0 +1,309 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use sqlx::{Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::{
+    operations::{TaskCrudOperations, UpdateTaskInput},
+    task::{Task, TaskStatus},
+};
+
+#[async_trait]
+pub trait TaskTransferOperations {
+    async fn duplicate_task(&self, id: Uuid, deep: bool, owner_id: Uuid) -> Result<Task, SDKError>;
+    async fn move_task(&self, id: Uuid, project_id: Uuid, actor_id: Uuid) -> Result<Task, SDKError>;
+}
+
+// Project the copies are created in, their workflow states are matched by name
+// in the given workflow.
+pub(crate) struct TaskCopyTarget {
+    pub project_id: Uuid,
+    pub workflow_id: Option<Uuid>,
+}
+
+impl SDKEngine {
+    // Copies the tasks with their labels, assignees and checklists, returns the
+    // id of every copy in the order of `task_ids`. Subtasks follow their parent
+    // when both are copied, without a target the copies stay next to the originals.
+    pub(crate) async fn copy_tasks_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        task_ids: &[Uuid],
+        owner_id: Uuid,
+        target: Option<TaskCopyTarget>,
+    ) -> Result<Vec<Uuid>, SDKError> {
+        let copy_ids: Vec<Uuid> = task_ids.iter().map(|_| Uuid::new_v4()).collect();
+
+        let (project_id, workflow_id) = match target {
+            Some(target) => (Some(target.project_id), target.workflow_id),
+            None => (None, None),
+        };
+
+        // project cycles and milestones stay behind when the copies change project
+        sqlx::query(
+            r#"
+            INSERT INTO tasks (id, title, description, owner_id, status, priority, due_date, project_id, lead_id, parent_id, state_id, last_actor_id, cycle_id, milestone_id, estimate)
+            SELECT
+                copies.copy_id,
+                tasks.title,
+                tasks.description,
+                $3,
+                tasks.status,
+                tasks.priority,
+                tasks.due_date,
+                COALESCE($4::uuid, tasks.project_id),
+                tasks.lead_id,
+                COALESCE(parents.copy_id, CASE WHEN $4::uuid IS NULL THEN tasks.parent_id END),
+                CASE WHEN $4::uuid IS NULL THEN tasks.state_id ELSE target_states.id END,
+                $3,
+                CASE WHEN $4::uuid IS NULL OR cycles.project_id IS NULL THEN tasks.cycle_id END,
+                CASE WHEN $4::uuid IS NULL THEN tasks.milestone_id END,
+                tasks.estimate
+            FROM unnest($1::uuid[], $2::uuid[]) AS copies(source_id, copy_id)
+                JOIN tasks ON tasks.id = copies.source_id
+                LEFT JOIN unnest($1::uuid[], $2::uuid[]) AS parents(source_id, copy_id)
+                    ON parents.source_id = tasks.parent_id
+                LEFT JOIN cycles ON cycles.id = tasks.cycle_id
+                LEFT JOIN workflow_states source_states ON source_states.id = tasks.state_id
+                LEFT JOIN workflow_states target_states
+                    ON target_states.workflow_id = $5 AND target_states.name = source_states.name
+            ORDER BY tasks.number NULLS LAST, tasks.created_at
+            "#,
+        )
+        .bind(task_ids)
+        .bind(&copy_ids)
+        .bind(owner_id)
+        .bind(project_id)
+        .bind(workflow_id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO labels_by_tasks (task_id, label_id)
+            SELECT copies.copy_id, labels_by_tasks.label_id
+            FROM unnest($1::uuid[], $2::uuid[]) AS copies(source_id, copy_id)
+                JOIN labels_by_tasks ON labels_by_tasks.task_id = copies.source_id
+            "#,
+        )
+        .bind(task_ids)
+        .bind(&copy_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO tasks_by_assignees (task_id, assignee_id)
+            SELECT copies.copy_id, tasks_by_assignees.assignee_id
+            FROM unnest($1::uuid[], $2::uuid[]) AS copies(source_id, copy_id)
+                JOIN tasks_by_assignees ON tasks_by_assignees.task_id = copies.source_id
+            "#,
+        )
+        .bind(task_ids)
+        .bind(&copy_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO task_checklist_items (task_id, owner_id, title, position, completed_at, completed_by_id)
+            SELECT copies.copy_id, $3, items.title, items.position, items.completed_at, items.completed_by_id
+            FROM unnest($1::uuid[], $2::uuid[]) AS copies(source_id, copy_id)
+                JOIN task_checklist_items items ON items.task_id = copies.source_id
+            "#,
+        )
+        .bind(task_ids)
+        .bind(&copy_ids)
+        .bind(owner_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(copy_ids)
+    }
+
+    pub(crate) async fn notify_tasks_copied(&self, copy_ids: &[Uuid]) -> Result<(), SDKError> {
+        for copy_id in copy_ids {
+            let task = self.get_task(*copy_id).await?;
+
+            let assignees: Vec<Uuid> =
+                sqlx::query_scalar("SELECT assignee_id FROM tasks_by_assignees WHERE task_id = $1")
+                    .bind(task.id)
+                    .fetch_all(self.db_pool.as_ref())
+                    .await?;
+
+            self.notify_task_created(&task, &assignees).await;
+        }
+
+        Ok(())
+    }
+
+    // Brings the subtasks of a task that changed project along with it. Their
+    // workflow states are resolved again and cycles, milestones and custom field
+    // values of the previous project are dropped, for the task itself too.
+    pub(crate) async fn move_subtasks_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        project_id: Option<Uuid>,
+        actor_id: Option<Uuid>,
+    ) -> Result<(), SDKError> {
+        let subtasks = sqlx::query(
+            r#"
+            WITH RECURSIVE subtasks AS (
+                SELECT id, project_id, state_id, status, number FROM tasks WHERE parent_id = $1
+                UNION
+                SELECT tasks.id, tasks.project_id, tasks.state_id, tasks.status, tasks.number
+                FROM tasks JOIN subtasks ON tasks.parent_id = subtasks.id
+            )
+            SELECT id, state_id, status FROM subtasks
+            WHERE project_id IS DISTINCT FROM $2
+            ORDER BY number
+            "#,
+        )
+        .bind(id)
+        .bind(project_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        for subtask in subtasks.iter() {
+            let status = subtask
+                .get::<'_, Option<String>, _>("status")
+                .and_then(|status| TaskStatus::from_str(&status).ok());
+
+            let state = self
+                .resolve_task_state(project_id, subtask.get("state_id"), status, None, None)
+                .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE tasks
+                SET
+                    project_id = $2,
+                    state_id = $3,
+                    status = COALESCE($4, status),
+                    last_actor_id = COALESCE($5, last_actor_id)
+                WHERE id = $1
+                "#,
+            )
+            .bind(subtask.get::<Uuid, _>("id"))
+            .bind(project_id)
+            .bind(state.as_ref().map(|(state, _)| state.id))
+            .bind(state.as_ref().map(|(_, status)| status.to_string()))
+            .bind(actor_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let moved: Vec<Uuid> = std::iter::once(id)
+            .chain(subtasks.iter().map(|subtask| subtask.get::<Uuid, _>("id")))
+            .collect();
+
+        // team cycles still apply when the team works on the new project
+        sqlx::query(
+            r#"
+            UPDATE tasks
+            SET
+                cycle_id = CASE WHEN cycles.project_id = $2
+                    OR EXISTS(SELECT 1 FROM teams_by_projects WHERE team_id = cycles.team_id AND project_id = $2)
+                    THEN tasks.cycle_id END,
+                milestone_id = CASE WHEN milestones.project_id = $2 THEN tasks.milestone_id END
+            FROM tasks AS moved
+                LEFT JOIN cycles ON cycles.id = moved.cycle_id
+                LEFT JOIN milestones ON milestones.id = moved.milestone_id
+            WHERE tasks.id = moved.id
+                AND tasks.id = ANY($1)
+                AND (tasks.cycle_id IS NOT NULL OR tasks.milestone_id IS NOT NULL)
+            "#,
+        )
+        .bind(&moved)
+        .bind(project_id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM custom_field_values
+            USING custom_fields
+            WHERE custom_fields.id = custom_field_values.field_id
+                AND custom_field_values.task_id = ANY($1)
+                AND custom_fields.project_id IS DISTINCT FROM $2
+            "#,
+        )
+        .bind(&moved)
+        .bind(project_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskTransferOperations for SDKEngine {
+    // A shallow copy leaves the subtasks out, a deep one copies the whole tree
+    // below the task.
+    async fn duplicate_task(&self, id: Uuid, deep: bool, owner_id: Uuid) -> Result<Task, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let task_ids: Vec<Uuid> = if deep {
+            sqlx::query_scalar(
+                r#"
+                WITH RECURSIVE tree AS (
+                    SELECT id, 0 AS depth FROM tasks WHERE id = $1 AND deleted_at IS NULL
+                    UNION
+                    SELECT tasks.id, tree.depth + 1 FROM tasks JOIN tree ON tasks.parent_id = tree.id
+                    WHERE tasks.deleted_at IS NULL
+                )
+                SELECT id FROM tree ORDER BY depth
+                "#,
+            )
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?
+        } else {
+            sqlx::query_scalar("SELECT id FROM tasks WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .fetch_all(&mut *tx)
+                .await?
+        };
+
+        if task_ids.is_empty() {
+            return Err(SDKError::ResourceNotFound);
+        }
+
+        let copy_ids = self.copy_tasks_in_tx(&mut tx, &task_ids, owner_id, None).await?;
+
+        tx.commit().await?;
+
+        self.notify_tasks_copied(&copy_ids).await?;
+
+        self.get_task(copy_ids[0]).await
+    }
+
+    // A subtask moved on its own leaves its parent behind unless the parent is
+    // already in the new project. A nil project takes the task out of any project.
+    async fn move_task(&self, id: Uuid, project_id: Uuid, actor_id: Uuid) -> Result<Task, SDKError> {
+        let task = self.get_task(id).await?;
+
+        let target = Some(project_id).filter(|project_id| !project_id.is_nil());
+
+        let detach = match task.parent_id {
+            Some(parent_id) => self.get_task(parent_id).await?.project_id != target,
+            None => false,
+        };
+
+        self.update_task(
+            id,
+            UpdateTaskInput {
+                project_id: Some(project_id),
+                parent_id: detach.then(Uuid::nil),
+                actor_id: Some(actor_id),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
@@ -1,27 +1,460 @@
-use super::task::Task;
-use crate::backend::engine::SDKEngine;
-use std::error::Error;
+use std::{
+    collections::HashMap,
+    error::Error,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
 
-trait TaskWatchers {
-    fn watch_created_tasks<H>(&self, handler: H) -> Result<(), Box<dyn Error>>
-    where
-        H: Fn(&Task) -> Result<(), Box<dyn Error>>;
+use strum_macros::Display;
+use tokio::{
+    sync::{watch, Mutex},
+    task::JoinSet,
+};
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+use super::{
+    operations::{GetTasksInput, GetTasksWhere, TaskCrudOperations},
+    task::Task,
+};
+use crate::{
+    backend::engine::SDKEngine,
+    common::runner::RunnerHandle,
+    errors::sdk::SDKError,
+    resources::changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+};
+
+const SNAPSHOT_BATCH_SIZE: i64 = 500;
+
+pub type TaskWatcherResult = Result<(), Box<dyn Error + Send + Sync>>;
+
+type TaskEventHandler = Arc<dyn Fn(TaskEvent) -> Pin<Box<dyn Future<Output = TaskWatcherResult> + Send>> + Send + Sync>;
+
+#[derive(Debug, Copy, Clone, Display, Eq, PartialEq, Hash)]
+pub enum TaskEventKind {
+    Created,
+    Updated,
+    StatusChanged,
+    Assigned,
+    Labeled,
+    Deleted,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskEvent {
+    pub kind: TaskEventKind,
+    pub task: Task,
+    // last known state of the task, missing for created tasks
+    pub previous: Option<Task>,
+
+    pub assignee_ids: Vec<Uuid>,
+    // only filled for `Assigned` events
+    pub added_assignee_ids: Vec<Uuid>,
+
+    pub label_ids: Vec<Uuid>,
+    // only filled for `Labeled` events
+    pub added_label_ids: Vec<Uuid>,
+
+    // member behind the last write to the task, when known
+    pub actor_id: Option<Uuid>,
+    // automation rule behind the last write to the task, if a rule made it
+    pub automation_rule_id: Option<Uuid>,
+}
+
+// an empty scope matches every task
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TaskWatcherScope {
+    pub project_id: Option<Uuid>,
+    pub assignee_id: Option<Uuid>,
+}
+
+impl TaskWatcherScope {
+    pub fn project(project_id: Uuid) -> Self {
+        Self {
+            project_id: Some(project_id),
+            assignee_id: None,
+        }
+    }
+
+    pub fn assignee(assignee_id: Uuid) -> Self {
+        Self {
+            project_id: None,
+            assignee_id: Some(assignee_id),
+        }
+    }
+
+    fn matches(&self, event: &TaskEvent) -> bool {
+        let project_matches = self.project_id.is_none() || self.project_id == event.task.project_id;
+        let assignee_matches = match self.assignee_id {
+            Some(assignee_id) => event.assignee_ids.contains(&assignee_id),
+            None => true,
+        };
+
+        project_matches && assignee_matches
+    }
+}
+
+#[derive(Clone)]
+struct TaskWatcherRegistration {
+    kind: TaskEventKind,
+    scope: TaskWatcherScope,
+    handler: TaskEventHandler,
+}
+
+#[derive(Clone)]
+struct TaskSnapshot {
+    task: Task,
+    assignee_ids: Vec<Uuid>,
+    label_ids: Vec<Uuid>,
+    actor_id: Option<Uuid>,
+    automation_rule_id: Option<Uuid>,
+}
+
+// Handlers are called from the `listen` stream of the tasks table. Every task is
+// snapshotted so updates can be told apart into status changes and assignments.
+#[derive(Clone)]
+pub struct TaskWatchers {
+    engine: Arc<SDKEngine>,
+    registrations: Arc<RwLock<HashMap<Uuid, TaskWatcherRegistration>>>,
+    snapshots: Arc<Mutex<HashMap<Uuid, TaskSnapshot>>>,
 }
 
-impl TaskWatchers for SDKEngine {
-    fn watch_created_tasks<H>(&self, _handler: H) -> Result<(), Box<dyn Error>>
+impl TaskWatchers {
+    pub fn new(engine: Arc<SDKEngine>) -> Self {
+        Self {
+            engine,
+            registrations: Arc::new(RwLock::new(HashMap::new())),
+            snapshots: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn on<F, Fut>(&self, kind: TaskEventKind, scope: TaskWatcherScope, handler: F) -> Uuid
+    where
+        F: Fn(TaskEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskWatcherResult> + Send + 'static,
+    {
+        let id = Uuid::new_v4();
+
+        self.registrations.write().unwrap().insert(
+            id,
+            TaskWatcherRegistration {
+                kind,
+                scope,
+                handler: Arc::new(move |event| Box::pin(handler(event))),
+            },
+        );
+
+        id
+    }
+
+    pub fn on_created<F, Fut>(&self, scope: TaskWatcherScope, handler: F) -> Uuid
+    where
+        F: Fn(TaskEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskWatcherResult> + Send + 'static,
+    {
+        self.on(TaskEventKind::Created, scope, handler)
+    }
+
+    pub fn on_updated<F, Fut>(&self, scope: TaskWatcherScope, handler: F) -> Uuid
     where
-        H: Fn(&Task) -> Result<(), Box<dyn Error>>,
+        F: Fn(TaskEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskWatcherResult> + Send + 'static,
     {
-        // let (tx, mut rx) = watch::channel(Task::default());
+        self.on(TaskEventKind::Updated, scope, handler)
+    }
 
-        // tokio::spawn(async move {
-        //     loop {
-        //         let task = self.task_event_recv.recv().unwrap();
-        //         // handler(&task).unwrap();
-        //     }
-        // });
+    pub fn on_status_changed<F, Fut>(&self, scope: TaskWatcherScope, handler: F) -> Uuid
+    where
+        F: Fn(TaskEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskWatcherResult> + Send + 'static,
+    {
+        self.on(TaskEventKind::StatusChanged, scope, handler)
+    }
+
+    pub fn on_assigned<F, Fut>(&self, scope: TaskWatcherScope, handler: F) -> Uuid
+    where
+        F: Fn(TaskEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskWatcherResult> + Send + 'static,
+    {
+        self.on(TaskEventKind::Assigned, scope, handler)
+    }
+
+    pub fn on_labeled<F, Fut>(&self, scope: TaskWatcherScope, handler: F) -> Uuid
+    where
+        F: Fn(TaskEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskWatcherResult> + Send + 'static,
+    {
+        self.on(TaskEventKind::Labeled, scope, handler)
+    }
+
+    pub fn on_deleted<F, Fut>(&self, scope: TaskWatcherScope, handler: F) -> Uuid
+    where
+        F: Fn(TaskEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskWatcherResult> + Send + 'static,
+    {
+        self.on(TaskEventKind::Deleted, scope, handler)
+    }
+
+    pub fn unregister(&self, id: Uuid) -> bool {
+        self.registrations.write().unwrap().remove(&id).is_some()
+    }
+
+    // Subscribes before taking the snapshot so no change slips in between. Events
+    // already being dispatched finish before the runner stops on shutdown.
+    pub async fn spawn(self) -> Result<RunnerHandle, SDKError> {
+        let mut events = self.engine.listen(ChangeResourceType::Tasks).await?;
+
+        self.load_snapshots().await?;
+
+        let (shutdown, mut shutdown_recv) = watch::channel(false);
+
+        let join = tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown_recv.changed() => break,
+                    event = events.next() => event,
+                };
+
+                match event {
+                    Some(Ok(event)) => {
+                        if let Err(err) = self.process(event).await {
+                            println!("Error processing task event: {:?}", err);
+                        }
+                    }
+                    Some(Err(err)) => println!("Error listening to task events: {:?}", err),
+                    None => break,
+                }
+            }
+        });
+
+        Ok(RunnerHandle::new(shutdown, join))
+    }
+
+    // pages through the tasks by id so neither the queries nor the lock grow with
+    // the size of the workspace
+    async fn load_snapshots(&self) -> Result<(), SDKError> {
+        let mut after = Uuid::nil();
+
+        loop {
+            let relations = sqlx::query!(
+                r#"
+                SELECT
+                    tasks.id,
+                    tasks.last_actor_id,
+                    tasks.last_automation_rule_id,
+                    array(SELECT assignee_id FROM tasks_by_assignees WHERE task_id = tasks.id) AS "assignee_ids!",
+                    array(SELECT label_id FROM labels_by_tasks WHERE task_id = tasks.id) AS "label_ids!"
+                FROM tasks
+                WHERE deleted_at IS NULL AND id > $1
+                ORDER BY id
+                LIMIT $2
+                "#,
+                after,
+                SNAPSHOT_BATCH_SIZE,
+            )
+            .fetch_all(self.engine.db_pool.as_ref())
+            .await?;
+
+            let Some(last) = relations.last() else {
+                return Ok(());
+            };
+
+            after = last.id;
+            let batch_size = relations.len() as i64;
+
+            let tasks = self
+                .engine
+                .get_tasks(Some(GetTasksInput {
+                    filter: Some(GetTasksWhere {
+                        ids: Some(relations.iter().map(|relation| relation.id).collect()),
+                        ..Default::default()
+                    }),
+                    include_archived: Some(true),
+                    ..Default::default()
+                }))
+                .await?;
+
+            let mut relations: HashMap<Uuid, _> = relations.into_iter().map(|row| (row.id, row)).collect();
+
+            let mut snapshots = self.snapshots.lock().await;
+
+            for task in tasks {
+                let Some(relation) = relations.remove(&task.id) else {
+                    continue;
+                };
+
+                snapshots.insert(
+                    task.id,
+                    TaskSnapshot {
+                        task,
+                        assignee_ids: relation.assignee_ids,
+                        label_ids: relation.label_ids,
+                        actor_id: relation.last_actor_id,
+                        automation_rule_id: relation.last_automation_rule_id,
+                    },
+                );
+            }
+
+            drop(snapshots);
+
+            if batch_size < SNAPSHOT_BATCH_SIZE {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn load_snapshot(&self, task_id: Uuid) -> Result<Option<TaskSnapshot>, SDKError> {
+        let task = match self.engine.get_task(task_id).await {
+            Ok(task) => task,
+            Err(SDKError::SQLXError(sqlx::Error::RowNotFound)) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let relation = sqlx::query!(
+            r#"
+            SELECT
+                last_actor_id,
+                last_automation_rule_id,
+                array(SELECT assignee_id FROM tasks_by_assignees WHERE task_id = $1) AS "assignee_ids!",
+                array(SELECT label_id FROM labels_by_tasks WHERE task_id = $1) AS "label_ids!"
+            FROM tasks
+            WHERE id = $1
+            "#,
+            task_id,
+        )
+        .fetch_one(self.engine.db_pool.as_ref())
+        .await?;
+
+        Ok(Some(TaskSnapshot {
+            task,
+            assignee_ids: relation.assignee_ids,
+            label_ids: relation.label_ids,
+            actor_id: relation.last_actor_id,
+            automation_rule_id: relation.last_automation_rule_id,
+        }))
+    }
+
+    async fn process(&self, event: ListenEvent) -> Result<(), SDKError> {
+        let mut snapshots = self.snapshots.lock().await;
+
+        let previous = match event.operation {
+            ChangeOperation::Delete => snapshots.remove(&event.row_id),
+            _ => snapshots.get(&event.row_id).cloned(),
+        };
+
+        let current = match event.operation {
+            ChangeOperation::Delete => None,
+            _ => self.load_snapshot(event.row_id).await?,
+        };
+
+        // trashed tasks read as deleted, restoring them creates them again
+        match &current {
+            Some(current) => snapshots.insert(current.task.id, current.clone()),
+            None => snapshots.remove(&event.row_id),
+        };
+
+        drop(snapshots);
+
+        for event in Self::diff(previous, current) {
+            self.dispatch(event).await;
+        }
 
         Ok(())
     }
+
+    fn diff(previous: Option<TaskSnapshot>, current: Option<TaskSnapshot>) -> Vec<TaskEvent> {
+        let event = |kind, snapshot: &TaskSnapshot, previous: Option<&TaskSnapshot>| TaskEvent {
+            kind,
+            task: snapshot.task.clone(),
+            previous: previous.map(|previous| previous.task.clone()),
+            assignee_ids: snapshot.assignee_ids.clone(),
+            added_assignee_ids: Vec::new(),
+            label_ids: snapshot.label_ids.clone(),
+            added_label_ids: Vec::new(),
+            actor_id: snapshot.actor_id,
+            automation_rule_id: snapshot.automation_rule_id,
+        };
+
+        let added = |current: &[Uuid], previous: &[Uuid]| -> Vec<Uuid> {
+            current.iter().filter(|id| !previous.contains(id)).copied().collect()
+        };
+
+        match (previous, current) {
+            (previous, Some(current)) => {
+                let mut events = Vec::new();
+
+                let (added_assignee_ids, added_label_ids) = match &previous {
+                    None => {
+                        events.push(event(TaskEventKind::Created, &current, None));
+
+                        (current.assignee_ids.clone(), current.label_ids.clone())
+                    }
+                    Some(previous) => {
+                        let removed = !added(&previous.assignee_ids, &current.assignee_ids).is_empty()
+                            || !added(&previous.label_ids, &current.label_ids).is_empty();
+
+                        if previous.task.updated_at != current.task.updated_at || removed {
+                            events.push(event(TaskEventKind::Updated, &current, Some(previous)));
+                        }
+
+                        if previous.task.status != current.task.status {
+                            events.push(event(TaskEventKind::StatusChanged, &current, Some(previous)));
+                        }
+
+                        (
+                            added(&current.assignee_ids, &previous.assignee_ids),
+                            added(&current.label_ids, &previous.label_ids),
+                        )
+                    }
+                };
+
+                if !added_assignee_ids.is_empty() {
+                    events.push(TaskEvent {
+                        added_assignee_ids,
+                        ..event(TaskEventKind::Assigned, &current, previous.as_ref())
+                    });
+                }
+
+                if !added_label_ids.is_empty() {
+                    events.push(TaskEvent {
+                        added_label_ids,
+                        ..event(TaskEventKind::Labeled, &current, previous.as_ref())
+                    });
+                }
+
+                events
+            }
+            (Some(previous), None) => vec![event(TaskEventKind::Deleted, &previous, None)],
+            (None, None) => Vec::new(),
+        }
+    }
+
+    // each handler runs in its own task so a failing or panicking one can't take
+    // the others, or the runner, down with it. They run side by side, the next
+    // event waits for the slowest one only so events keep their order.
+    async fn dispatch(&self, event: TaskEvent) {
+        let registrations: Vec<TaskWatcherRegistration> = self
+            .registrations
+            .read()
+            .unwrap()
+            .values()
+            .filter(|registration| registration.kind == event.kind && registration.scope.matches(&event))
+            .cloned()
+            .collect();
+
+        let mut handlers = JoinSet::new();
+
+        for registration in registrations {
+            handlers.spawn((registration.handler)(event.clone()));
+        }
+
+        while let Some(result) = handlers.join_next().await {
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => println!("Task watcher for {} events failed: {:?}", event.kind, err),
+                Err(err) => println!("Task watcher for {} events panicked: {:?}", event.kind, err),
+            }
+        }
+    }
 }
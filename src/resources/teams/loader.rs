@@ -23,7 +23,7 @@ impl Loader<Uuid> for TeamLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let teams = sqlx::query!(
             r#"
-            SELECT * FROM teams WHERE id  = ANY($1)
+            SELECT * FROM teams WHERE id  = ANY($1) AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -223,7 +223,7 @@ impl TeamCrudOperations for SDKEngine {
             r#"
             SELECT id, created_at, updated_at, name, owner_id, visibility, prefix
             FROM teams
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
@@ -247,10 +247,10 @@ impl TeamCrudOperations for SDKEngine {
     }
 
     async fn get_teams(&self, input: GetTeamsInput) -> Result<Vec<Team>, SDKError> {
-        let mut query = "SELECT * FROM teams ".to_string();
+        let mut query = "SELECT * FROM teams WHERE deleted_at IS NULL ".to_string();
 
         if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+            query.push_str(format!("AND ({}) ", filter.compile_sql()).as_str());
         }
 
         if let Some(sort_by) = input.sort_by {
@@ -302,7 +302,7 @@ impl TeamCrudOperations for SDKEngine {
                 visibility = COALESCE($3, visibility),
                 prefix = COALESCE($4, prefix),
                 updated_at = now()
-            WHERE id = $5
+            WHERE id = $5 AND deleted_at IS NULL
             RETURNING *
             "#,
             input.name,
@@ -391,8 +391,8 @@ impl TeamCrudOperations for SDKEngine {
     async fn delete_team(&self, id: Uuid) -> Result<Team, SDKError> {
         let team_info = sqlx::query!(
             r#"
-            DELETE FROM teams
-            WHERE id = $1
+            UPDATE teams SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
             id
@@ -2,8 +2,8 @@ use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::{
-    backend::loaders::SDKLoaders, errors::sdk::SDKError, resources::members::member::Member,
-    resources::projects::project::Project,
+    backend::loaders::SDKLoaders, errors::sdk::SDKError, resources::cycles::cycle::Cycle,
+    resources::members::member::Member, resources::projects::project::Project,
 };
 
 use super::team::Team;
@@ -13,12 +13,19 @@ pub trait TeamRelations {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
     async fn projects(&self, loaders: &SDKLoaders) -> Result<Vec<Project>, SDKError>;
     async fn members(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError>;
+    async fn cycles(&self, loaders: &SDKLoaders) -> Result<Vec<Cycle>, SDKError>;
+    async fn active_cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError>;
 }
 
 #[async_trait]
 impl TeamRelations for Team {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
-        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
 
         Ok(data)
     }
@@ -42,7 +49,8 @@ impl TeamRelations for Team {
 
         let projects: &Vec<Project> = &ids
             .into_iter()
-            .map(|id| projects_map.get(&id).unwrap().clone())
+            .filter_map(|id| projects_map.get(&id).cloned())
+            .filter(|project| project.archived_at.is_none())
             .collect();
 
         Ok(projects.clone())
@@ -52,7 +60,8 @@ impl TeamRelations for Team {
         let ids: Vec<Uuid> = sqlx::query!(
             r#"
             SELECT member_id FROM members_by_teams
-            WHERE team_id = $1
+            JOIN members ON members.id = members_by_teams.member_id
+            WHERE team_id = $1 AND members.deleted_at IS NULL
             "#,
             &self.id
         )
@@ -65,11 +74,52 @@ impl TeamRelations for Team {
 
         let members_map = loaders.member_loader.load_many(ids.clone()).await.unwrap();
 
-        let members: &Vec<Member> = &ids
-            .into_iter()
-            .map(|id| members_map.get(&id).unwrap().clone())
-            .collect();
+        let members: &Vec<Member> = &ids.into_iter().filter_map(|id| members_map.get(&id).cloned()).collect();
 
         Ok(members.clone())
     }
+
+    async fn cycles(&self, loaders: &SDKLoaders) -> Result<Vec<Cycle>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM cycles
+            WHERE team_id = $1 AND deleted_at IS NULL
+            ORDER BY starts_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+
+        let cycles_map = loaders.cycle_loader.load_many(ids.clone()).await.unwrap();
+
+        let cycles: &Vec<Cycle> = &ids.into_iter().map(|id| cycles_map.get(&id).unwrap().clone()).collect();
+
+        Ok(cycles.clone())
+    }
+
+    async fn active_cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError> {
+        let Some(cycle) = sqlx::query!(
+            r#"
+            SELECT id FROM cycles
+            WHERE team_id = $1 AND completed_at IS NULL AND starts_at <= now() AND deleted_at IS NULL
+            ORDER BY starts_at DESC
+            LIMIT 1
+            "#,
+            &self.id
+        )
+        .fetch_optional(&*loaders.engine.db_pool)
+        .await
+        .unwrap() else {
+            return Ok(None);
+        };
+
+        let data = loaders.cycle_loader.load_one(cycle.id).await.unwrap();
+
+        Ok(data)
+    }
 }
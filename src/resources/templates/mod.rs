@@ -0,0 +1,3 @@
+pub mod operations;
+pub mod relations;
+pub mod template;
@@ -0,0 +1,605 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
+};
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{
+        notifications::notification::WatchReason,
+        projects::{
+            operations::{CreateProjectInput, ProjectCrudOperations},
+            project::{EstimateScale, Project, ProjectVisibility},
+        },
+        tasks::{
+            extensions::CreateTasksInput,
+            operations::{CreateTaskInput, TaskCrudOperations},
+            task::{Task, TaskPriority},
+        },
+    },
+};
+
+use super::template::{Template, TemplateKind, TemplateTask};
+
+#[async_trait]
+pub trait TemplateOperations {
+    async fn save_project_as_template(&self, input: SaveProjectAsTemplateInput) -> Result<Template, SDKError>;
+    async fn save_task_as_template(&self, input: SaveTaskAsTemplateInput) -> Result<Template, SDKError>;
+
+    async fn get_template(&self, id: Uuid) -> Result<Template, SDKError>;
+    async fn get_templates(&self, input: GetTemplatesInput) -> Result<Vec<Template>, SDKError>;
+    async fn get_template_tasks(&self, template_id: Uuid) -> Result<Vec<TemplateTask>, SDKError>;
+    async fn delete_template(&self, id: Uuid) -> Result<Template, SDKError>;
+
+    async fn instantiate_project_template(&self, input: InstantiateProjectTemplateInput) -> Result<Project, SDKError>;
+    async fn instantiate_task_template(&self, input: InstantiateTaskTemplateInput) -> Result<Vec<Task>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct SaveProjectAsTemplateInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct SaveTaskAsTemplateInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+    pub task_id: Uuid,
+    pub name: String,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetTemplatesInput {
+    #[builder(setter(strip_option), default)]
+    pub kind: Option<TemplateKind>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct TemplateVariableInput {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct InstantiateProjectTemplateInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+    pub template_id: Uuid,
+
+    // now when left out
+    #[builder(setter(strip_option), default)]
+    pub start_date: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub variables: Option<Vec<TemplateVariableInput>>,
+
+    // the template prefix when left out
+    #[builder(setter(strip_option), default)]
+    pub prefix: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub members: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub teams: Option<Vec<Uuid>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct InstantiateTaskTemplateInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+    pub template_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    // the root tasks of the template become subtasks of this task
+    #[builder(setter(strip_option), default)]
+    pub parent_id: Option<Uuid>,
+
+    // now when left out
+    #[builder(setter(strip_option), default)]
+    pub start_date: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub variables: Option<Vec<TemplateVariableInput>>,
+}
+
+pub(crate) fn template_from_row(row: &PgRow) -> Template {
+    Template {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        owner_id: row.get("owner_id"),
+        kind: TemplateKind::from_str(row.get("kind")).unwrap_or_default(),
+        name: row.get("name"),
+        description: row.get("description"),
+        project_name: row.get("project_name"),
+        project_description: row.get("project_description"),
+        project_prefix: row.get("project_prefix"),
+        project_visibility: row
+            .get::<Option<String>, _>("project_visibility")
+            .and_then(|visibility| ProjectVisibility::from_str(&visibility).ok()),
+        estimate_scale: row
+            .get::<Option<String>, _>("estimate_scale")
+            .and_then(|scale| EstimateScale::from_str(&scale).ok()),
+        due_offset_days: row.get("due_offset_days"),
+    }
+}
+
+pub(crate) fn template_task_from_row(row: &PgRow) -> TemplateTask {
+    TemplateTask {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        template_id: row.get("template_id"),
+        parent_id: row.get("parent_id"),
+        position: row.get("position"),
+        title: row.get("title"),
+        description: row.get("description"),
+        priority: row
+            .get::<Option<String>, _>("priority")
+            .and_then(|priority| TaskPriority::from_str(&priority).ok())
+            .unwrap_or_default(),
+        estimate: row.get("estimate"),
+        due_offset_days: row.get("due_offset_days"),
+        label_ids: row.get("label_ids"),
+    }
+}
+
+// whole calendar days in UTC, the time of day follows the new start
+fn offset_days(from: DateTime<Utc>, to: Option<DateTime<Utc>>) -> Option<i32> {
+    to.map(|to| (to.date_naive() - from.date_naive()).num_days() as i32)
+}
+
+fn offset_date(start: DateTime<Utc>, days: Option<i32>) -> Option<DateTime<Utc>> {
+    days.map(|days| start + Duration::try_days(days.into()).unwrap_or_default())
+}
+
+// Replaces every {{name}} with its value, names without a value are collected
+// in `missing`. Braces around anything but a plain name are kept as they are.
+fn substitute_variables(text: &str, variables: &HashMap<String, String>, missing: &mut BTreeSet<String>) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest[2..].find("}}").map(|end| 2 + end) else {
+            break;
+        };
+
+        let name = rest[2..end].trim();
+
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            match variables.get(name) {
+                Some(value) => result.push_str(value),
+                None => {
+                    missing.insert(name.to_string());
+                }
+            }
+        } else {
+            result.push_str(&rest[..end + 2]);
+        }
+
+        rest = &rest[end + 2..];
+    }
+
+    result.push_str(rest);
+
+    result
+}
+
+struct TemplateVariables {
+    values: HashMap<String, String>,
+    missing: BTreeSet<String>,
+}
+
+impl TemplateVariables {
+    fn new(variables: Option<Vec<TemplateVariableInput>>) -> Self {
+        TemplateVariables {
+            values: variables
+                .unwrap_or_default()
+                .into_iter()
+                .map(|variable| (variable.name.trim().to_string(), variable.value))
+                .collect(),
+            missing: BTreeSet::new(),
+        }
+    }
+
+    fn fill(&mut self, text: &str) -> String {
+        substitute_variables(text, &self.values, &mut self.missing)
+    }
+
+    fn check(&self) -> Result<(), SDKError> {
+        if self.missing.is_empty() {
+            return Ok(());
+        }
+
+        Err(SDKError::MissingTemplateVariables(
+            self.missing.iter().cloned().collect::<Vec<String>>().join(", "),
+        ))
+    }
+}
+
+impl SDKEngine {
+    // Copies the tasks matched by `roots` and all their subtasks into the
+    // template, canceled tasks and their subtasks are left out. Tasks whose
+    // parent is not copied become root tasks of the template.
+    async fn copy_template_tasks(
+        tx: &mut Transaction<'_, Postgres>,
+        template_id: Uuid,
+        roots: &str,
+        id: Uuid,
+        start: DateTime<Utc>,
+    ) -> Result<(), SDKError> {
+        sqlx::query(
+            format!(
+                r#"
+                WITH RECURSIVE tree AS (
+                    SELECT * FROM tasks WHERE {} AND deleted_at IS NULL
+                    UNION
+                    SELECT tasks.* FROM tasks
+                    JOIN tree ON tasks.parent_id = tree.id
+                    WHERE COALESCE(tasks.status, '') <> 'Canceled' AND tasks.deleted_at IS NULL
+                ), mapped AS MATERIALIZED (
+                    SELECT id AS task_id, gen_random_uuid() AS template_task_id FROM tree
+                )
+                INSERT INTO template_tasks (id, template_id, parent_id, position, title, description, priority, estimate, due_offset_days, label_ids)
+                SELECT
+                    mapped.template_task_id,
+                    $2,
+                    parent.template_task_id,
+                    (row_number() OVER (PARTITION BY parent.template_task_id ORDER BY tree.created_at) - 1)::integer,
+                    tree.title,
+                    tree.description,
+                    tree.priority,
+                    tree.estimate,
+                    (tree.due_date AT TIME ZONE 'UTC')::date - ($3 AT TIME ZONE 'UTC')::date,
+                    ARRAY(SELECT label_id FROM labels_by_tasks WHERE task_id = tree.id)
+                FROM tree
+                JOIN mapped ON mapped.task_id = tree.id
+                LEFT JOIN mapped AS parent ON parent.task_id = tree.parent_id
+                "#,
+                roots
+            )
+            .as_str(),
+        )
+        .bind(id)
+        .bind(template_id)
+        .bind(start)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    // Creates the tasks level by level so every subtask finds its parent,
+    // returns every task created.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_template_tasks_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        template_tasks: &[TemplateTask],
+        variables: &mut TemplateVariables,
+        owner_id: Uuid,
+        project_id: Option<Uuid>,
+        parent_id: Option<Uuid>,
+        start: DateTime<Utc>,
+    ) -> Result<Vec<Task>, SDKError> {
+        let label_ids: Vec<Uuid> = template_tasks.iter().flat_map(|task| task.label_ids.clone()).collect();
+
+        // labels deleted since the template was saved are dropped
+        let labels: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM labels WHERE id = ANY($1) AND deleted_at IS NULL")
+            .bind(&label_ids)
+            .fetch_all(&mut **tx)
+            .await?;
+
+        let states = match project_id {
+            Some(project_id) => {
+                self.resolve_task_state(Some(project_id), None, None, None, None)
+                    .await?
+            }
+            None => None,
+        };
+
+        let mut created = HashMap::<Uuid, Uuid>::new();
+        let mut tasks = Vec::new();
+
+        loop {
+            let level: Vec<&TemplateTask> = template_tasks
+                .iter()
+                .filter(|task| !created.contains_key(&task.id))
+                .filter(|task| task.parent_id.is_none_or(|parent_id| created.contains_key(&parent_id)))
+                .collect();
+
+            if level.is_empty() {
+                break;
+            }
+
+            let input = CreateTasksInput {
+                tasks: level
+                    .iter()
+                    .map(|task| CreateTaskInput {
+                        title: variables.fill(&task.title),
+                        owner_id,
+                        priority: Some(task.priority),
+                        description: task.description.as_ref().map(|description| variables.fill(description)),
+                        due_date: offset_date(start, task.due_offset_days),
+                        project_id,
+                        parent_id: task.parent_id.map(|parent_id| created[&parent_id]).or(parent_id),
+                        estimate: task.estimate,
+                        labels: Some(
+                            task.label_ids
+                                .iter()
+                                .filter(|label_id| labels.contains(label_id))
+                                .copied()
+                                .collect(),
+                        ),
+                        ..Default::default()
+                    })
+                    .collect(),
+            };
+
+            variables.check()?;
+
+            let level_states: Vec<_> = level.iter().map(|_| states.clone()).collect();
+            let level_tasks = self.create_tasks_in_tx(tx, &input, &level_states).await?;
+
+            for (template_task, task) in level.iter().zip(level_tasks.iter()) {
+                created.insert(template_task.id, task.id);
+            }
+
+            tasks.extend(level_tasks);
+        }
+
+        Ok(tasks)
+    }
+}
+
+#[async_trait]
+impl TemplateOperations for SDKEngine {
+    // Due dates are kept as days from the start of the project, or from its
+    // creation when it has no start date.
+    async fn save_project_as_template(&self, input: SaveProjectAsTemplateInput) -> Result<Template, SDKError> {
+        let project = self.get_project(input.project_id).await?;
+        let start = project.start_date.unwrap_or(project.created_at);
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let template_info = sqlx::query(
+            r#"
+            INSERT INTO templates (owner_id, kind, name, description, project_name, project_description, project_prefix, project_visibility, estimate_scale, due_offset_days)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(input.owner_id)
+        .bind(TemplateKind::Project.to_string())
+        .bind(input.name)
+        .bind(input.description)
+        .bind(project.name)
+        .bind(project.description)
+        .bind(project.prefix)
+        .bind(project.visibility.to_string())
+        .bind(project.estimate_scale.to_string())
+        .bind(offset_days(start, project.due_date))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let template = template_from_row(&template_info);
+
+        Self::copy_template_tasks(
+            &mut tx,
+            template.id,
+            "project_id = $1 AND COALESCE(status, '') <> 'Canceled'",
+            project.id,
+            start,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(template)
+    }
+
+    // Due dates are kept as days from the creation of the task.
+    async fn save_task_as_template(&self, input: SaveTaskAsTemplateInput) -> Result<Template, SDKError> {
+        let task = self.get_task(input.task_id).await?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let template_info = sqlx::query(
+            r#"
+            INSERT INTO templates (owner_id, kind, name, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.owner_id)
+        .bind(TemplateKind::Task.to_string())
+        .bind(input.name)
+        .bind(input.description)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let template = template_from_row(&template_info);
+
+        Self::copy_template_tasks(&mut tx, template.id, "id = $1", task.id, task.created_at).await?;
+
+        tx.commit().await?;
+
+        Ok(template)
+    }
+
+    async fn get_template(&self, id: Uuid) -> Result<Template, SDKError> {
+        let template_info = sqlx::query("SELECT * FROM templates WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(template_from_row(&template_info))
+    }
+
+    async fn get_templates(&self, input: GetTemplatesInput) -> Result<Vec<Template>, SDKError> {
+        let mut query = "SELECT * FROM templates WHERE deleted_at IS NULL ".to_string();
+
+        if let Some(kind) = input.kind {
+            query.push_str(format!("AND kind = '{}' ", kind).as_str());
+        }
+
+        query.push_str("ORDER BY name ");
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let templates_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(templates_info.iter().map(template_from_row).collect())
+    }
+
+    async fn get_template_tasks(&self, template_id: Uuid) -> Result<Vec<TemplateTask>, SDKError> {
+        let template_tasks_info =
+            sqlx::query("SELECT * FROM template_tasks WHERE template_id = $1 ORDER BY parent_id NULLS FIRST, position")
+                .bind(template_id)
+                .fetch_all(self.db_pool.as_ref())
+                .await?;
+
+        Ok(template_tasks_info.iter().map(template_task_from_row).collect())
+    }
+
+    async fn delete_template(&self, id: Uuid) -> Result<Template, SDKError> {
+        let template_info =
+            sqlx::query("UPDATE templates SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL RETURNING *")
+                .bind(id)
+                .fetch_one(self.db_pool.as_ref())
+                .await?;
+
+        Ok(template_from_row(&template_info))
+    }
+
+    // The project and all its tasks are created in one transaction, nothing is
+    // left behind when a variable has no value.
+    async fn instantiate_project_template(&self, input: InstantiateProjectTemplateInput) -> Result<Project, SDKError> {
+        let template = self.get_template(input.template_id).await?;
+        let template_tasks = self.get_template_tasks(template.id).await?;
+
+        let start = input.start_date.unwrap_or_else(Utc::now);
+        let mut variables = TemplateVariables::new(input.variables);
+
+        let project_input = CreateProjectInput {
+            name: variables.fill(template.project_name.as_ref().unwrap_or(&template.name)),
+            owner_id: input.owner_id,
+            visibility: template.project_visibility,
+            estimate_scale: template.estimate_scale,
+            prefix: input
+                .prefix
+                .or(template.project_prefix.as_ref().map(|prefix| variables.fill(prefix))),
+            description: template
+                .project_description
+                .as_ref()
+                .map(|description| variables.fill(description)),
+            start_date: Some(start),
+            due_date: offset_date(start, template.due_offset_days),
+            members: input.members,
+            teams: input.teams,
+            ..Default::default()
+        };
+
+        variables.check()?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let project = self.create_project_in_tx(&mut tx, project_input).await?;
+
+        let tasks = self
+            .create_template_tasks_in_tx(
+                &mut tx,
+                &template_tasks,
+                &mut variables,
+                input.owner_id,
+                Some(project.id),
+                None,
+                start,
+            )
+            .await?;
+
+        tx.commit().await?;
+
+        self.auto_watch(&[project.owner_id], None, Some(project.id), WatchReason::Created)
+            .await?;
+
+        for task in tasks.iter() {
+            self.notify_task_created(task, &[]).await;
+        }
+
+        Ok(project)
+    }
+
+    // Returns the root tasks of the template, subtasks hang below them.
+    async fn instantiate_task_template(&self, input: InstantiateTaskTemplateInput) -> Result<Vec<Task>, SDKError> {
+        let template_tasks = self.get_template_tasks(input.template_id).await?;
+
+        for task in template_tasks.iter() {
+            self.validate_task_estimate(input.project_id, task.estimate).await?;
+        }
+
+        let start = input.start_date.unwrap_or_else(Utc::now);
+        let mut variables = TemplateVariables::new(input.variables);
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let tasks = self
+            .create_template_tasks_in_tx(
+                &mut tx,
+                &template_tasks,
+                &mut variables,
+                input.owner_id,
+                input.project_id,
+                input.parent_id,
+                start,
+            )
+            .await?;
+
+        tx.commit().await?;
+
+        for task in tasks.iter() {
+            self.notify_task_created(task, &[]).await;
+        }
+
+        Ok(tasks
+            .into_iter()
+            .filter(|task| task.parent_id == input.parent_id)
+            .collect())
+    }
+}
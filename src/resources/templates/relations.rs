@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+
+use crate::{backend::loaders::SDKLoaders, errors::sdk::SDKError, resources::members::member::Member};
+
+use super::{
+    operations::TemplateOperations,
+    template::{Template, TemplateTask},
+};
+
+#[async_trait]
+pub trait TemplateRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<TemplateTask>, SDKError>;
+}
+
+#[async_trait]
+impl TemplateRelations for Template {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders
+            .member_loader
+            .load_one(self.owner_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<TemplateTask>, SDKError> {
+        loaders.engine.get_template_tasks(self.id).await
+    }
+}
@@ -0,0 +1,67 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{
+    projects::project::{EstimateScale, ProjectVisibility},
+    tasks::task::TaskPriority,
+};
+
+// Names, titles, descriptions and prefixes may hold variables such as
+// {{client_name}}, filled in when the template is instantiated.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTemplate")]
+pub struct Template {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub kind: TemplateKind,
+
+    pub name: String,
+    pub description: Option<String>,
+
+    // only set on project templates
+    pub project_name: Option<String>,
+    pub project_description: Option<String>,
+    pub project_prefix: Option<String>,
+    pub project_visibility: Option<ProjectVisibility>,
+    pub estimate_scale: Option<EstimateScale>,
+    // days from the start to the due date of the project
+    pub due_offset_days: Option<i32>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTemplateTask")]
+pub struct TemplateTask {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub template_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub position: i32,
+
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: TaskPriority,
+    pub estimate: Option<f64>,
+    // days from the start to the due date of the task
+    pub due_offset_days: Option<i32>,
+    pub label_ids: Vec<Uuid>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum TemplateKind {
+    #[default]
+    Project,
+    Task,
+}
@@ -0,0 +1,4 @@
+pub mod operations;
+pub mod relations;
+pub mod time_entry;
+pub mod timesheets;
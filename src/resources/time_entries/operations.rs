@@ -0,0 +1,338 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::time_entry::TimeEntry;
+
+#[async_trait]
+pub trait TimeEntryOperations {
+    async fn create_time_entry(&self, input: CreateTimeEntryInput) -> Result<TimeEntry, SDKError>;
+    async fn get_time_entry(&self, id: Uuid) -> Result<TimeEntry, SDKError>;
+    async fn get_time_entries(&self, input: GetTimeEntriesInput) -> Result<Vec<TimeEntry>, SDKError>;
+    async fn update_time_entry(&self, id: Uuid, input: UpdateTimeEntryInput) -> Result<TimeEntry, SDKError>;
+    async fn delete_time_entry(&self, id: Uuid) -> Result<TimeEntry, SDKError>;
+
+    async fn start_timer(&self, input: StartTimerInput) -> Result<TimeEntry, SDKError>;
+    async fn stop_timer(&self, member_id: Uuid) -> Result<TimeEntry, SDKError>;
+    async fn get_running_timer(&self, member_id: Uuid) -> Result<Option<TimeEntry>, SDKError>;
+}
+
+// Entries logged by hand need an end or a duration, running entries come from
+// start_timer.
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateTimeEntryInput {
+    #[graphql(skip)]
+    pub member_id: Uuid,
+    pub task_id: Uuid,
+
+    // end minus the duration when left out
+    #[builder(setter(strip_option), default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub ended_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub duration_seconds: Option<i64>,
+
+    #[builder(setter(strip_option), default)]
+    pub note: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub billable: Option<bool>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateTimeEntryInput {
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub ended_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub note: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub billable: Option<bool>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct StartTimerInput {
+    #[graphql(skip)]
+    pub member_id: Uuid,
+    pub task_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub note: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub billable: Option<bool>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetTimeEntriesInput {
+    #[builder(setter(strip_option), default)]
+    pub member_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub billable: Option<bool>,
+
+    // entries started within the range
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+impl GetTimeEntriesInput {
+    // clauses over time_entries joined with tasks
+    pub(crate) fn compile_sql(&self) -> Vec<String> {
+        let mut and_clauses = Vec::new();
+
+        if let Some(member_id) = &self.member_id {
+            and_clauses.push(format!("time_entries.member_id = '{}'", member_id));
+        }
+
+        if let Some(task_id) = &self.task_id {
+            and_clauses.push(format!("time_entries.task_id = '{}'", task_id));
+        }
+
+        if let Some(project_id) = &self.project_id {
+            and_clauses.push(format!("tasks.project_id = '{}'", project_id));
+        }
+
+        if let Some(billable) = &self.billable {
+            and_clauses.push(format!("time_entries.billable = {}", billable));
+        }
+
+        if let Some(from) = &self.from {
+            and_clauses.push(format!("time_entries.started_at >= '{}'", from));
+        }
+
+        if let Some(to) = &self.to {
+            and_clauses.push(format!("time_entries.started_at < '{}'", to));
+        }
+
+        and_clauses
+    }
+}
+
+pub(crate) fn time_entry_from_row(row: &PgRow) -> TimeEntry {
+    let started_at: DateTime<Utc> = row.get("started_at");
+    let ended_at: Option<DateTime<Utc>> = row.get("ended_at");
+
+    TimeEntry {
+        id: row.get("id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        member_id: row.get("member_id"),
+        task_id: row.get("task_id"),
+        started_at,
+        ended_at,
+        duration_seconds: ended_at.map(|ended_at| (ended_at - started_at).num_seconds()),
+        note: row.get("note"),
+        billable: row.get("billable"),
+    }
+}
+
+fn is_running_timer_conflict(error: &sqlx::Error) -> bool {
+    matches!(
+        error,
+        sqlx::Error::Database(error) if error.constraint() == Some("time_entries_running_member_id_idx")
+    )
+}
+
+#[async_trait]
+impl TimeEntryOperations for SDKEngine {
+    async fn create_time_entry(&self, input: CreateTimeEntryInput) -> Result<TimeEntry, SDKError> {
+        let duration = input
+            .duration_seconds
+            .map(|seconds| Duration::try_seconds(seconds).unwrap_or_default());
+
+        let (started_at, ended_at) = match (input.started_at, input.ended_at, duration) {
+            (Some(started_at), Some(ended_at), _) => (started_at, ended_at),
+            (Some(started_at), None, Some(duration)) => (started_at, started_at + duration),
+            (None, Some(ended_at), Some(duration)) => (ended_at - duration, ended_at),
+            (None, None, Some(duration)) => {
+                let ended_at = Utc::now();
+
+                (ended_at - duration, ended_at)
+            }
+            _ => {
+                return Err(SDKError::InvalidTimeEntry(
+                    "an end or a duration is required".to_string(),
+                ))
+            }
+        };
+
+        if ended_at < started_at {
+            return Err(SDKError::InvalidTimeEntry("it must end after it starts".to_string()));
+        }
+
+        let time_entry_info = sqlx::query(
+            r#"
+            INSERT INTO time_entries (member_id, task_id, started_at, ended_at, note, billable)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(input.member_id)
+        .bind(input.task_id)
+        .bind(started_at)
+        .bind(ended_at)
+        .bind(input.note)
+        .bind(input.billable.unwrap_or(true))
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(time_entry_from_row(&time_entry_info))
+    }
+
+    async fn get_time_entry(&self, id: Uuid) -> Result<TimeEntry, SDKError> {
+        let time_entry_info = sqlx::query("SELECT * FROM time_entries WHERE id = $1")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(time_entry_from_row(&time_entry_info))
+    }
+
+    async fn get_time_entries(&self, input: GetTimeEntriesInput) -> Result<Vec<TimeEntry>, SDKError> {
+        let and_clauses = input.compile_sql();
+
+        let mut query =
+            "SELECT time_entries.* FROM time_entries JOIN tasks ON tasks.id = time_entries.task_id ".to_string();
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY time_entries.started_at DESC ");
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let time_entries_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(time_entries_info.iter().map(time_entry_from_row).collect())
+    }
+
+    async fn update_time_entry(&self, id: Uuid, input: UpdateTimeEntryInput) -> Result<TimeEntry, SDKError> {
+        let time_entry = self.get_time_entry(id).await?;
+
+        let started_at = input.started_at.unwrap_or(time_entry.started_at);
+
+        if input
+            .ended_at
+            .or(time_entry.ended_at)
+            .is_some_and(|ended_at| ended_at < started_at)
+        {
+            return Err(SDKError::InvalidTimeEntry("it must end after it starts".to_string()));
+        }
+
+        let time_entry_info = sqlx::query(
+            r#"
+            UPDATE time_entries
+            SET
+                task_id = COALESCE($1, task_id),
+                started_at = $2,
+                ended_at = COALESCE($3, ended_at),
+                note = COALESCE($4, note),
+                billable = COALESCE($5, billable)
+            WHERE id = $6
+            RETURNING *
+            "#,
+        )
+        .bind(input.task_id)
+        .bind(started_at)
+        .bind(input.ended_at)
+        .bind(input.note)
+        .bind(input.billable)
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(time_entry_from_row(&time_entry_info))
+    }
+
+    async fn delete_time_entry(&self, id: Uuid) -> Result<TimeEntry, SDKError> {
+        let time_entry_info = sqlx::query("DELETE FROM time_entries WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(time_entry_from_row(&time_entry_info))
+    }
+
+    // The running timer index keeps a second timer out, even for concurrent starts.
+    async fn start_timer(&self, input: StartTimerInput) -> Result<TimeEntry, SDKError> {
+        let time_entry_info = sqlx::query(
+            r#"
+            INSERT INTO time_entries (member_id, task_id, started_at, note, billable)
+            VALUES ($1, $2, now(), $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.member_id)
+        .bind(input.task_id)
+        .bind(input.note)
+        .bind(input.billable.unwrap_or(true))
+        .fetch_one(self.db_pool.as_ref())
+        .await
+        .map_err(|error| {
+            if is_running_timer_conflict(&error) {
+                SDKError::TimerAlreadyRunning
+            } else {
+                SDKError::from(error)
+            }
+        })?;
+
+        Ok(time_entry_from_row(&time_entry_info))
+    }
+
+    async fn stop_timer(&self, member_id: Uuid) -> Result<TimeEntry, SDKError> {
+        let time_entry_info = sqlx::query(
+            r#"
+            UPDATE time_entries
+            SET ended_at = greatest(now(), started_at)
+            WHERE member_id = $1 AND ended_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(member_id)
+        .fetch_optional(self.db_pool.as_ref())
+        .await?
+        .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(time_entry_from_row(&time_entry_info))
+    }
+
+    async fn get_running_timer(&self, member_id: Uuid) -> Result<Option<TimeEntry>, SDKError> {
+        let time_entry_info = sqlx::query("SELECT * FROM time_entries WHERE member_id = $1 AND ended_at IS NULL")
+            .bind(member_id)
+            .fetch_optional(self.db_pool.as_ref())
+            .await?;
+
+        Ok(time_entry_info.as_ref().map(time_entry_from_row))
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, tasks::task::Task},
+};
+
+use super::time_entry::TimeEntry;
+
+#[async_trait]
+pub trait TimeEntryRelations {
+    async fn member(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Task, SDKError>;
+}
+
+#[async_trait]
+impl TimeEntryRelations for TimeEntry {
+    async fn member(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders
+            .member_loader
+            .load_one(self.member_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Task, SDKError> {
+        let data = loaders
+            .task_loader
+            .load_one(self.task_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        Ok(data)
+    }
+}
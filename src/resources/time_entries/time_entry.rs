@@ -0,0 +1,53 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTimeEntry")]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub member_id: Uuid,
+    pub task_id: Uuid,
+
+    pub started_at: DateTime<Utc>,
+    // none while the timer runs
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+
+    pub note: Option<String>,
+    pub billable: bool,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTimesheetRow")]
+pub struct TimesheetRow {
+    // task, project or member id, or the first day of the period
+    pub key: Option<String>,
+    pub label: Option<String>,
+
+    pub entries: i64,
+    pub total_seconds: i64,
+    pub billable_seconds: i64,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum TimesheetGroupBy {
+    #[default]
+    Task,
+    Project,
+    Member,
+    Day,
+    Week,
+    Month,
+}
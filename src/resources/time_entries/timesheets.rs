@@ -0,0 +1,197 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::{
+    operations::GetTimeEntriesInput,
+    time_entry::{TimesheetGroupBy, TimesheetRow},
+};
+
+#[async_trait]
+pub trait TimesheetOperations {
+    async fn get_timesheet(&self, input: GetTimesheetInput) -> Result<Vec<TimesheetRow>, SDKError>;
+    async fn export_timesheet_csv(&self, input: GetTimeEntriesInput) -> Result<String, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetTimesheetInput {
+    #[builder(setter(strip_option), default)]
+    pub group_by: Option<TimesheetGroupBy>,
+
+    #[builder(setter(strip_option), default)]
+    pub member_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub billable: Option<bool>,
+
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+fn csv_field(value: &str) -> String {
+    // spreadsheets run fields starting like a formula, titles and notes are user input
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[async_trait]
+impl TimesheetOperations for SDKEngine {
+    // Running timers count up to now.
+    async fn get_timesheet(&self, input: GetTimesheetInput) -> Result<Vec<TimesheetRow>, SDKError> {
+        let and_clauses = GetTimeEntriesInput {
+            member_id: input.member_id,
+            task_id: input.task_id,
+            project_id: input.project_id,
+            billable: input.billable,
+            from: input.from,
+            to: input.to,
+            limit: None,
+            offset: None,
+        }
+        .compile_sql();
+
+        let (key, label) = match input.group_by.unwrap_or_default() {
+            TimesheetGroupBy::Task => ("time_entries.task_id::text", "tasks.title"),
+            TimesheetGroupBy::Project => ("tasks.project_id::text", "projects.name"),
+            TimesheetGroupBy::Member => ("time_entries.member_id::text", "members.name"),
+            TimesheetGroupBy::Day => (
+                "to_char(date_trunc('day', time_entries.started_at AT TIME ZONE 'UTC'), 'YYYY-MM-DD')",
+                "NULL::text",
+            ),
+            TimesheetGroupBy::Week => (
+                "to_char(date_trunc('week', time_entries.started_at AT TIME ZONE 'UTC'), 'YYYY-MM-DD')",
+                "NULL::text",
+            ),
+            TimesheetGroupBy::Month => (
+                "to_char(date_trunc('month', time_entries.started_at AT TIME ZONE 'UTC'), 'YYYY-MM-DD')",
+                "NULL::text",
+            ),
+        };
+
+        let mut query = format!(
+            r#"
+            SELECT
+                {key} AS key,
+                min({label}) AS label,
+                count(*) AS entries,
+                sum(extract(epoch FROM COALESCE(time_entries.ended_at, now()) - time_entries.started_at))::bigint AS total_seconds,
+                COALESCE(sum(extract(epoch FROM COALESCE(time_entries.ended_at, now()) - time_entries.started_at))
+                    FILTER (WHERE time_entries.billable), 0)::bigint AS billable_seconds
+            FROM time_entries
+            JOIN tasks ON tasks.id = time_entries.task_id
+            JOIN members ON members.id = time_entries.member_id
+            LEFT JOIN projects ON projects.id = tasks.project_id
+            "#,
+        );
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("GROUP BY 1 ORDER BY 1");
+
+        let rows = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TimesheetRow {
+                label: row.get::<Option<String>, _>("label").or(row.get("key")),
+                key: row.get("key"),
+                entries: row.get("entries"),
+                total_seconds: row.get("total_seconds"),
+                billable_seconds: row.get("billable_seconds"),
+            })
+            .collect())
+    }
+
+    // One line per entry, oldest first. Limit and offset are ignored so the
+    // export holds the whole range.
+    async fn export_timesheet_csv(&self, input: GetTimeEntriesInput) -> Result<String, SDKError> {
+        let and_clauses = input.compile_sql();
+
+        let mut query = r#"
+            SELECT
+                time_entries.started_at,
+                time_entries.ended_at,
+                time_entries.note,
+                time_entries.billable,
+                members.name AS member_name,
+                members.email AS member_email,
+                projects.name AS project_name,
+                tasks.identifier AS task_identifier,
+                tasks.title AS task_title
+            FROM time_entries
+            JOIN tasks ON tasks.id = time_entries.task_id
+            JOIN members ON members.id = time_entries.member_id
+            LEFT JOIN projects ON projects.id = tasks.project_id
+            "#
+        .to_string();
+
+        if !and_clauses.is_empty() {
+            query.push_str(format!("WHERE {} ", and_clauses.join(" AND ")).as_str());
+        }
+
+        query.push_str("ORDER BY time_entries.started_at");
+
+        let rows = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        let mut csv = "date,member,email,project,task,title,started_at,ended_at,hours,billable,note\n".to_string();
+
+        for row in rows.iter() {
+            let started_at: DateTime<Utc> = row.get("started_at");
+            let ended_at: Option<DateTime<Utc>> = row.get("ended_at");
+
+            let hours = ended_at
+                .map(|ended_at| format!("{:.2}", (ended_at - started_at).num_seconds() as f64 / 3600.0))
+                .unwrap_or_default();
+
+            let fields = [
+                started_at.format("%Y-%m-%d").to_string(),
+                row.get("member_name"),
+                row.get("member_email"),
+                row.get::<Option<String>, _>("project_name").unwrap_or_default(),
+                row.get::<Option<String>, _>("task_identifier").unwrap_or_default(),
+                row.get("task_title"),
+                started_at.to_rfc3339(),
+                ended_at.map(|ended_at| ended_at.to_rfc3339()).unwrap_or_default(),
+                hours,
+                row.get::<bool, _>("billable").to_string(),
+                row.get::<Option<String>, _>("note").unwrap_or_default(),
+            ];
+
+            csv.push_str(
+                fields
+                    .iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<String>>()
+                    .join(",")
+                    .as_str(),
+            );
+            csv.push('\n');
+        }
+
+        Ok(csv)
+    }
+}
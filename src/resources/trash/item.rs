@@ -0,0 +1,19 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::resources::changes::change::ChangeResourceType;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTrashItem")]
+pub struct TrashItem {
+    pub id: Uuid,
+    pub resource_type: ChangeResourceType,
+    pub name: String,
+
+    pub deleted_at: DateTime<Utc>,
+}
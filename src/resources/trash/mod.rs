@@ -0,0 +1,3 @@
+pub mod item;
+pub mod operations;
+pub mod purger;
@@ -0,0 +1,317 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{error::ErrorKind, Row};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{
+        assets::{asset::Asset, operations::AssetCrudOperations},
+        automations::{automation::AutomationRule, operations::AutomationOperations},
+        changes::change::ChangeResourceType,
+        cycles::{cycle::Cycle, operations::CycleCrudOperations},
+        labels::{label::Label, operations::LabelCrudOperations},
+        members::{member::Member, operations::MemberCrudOperations},
+        milestones::{milestone::Milestone, operations::MilestoneCrudOperations},
+        projects::{operations::ProjectCrudOperations, project::Project},
+        tasks::{operations::TaskCrudOperations, task::Task},
+        teams::{operations::TeamCrudOperations, team::Team},
+        templates::{operations::TemplateOperations, template::Template},
+    },
+};
+
+use super::item::TrashItem;
+
+#[async_trait]
+pub trait TrashOperations {
+    async fn get_trash(&self, input: GetTrashInput) -> Result<Vec<TrashItem>, SDKError>;
+
+    async fn restore_task(&self, id: Uuid) -> Result<Task, SDKError>;
+    async fn restore_project(&self, id: Uuid) -> Result<Project, SDKError>;
+    async fn restore_member(&self, id: Uuid) -> Result<Member, SDKError>;
+    async fn restore_team(&self, id: Uuid) -> Result<Team, SDKError>;
+    async fn restore_label(&self, id: Uuid) -> Result<Label, SDKError>;
+    async fn restore_asset(&self, id: Uuid) -> Result<Asset, SDKError>;
+    async fn restore_milestone(&self, id: Uuid) -> Result<Milestone, SDKError>;
+    async fn restore_cycle(&self, id: Uuid) -> Result<Cycle, SDKError>;
+    async fn restore_template(&self, id: Uuid) -> Result<Template, SDKError>;
+    async fn restore_automation_rule(&self, id: Uuid) -> Result<AutomationRule, SDKError>;
+
+    async fn purge_trash(&self, before: DateTime<Utc>) -> Result<i64, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetTrashInput {
+    #[builder(setter(strip_option), default)]
+    pub resource_type: Option<ChangeResourceType>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+impl SDKEngine {
+    async fn restore_from_trash(&self, table: &str, id: Uuid) -> Result<(), SDKError> {
+        let restored = sqlx::query(
+            format!(
+                "UPDATE {} SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+                table
+            )
+            .as_str(),
+        )
+        .bind(id)
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        if restored.rows_affected() == 0 {
+            return Err(SDKError::ResourceNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TrashOperations for SDKEngine {
+    async fn get_trash(&self, input: GetTrashInput) -> Result<Vec<TrashItem>, SDKError> {
+        let items = sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT id, 'Tasks' AS resource_type, title AS name, deleted_at FROM tasks WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Projects', name, deleted_at FROM projects WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Members', name, deleted_at FROM members WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Teams', name, deleted_at FROM teams WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Labels', name, deleted_at FROM labels WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Assets', name, deleted_at FROM assets WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Milestones', name, deleted_at FROM milestones WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Cycles', COALESCE(name, 'Cycle ' || number), deleted_at FROM cycles WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'Templates', name, deleted_at FROM templates WHERE deleted_at IS NOT NULL
+                UNION ALL
+                SELECT id, 'AutomationRules', name, deleted_at FROM automation_rules WHERE deleted_at IS NOT NULL
+            ) AS trash
+            WHERE $1::text IS NULL OR resource_type = $1
+            ORDER BY deleted_at DESC, id
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(input.resource_type.map(|resource_type| resource_type.to_string()))
+        .bind(input.limit.map(i64::from))
+        .bind(input.offset.unwrap_or(0) as i64)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(items
+            .iter()
+            .filter_map(|item| {
+                Some(TrashItem {
+                    id: item.get("id"),
+                    resource_type: ChangeResourceType::from_str(item.get::<&str, _>("resource_type")).ok()?,
+                    name: item.get("name"),
+                    deleted_at: item.get("deleted_at"),
+                })
+            })
+            .collect())
+    }
+
+    // Subtasks trashed along with the task come back with it, the ones trashed
+    // on their own before stay in the trash.
+    async fn restore_task(&self, id: Uuid) -> Result<Task, SDKError> {
+        let restored = sqlx::query(
+            r#"
+            WITH RECURSIVE restored AS (
+                SELECT id, deleted_at FROM tasks WHERE id = $1 AND deleted_at IS NOT NULL
+                UNION
+                SELECT tasks.id, tasks.deleted_at FROM tasks
+                JOIN restored ON tasks.parent_id = restored.id
+                WHERE tasks.deleted_at = restored.deleted_at
+            )
+            UPDATE tasks SET deleted_at = NULL
+            FROM restored
+            WHERE tasks.id = restored.id
+            "#,
+        )
+        .bind(id)
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        if restored.rows_affected() == 0 {
+            return Err(SDKError::ResourceNotFound);
+        }
+
+        self.get_task(id).await
+    }
+
+    // Tasks trashed along with the project come back with it.
+    async fn restore_project(&self, id: Uuid) -> Result<Project, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let deleted_at: Option<DateTime<Utc>> =
+            sqlx::query_scalar("SELECT deleted_at FROM projects WHERE id = $1 AND deleted_at IS NOT NULL FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        let Some(deleted_at) = deleted_at else {
+            return Err(SDKError::ResourceNotFound);
+        };
+
+        sqlx::query("UPDATE projects SET deleted_at = NULL WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            WITH RECURSIVE restored AS (
+                SELECT id FROM tasks WHERE project_id = $1 AND deleted_at = $2
+                UNION
+                SELECT tasks.id FROM tasks JOIN restored ON tasks.parent_id = restored.id
+                WHERE tasks.deleted_at = $2
+            )
+            UPDATE tasks SET deleted_at = NULL
+            FROM restored
+            WHERE tasks.id = restored.id
+            "#,
+        )
+        .bind(id)
+        .bind(deleted_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_project(id).await
+    }
+
+    async fn restore_member(&self, id: Uuid) -> Result<Member, SDKError> {
+        self.restore_from_trash("members", id).await?;
+
+        self.get_member(id).await
+    }
+
+    async fn restore_team(&self, id: Uuid) -> Result<Team, SDKError> {
+        self.restore_from_trash("teams", id).await?;
+
+        self.get_team(id).await
+    }
+
+    async fn restore_label(&self, id: Uuid) -> Result<Label, SDKError> {
+        self.restore_from_trash("labels", id).await?;
+
+        self.get_label(id).await
+    }
+
+    async fn restore_asset(&self, id: Uuid) -> Result<Asset, SDKError> {
+        self.restore_from_trash("assets", id).await?;
+
+        self.get_asset(id).await
+    }
+
+    async fn restore_milestone(&self, id: Uuid) -> Result<Milestone, SDKError> {
+        self.restore_from_trash("milestones", id).await?;
+
+        self.get_milestone(id).await
+    }
+
+    async fn restore_cycle(&self, id: Uuid) -> Result<Cycle, SDKError> {
+        self.restore_from_trash("cycles", id).await?;
+
+        self.get_cycle(id).await
+    }
+
+    async fn restore_template(&self, id: Uuid) -> Result<Template, SDKError> {
+        self.restore_from_trash("templates", id).await?;
+
+        self.get_template(id).await
+    }
+
+    async fn restore_automation_rule(&self, id: Uuid) -> Result<AutomationRule, SDKError> {
+        self.restore_from_trash("automation_rules", id).await?;
+
+        self.get_automation_rule(id).await
+    }
+
+    // Deletes for good everything trashed before `before`, returns how many rows
+    // were purged. Rows pointing to purged ones go with them through their
+    // foreign keys, members still referenced by shared records or the history
+    // are kept.
+    async fn purge_trash(&self, before: DateTime<Utc>) -> Result<i64, SDKError> {
+        let mut purged = 0;
+
+        // tasks restored on their own outlive their purged project, without it
+        sqlx::query(
+            r#"
+            UPDATE tasks SET project_id = NULL
+            FROM projects
+            WHERE tasks.project_id = projects.id
+                AND projects.deleted_at < $1
+                AND (tasks.deleted_at IS NULL OR tasks.deleted_at >= $1)
+            "#,
+        )
+        .bind(before)
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        for table in [
+            "tasks",
+            "projects",
+            "teams",
+            "labels",
+            "assets",
+            "milestones",
+            "cycles",
+            "templates",
+            "automation_rules",
+        ] {
+            let deleted = sqlx::query(format!("DELETE FROM {} WHERE deleted_at < $1", table).as_str())
+                .bind(before)
+                .execute(self.db_pool.as_ref())
+                .await?;
+
+            purged += deleted.rows_affected() as i64;
+        }
+
+        let member_ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM members WHERE deleted_at < $1")
+            .bind(before)
+            .fetch_all(self.db_pool.as_ref())
+            .await?;
+
+        // members that owned shared records are restricted by the foreign keys, the
+        // ones owning tasks, projects and the like can't leave them without an
+        // owner, both stay in the trash
+        for member_id in member_ids {
+            match sqlx::query("DELETE FROM members WHERE id = $1")
+                .bind(member_id)
+                .execute(self.db_pool.as_ref())
+                .await
+            {
+                Ok(deleted) => purged += deleted.rows_affected() as i64,
+                Err(sqlx::Error::Database(err))
+                    if matches!(err.kind(), ErrorKind::ForeignKeyViolation | ErrorKind::NotNullViolation) =>
+                {
+                    continue
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(purged)
+    }
+}
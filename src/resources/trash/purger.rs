@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        clock::Clock,
+        runner::{spawn_periodic, RunnerHandle},
+    },
+    errors::sdk::SDKError,
+};
+
+use super::operations::TrashOperations;
+
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+#[derive(Clone)]
+pub struct TrashPurger {
+    engine: Arc<SDKEngine>,
+    clock: Arc<dyn Clock>,
+    retention: Duration,
+}
+
+impl TrashPurger {
+    pub fn new(engine: Arc<SDKEngine>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            engine,
+            clock,
+            retention: Duration::try_days(DEFAULT_RETENTION_DAYS).unwrap_or_default(),
+        }
+    }
+
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub async fn run_once(&self) -> Result<i64, SDKError> {
+        self.engine.purge_trash(self.clock.now() - self.retention).await
+    }
+
+    pub fn spawn(self, interval: std::time::Duration) -> RunnerHandle {
+        spawn_periodic(interval, move || {
+            let purger = self.clone();
+
+            async move {
+                if let Err(err) = purger.run_once().await {
+                    println!("Error purging the trash: {:?}", err);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+
+use super::workflow::{WorkflowState, WorkflowStateCategory};
+
+pub struct WorkflowStateLoader(Arc<SDKEngine>);
+
+impl WorkflowStateLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for WorkflowStateLoader {
+    type Value = WorkflowState;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let states = sqlx::query!(
+            r#"
+            SELECT * FROM workflow_states WHERE id  = ANY($1)
+            "#,
+            &keys
+        )
+        .fetch_all(&*self.0.db_pool)
+        .await
+        .unwrap();
+
+        let states_map: HashMap<Uuid, WorkflowState> = states
+            .iter()
+            .map(|state| {
+                (
+                    state.id,
+                    WorkflowState {
+                        id: state.id,
+                        created_at: state.created_at,
+                        updated_at: state.updated_at,
+                        workflow_id: state.workflow_id,
+                        name: state.name.clone(),
+                        category: WorkflowStateCategory::from_str(&state.category).unwrap_or_default(),
+                        position: state.position,
+                        color: state.color.clone(),
+                        is_default: state.is_default,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(states_map)
+    }
+}
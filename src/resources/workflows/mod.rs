@@ -0,0 +1,3 @@
+pub mod loader;
+pub mod operations;
+pub mod workflow;
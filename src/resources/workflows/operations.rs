@@ -0,0 +1,751 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError, resources::tasks::task::TaskStatus,
+};
+
+use super::workflow::{Workflow, WorkflowState, WorkflowStateCategory, WorkflowTransition};
+
+#[async_trait]
+pub trait WorkflowCrudOperations {
+    async fn create_workflow(&self, input: CreateWorkflowInput) -> Result<Workflow, SDKError>;
+    async fn get_workflow(&self, id: Uuid) -> Result<Workflow, SDKError>;
+    async fn get_project_workflow(&self, project_id: Uuid) -> Result<Option<Workflow>, SDKError>;
+    async fn get_workflows(&self, input: GetWorkflowsInput) -> Result<Vec<Workflow>, SDKError>;
+    async fn update_workflow(&self, id: Uuid, input: UpdateWorkflowInput) -> Result<Workflow, SDKError>;
+    async fn delete_workflow(&self, id: Uuid) -> Result<Workflow, SDKError>;
+
+    async fn create_workflow_state(&self, input: CreateWorkflowStateInput) -> Result<WorkflowState, SDKError>;
+    async fn get_workflow_states(&self, workflow_id: Uuid) -> Result<Vec<WorkflowState>, SDKError>;
+    async fn update_workflow_state(&self, id: Uuid, input: UpdateWorkflowStateInput)
+        -> Result<WorkflowState, SDKError>;
+    async fn delete_workflow_state(&self, id: Uuid) -> Result<WorkflowState, SDKError>;
+
+    async fn create_workflow_transition(
+        &self,
+        input: CreateWorkflowTransitionInput,
+    ) -> Result<WorkflowTransition, SDKError>;
+    async fn get_workflow_transitions(&self, workflow_id: Uuid) -> Result<Vec<WorkflowTransition>, SDKError>;
+    async fn delete_workflow_transition(&self, id: Uuid) -> Result<WorkflowTransition, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateWorkflowInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub project_id: Uuid,
+    pub name: String,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateWorkflowInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetWorkflowsInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetWorkflowsWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetWorkflowsWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub owner_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetWorkflowsWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetWorkflowsWhere>>,
+}
+
+impl GetWorkflowsWhere {
+    pub fn compile_sql(&self) -> String {
+        let mut and_clauses = Vec::new();
+        let mut or_clauses = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            and_clauses.push(format!(
+                "id = ANY(array[{}]::uuid[])",
+                ids.iter()
+                    .map(|x| format!("'{}'", x))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            and_clauses.push(format!("owner_id = '{}'", owner_id));
+        }
+        if let Some(project_id) = &self.project_id {
+            and_clauses.push(format!("project_id = '{}'", project_id));
+        }
+        if let Some(name) = &self.name {
+            and_clauses.push(format!("name = '{}'", name));
+        }
+
+        if let Some(ands) = &self._and {
+            for and in ands {
+                and_clauses.push(and.compile_sql());
+            }
+        }
+        if let Some(ors) = &self._or {
+            for or in ors {
+                or_clauses.push(or.compile_sql());
+            }
+        }
+
+        let mut where_clause = String::new();
+        if !and_clauses.is_empty() {
+            where_clause.push_str(&format!("({})", and_clauses.join(" AND ")));
+        }
+        if !or_clauses.is_empty() {
+            if !where_clause.is_empty() {
+                where_clause.push_str(" OR ");
+            }
+            where_clause.push_str(&format!("({})", or_clauses.join(" OR ")));
+        }
+
+        where_clause
+    }
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateWorkflowStateInput {
+    pub workflow_id: Uuid,
+    pub name: String,
+    pub category: WorkflowStateCategory,
+
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub color: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub is_default: Option<bool>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateWorkflowStateInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub category: Option<WorkflowStateCategory>,
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub color: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub is_default: Option<bool>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateWorkflowTransitionInput {
+    pub from_state_id: Uuid,
+    pub to_state_id: Uuid,
+}
+
+// the built-in statuses a new workflow starts with, so custom states are added on top of them
+const DEFAULT_WORKFLOW_STATES: [(TaskStatus, WorkflowStateCategory); 5] = [
+    (TaskStatus::Backlog, WorkflowStateCategory::Unstarted),
+    (TaskStatus::ToDo, WorkflowStateCategory::Unstarted),
+    (TaskStatus::InProgress, WorkflowStateCategory::Started),
+    (TaskStatus::Done, WorkflowStateCategory::Completed),
+    (TaskStatus::Canceled, WorkflowStateCategory::Canceled),
+];
+
+impl SDKEngine {
+    // state a task should end up in; `None` means the project has no workflow and the plain status enum applies
+    pub(crate) async fn resolve_task_state(
+        &self,
+        project_id: Option<Uuid>,
+        current_state_id: Option<Uuid>,
+        current_status: Option<TaskStatus>,
+        status: Option<TaskStatus>,
+        state_id: Option<Uuid>,
+    ) -> Result<Option<(WorkflowState, TaskStatus)>, SDKError> {
+        let workflow = match project_id {
+            Some(project_id) => self.get_project_workflow(project_id).await?,
+            None => None,
+        };
+
+        let Some(workflow) = workflow else {
+            if state_id.is_some() {
+                return Err(SDKError::InvalidWorkflowState);
+            }
+
+            return Ok(None);
+        };
+
+        let states = self.get_workflow_states(workflow.id).await?;
+
+        if states.is_empty() {
+            if state_id.is_some() {
+                return Err(SDKError::InvalidWorkflowState);
+            }
+
+            return Ok(None);
+        }
+
+        let current = current_state_id.and_then(|id| states.iter().find(|s| s.id == id));
+
+        let target = if let Some(state_id) = state_id {
+            states
+                .iter()
+                .find(|s| s.id == state_id)
+                .ok_or(SDKError::InvalidWorkflowState)?
+        } else if let Some(status) = status {
+            let category = WorkflowStateCategory::from(status);
+
+            // prefer the built-in state named after the status, then stay put if the category already fits
+            match states
+                .iter()
+                .find(|s| s.category == category && s.name == status.to_string())
+            {
+                Some(named) => named,
+                None => match current {
+                    Some(current) if current.category == category => current,
+                    _ => states
+                        .iter()
+                        .find(|s| s.category == category)
+                        .ok_or(SDKError::InvalidWorkflowState)?,
+                },
+            }
+        } else if let Some(current) = current {
+            current
+        } else {
+            let category = WorkflowStateCategory::from(current_status.unwrap_or_default());
+
+            states
+                .iter()
+                .find(|s| s.category == category && s.is_default)
+                .or_else(|| states.iter().find(|s| s.category == category))
+                .or_else(|| states.iter().find(|s| s.is_default))
+                .unwrap_or(&states[0])
+        };
+
+        if let Some(current) = current {
+            if current.id != target.id {
+                let transitions = self.get_workflow_transitions(workflow.id).await?;
+
+                // a workflow without transitions allows moving between any of its states
+                if !transitions.is_empty()
+                    && !transitions
+                        .iter()
+                        .any(|t| t.from_state_id == current.id && t.to_state_id == target.id)
+                {
+                    return Err(SDKError::WorkflowTransitionNotAllowed);
+                }
+            }
+        }
+
+        // keep the finer grained status when it already fits the state category
+        let resolved_status = match (TaskStatus::from_str(&target.name), status.or(current_status)) {
+            (Ok(named), _) if WorkflowStateCategory::from(named) == target.category => named,
+            (_, Some(status)) if WorkflowStateCategory::from(status) == target.category => status,
+            _ => target.category.task_status(),
+        };
+
+        Ok(Some((target.clone(), resolved_status)))
+    }
+}
+
+#[async_trait]
+impl WorkflowCrudOperations for SDKEngine {
+    async fn create_workflow(&self, input: CreateWorkflowInput) -> Result<Workflow, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let workflow_info = sqlx::query!(
+            r#"
+            INSERT INTO workflows (owner_id, project_id, name, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            input.owner_id,
+            input.project_id,
+            input.name,
+            input.description,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (position, (status, category)) in DEFAULT_WORKFLOW_STATES.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO workflow_states (workflow_id, name, category, position, is_default)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+                workflow_info.id,
+                status.to_string(),
+                category.to_string(),
+                position as i32,
+                position == 0,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // tasks already in the project start out in the state matching their status
+        sqlx::query!(
+            r#"
+            UPDATE tasks
+            SET state_id = workflow_states.id
+            FROM workflow_states
+            WHERE tasks.project_id = $1
+                AND workflow_states.workflow_id = $2
+                AND workflow_states.name = CASE
+                    WHEN COALESCE(tasks.status, 'None') IN ('None', 'Draft') THEN 'Backlog'
+                    ELSE tasks.status
+                END
+            "#,
+            input.project_id,
+            workflow_info.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+            name: workflow_info.name,
+            description: workflow_info.description,
+        })
+    }
+
+    async fn get_workflow(&self, id: Uuid) -> Result<Workflow, SDKError> {
+        let workflow_info = sqlx::query!(
+            r#"
+            SELECT * FROM workflows
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+            name: workflow_info.name,
+            description: workflow_info.description,
+        })
+    }
+
+    async fn get_project_workflow(&self, project_id: Uuid) -> Result<Option<Workflow>, SDKError> {
+        let workflow_info = sqlx::query!(
+            r#"
+            SELECT * FROM workflows
+            WHERE project_id = $1
+            "#,
+            project_id,
+        )
+        .fetch_optional(self.db_pool.as_ref())
+        .await?;
+
+        Ok(workflow_info.map(|workflow_info| Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+            name: workflow_info.name,
+            description: workflow_info.description,
+        }))
+    }
+
+    async fn get_workflows(&self, input: GetWorkflowsInput) -> Result<Vec<Workflow>, SDKError> {
+        let mut query = "SELECT * FROM workflows ".to_string();
+
+        if let Some(filter) = input.filter {
+            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
+        }
+
+        if let Some(sort_by) = input.sort_by {
+            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
+        }
+
+        if let Some(sort_order) = input.sort_order {
+            query.push_str(format!("{} ", sort_order).as_str());
+        }
+
+        if let Some(limit) = input.limit {
+            query.push_str(format!("LIMIT {} ", limit).as_str());
+        }
+
+        if let Some(offset) = input.offset {
+            query.push_str(format!("OFFSET {} ", offset).as_str());
+        }
+
+        let workflows_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
+
+        let workflows = workflows_info
+            .into_iter()
+            .map(|workflow_info| Workflow {
+                id: workflow_info.get("id"),
+                created_at: workflow_info.get("created_at"),
+                updated_at: workflow_info.get("updated_at"),
+                owner_id: workflow_info.get("owner_id"),
+                project_id: workflow_info.get("project_id"),
+                name: workflow_info.get("name"),
+                description: workflow_info.get("description"),
+            })
+            .collect();
+
+        Ok(workflows)
+    }
+
+    async fn update_workflow(&self, id: Uuid, input: UpdateWorkflowInput) -> Result<Workflow, SDKError> {
+        let workflow_info = sqlx::query!(
+            r#"
+            UPDATE workflows
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description)
+            WHERE id = $3
+            RETURNING *
+            "#,
+            input.name,
+            input.description,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+            name: workflow_info.name,
+            description: workflow_info.description,
+        })
+    }
+
+    async fn delete_workflow(&self, id: Uuid) -> Result<Workflow, SDKError> {
+        let workflow_info = sqlx::query!(
+            r#"
+            DELETE FROM workflows WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+            name: workflow_info.name,
+            description: workflow_info.description,
+        })
+    }
+
+    async fn create_workflow_state(&self, input: CreateWorkflowStateInput) -> Result<WorkflowState, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+        let is_default = input.is_default.unwrap_or(false);
+
+        if is_default {
+            sqlx::query!(
+                r#"
+                UPDATE workflow_states SET is_default = false WHERE workflow_id = $1
+                "#,
+                input.workflow_id,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let state_info = sqlx::query!(
+            r#"
+            INSERT INTO workflow_states (workflow_id, name, category, position, color, is_default)
+            VALUES ($1, $2, $3, COALESCE($4, (SELECT COALESCE(MAX(position) + 1, 0) FROM workflow_states WHERE workflow_id = $1)), $5, $6)
+            RETURNING *
+            "#,
+            input.workflow_id,
+            input.name,
+            input.category.to_string(),
+            input.position,
+            input.color,
+            is_default,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(WorkflowState {
+            id: state_info.id,
+            created_at: state_info.created_at,
+            updated_at: state_info.updated_at,
+            workflow_id: state_info.workflow_id,
+            name: state_info.name,
+            category: WorkflowStateCategory::from_str(&state_info.category).unwrap_or_default(),
+            position: state_info.position,
+            color: state_info.color,
+            is_default: state_info.is_default,
+        })
+    }
+
+    async fn get_workflow_states(&self, workflow_id: Uuid) -> Result<Vec<WorkflowState>, SDKError> {
+        let states_info = sqlx::query!(
+            r#"
+            SELECT * FROM workflow_states
+            WHERE workflow_id = $1
+            ORDER BY position, created_at
+            "#,
+            workflow_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(states_info
+            .into_iter()
+            .map(|state_info| WorkflowState {
+                id: state_info.id,
+                created_at: state_info.created_at,
+                updated_at: state_info.updated_at,
+                workflow_id: state_info.workflow_id,
+                name: state_info.name,
+                category: WorkflowStateCategory::from_str(&state_info.category).unwrap_or_default(),
+                position: state_info.position,
+                color: state_info.color,
+                is_default: state_info.is_default,
+            })
+            .collect())
+    }
+
+    async fn update_workflow_state(
+        &self,
+        id: Uuid,
+        input: UpdateWorkflowStateInput,
+    ) -> Result<WorkflowState, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        if input.is_default == Some(true) {
+            sqlx::query!(
+                r#"
+                UPDATE workflow_states SET is_default = false
+                WHERE workflow_id = (SELECT workflow_id FROM workflow_states WHERE id = $1)
+                "#,
+                id,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let state_info = sqlx::query!(
+            r#"
+            UPDATE workflow_states
+            SET
+                name = COALESCE($1, name),
+                category = COALESCE($2, category),
+                position = COALESCE($3, position),
+                color = COALESCE($4, color),
+                is_default = COALESCE($5, is_default)
+            WHERE id = $6
+            RETURNING *
+            "#,
+            input.name,
+            input.category.map(|category| category.to_string()),
+            input.position,
+            input.color,
+            input.is_default,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // tasks in a recategorized state follow it
+        if let Some(category) = input.category {
+            sqlx::query!(
+                r#"
+                UPDATE tasks SET status = $1 WHERE state_id = $2
+                "#,
+                category.task_status().to_string(),
+                id,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(WorkflowState {
+            id: state_info.id,
+            created_at: state_info.created_at,
+            updated_at: state_info.updated_at,
+            workflow_id: state_info.workflow_id,
+            name: state_info.name,
+            category: WorkflowStateCategory::from_str(&state_info.category).unwrap_or_default(),
+            position: state_info.position,
+            color: state_info.color,
+            is_default: state_info.is_default,
+        })
+    }
+
+    async fn delete_workflow_state(&self, id: Uuid) -> Result<WorkflowState, SDKError> {
+        let state_info = sqlx::query!(
+            r#"
+            DELETE FROM workflow_states WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(WorkflowState {
+            id: state_info.id,
+            created_at: state_info.created_at,
+            updated_at: state_info.updated_at,
+            workflow_id: state_info.workflow_id,
+            name: state_info.name,
+            category: WorkflowStateCategory::from_str(&state_info.category).unwrap_or_default(),
+            position: state_info.position,
+            color: state_info.color,
+            is_default: state_info.is_default,
+        })
+    }
+
+    async fn create_workflow_transition(
+        &self,
+        input: CreateWorkflowTransitionInput,
+    ) -> Result<WorkflowTransition, SDKError> {
+        let states = sqlx::query!(
+            r#"
+            SELECT DISTINCT workflow_id FROM workflow_states
+            WHERE id = ANY($1)
+            "#,
+            &[input.from_state_id, input.to_state_id],
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        // both ends must exist and belong to the same workflow
+        let [workflow] = states.as_slice() else {
+            return Err(SDKError::InvalidWorkflowState);
+        };
+
+        if input.from_state_id == input.to_state_id {
+            return Err(SDKError::InvalidWorkflowState);
+        }
+
+        let transition_info = sqlx::query!(
+            r#"
+            INSERT INTO workflow_transitions (workflow_id, from_state_id, to_state_id)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+            workflow.workflow_id,
+            input.from_state_id,
+            input.to_state_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(WorkflowTransition {
+            id: transition_info.id,
+            created_at: transition_info.created_at,
+            updated_at: transition_info.updated_at,
+            workflow_id: transition_info.workflow_id,
+            from_state_id: transition_info.from_state_id,
+            to_state_id: transition_info.to_state_id,
+        })
+    }
+
+    async fn get_workflow_transitions(&self, workflow_id: Uuid) -> Result<Vec<WorkflowTransition>, SDKError> {
+        let transitions_info = sqlx::query!(
+            r#"
+            SELECT * FROM workflow_transitions
+            WHERE workflow_id = $1
+            "#,
+            workflow_id,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(transitions_info
+            .into_iter()
+            .map(|transition_info| WorkflowTransition {
+                id: transition_info.id,
+                created_at: transition_info.created_at,
+                updated_at: transition_info.updated_at,
+                workflow_id: transition_info.workflow_id,
+                from_state_id: transition_info.from_state_id,
+                to_state_id: transition_info.to_state_id,
+            })
+            .collect())
+    }
+
+    async fn delete_workflow_transition(&self, id: Uuid) -> Result<WorkflowTransition, SDKError> {
+        let transition_info = sqlx::query!(
+            r#"
+            DELETE FROM workflow_transitions WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(WorkflowTransition {
+            id: transition_info.id,
+            created_at: transition_info.created_at,
+            updated_at: transition_info.updated_at,
+            workflow_id: transition_info.workflow_id,
+            from_state_id: transition_info.from_state_id,
+            to_state_id: transition_info.to_state_id,
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::tasks::task::TaskStatus;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKWorkflow")]
+pub struct Workflow {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub project_id: Uuid,
+
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKWorkflowState")]
+pub struct WorkflowState {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub workflow_id: Uuid,
+
+    pub name: String,
+    pub category: WorkflowStateCategory,
+    pub position: i32,
+    pub color: Option<String>,
+    pub is_default: bool,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKWorkflowTransition")]
+pub struct WorkflowTransition {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub workflow_id: Uuid,
+    pub from_state_id: Uuid,
+    pub to_state_id: Uuid,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum WorkflowStateCategory {
+    #[default]
+    Unstarted,
+    Started,
+    Completed,
+    Canceled,
+}
+
+impl WorkflowStateCategory {
+    // the status a task gets when it enters a custom state of this category
+    pub fn task_status(&self) -> TaskStatus {
+        match self {
+            WorkflowStateCategory::Unstarted => TaskStatus::ToDo,
+            WorkflowStateCategory::Started => TaskStatus::InProgress,
+            WorkflowStateCategory::Completed => TaskStatus::Done,
+            WorkflowStateCategory::Canceled => TaskStatus::Canceled,
+        }
+    }
+}
+
+impl From<TaskStatus> for WorkflowStateCategory {
+    fn from(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::None | TaskStatus::Draft | TaskStatus::Backlog | TaskStatus::ToDo => {
+                WorkflowStateCategory::Unstarted
+            }
+            TaskStatus::InProgress => WorkflowStateCategory::Started,
+            TaskStatus::Done => WorkflowStateCategory::Completed,
+            TaskStatus::Canceled => WorkflowStateCategory::Canceled,
+        }
+    }
+}